@@ -7,6 +7,11 @@ pub trait InterpolateVar: IntoView + Clone + 'static + Send + Sync {}
 
 impl<T: IntoView + Clone + 'static + Send + Sync> InterpolateVar for T {}
 
+/// Marker trait for a closure returning the declared type of a `{{ var: type }}` interpolation variable.
+pub trait InterpolateTypedVar<T>: Fn() -> T + IntoView + Clone + 'static + Send + Sync {}
+
+impl<T, F: Fn() -> T + IntoView + Clone + 'static + Send + Sync> InterpolateTypedVar<T> for F {}
+
 /// Attributes of a parsed component
 pub type Attributes = Vec<leptos::attr::any_attribute::AnyAttribute>;
 
@@ -99,3 +104,48 @@ impl<T: Into<icu_plurals::PluralOperands>, F: Fn() -> T + Clone + 'static + Send
 {
     type Count = T;
 }
+
+/// Marker trait for the count of plurals with an offset
+#[cfg(feature = "plurals")]
+pub trait InterpolatePluralOffsetCount: InterpolatePluralCount<Count: PluralCountOffset> {}
+
+#[cfg(feature = "plurals")]
+impl<F: InterpolatePluralCount<Count: PluralCountOffset>> InterpolatePluralOffsetCount for F {}
+
+/// Trait for a plural count that can be offset, the plural category is then the one of the offset count.
+#[cfg(feature = "plurals")]
+pub trait PluralCountOffset: IntoView + Clone + 'static + Send + Sync {
+    /// Subtract `offset` from the count
+    fn offset_by(self, offset: u64) -> Self;
+}
+
+#[cfg(feature = "plurals")]
+macro_rules! impl_plural_count_offset {
+    ($($t:ty),*) => {
+        $(
+            impl PluralCountOffset for $t {
+                fn offset_by(self, offset: u64) -> Self {
+                    self.saturating_sub(<$t>::try_from(offset).unwrap_or(<$t>::MAX))
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "plurals")]
+impl_plural_count_offset!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Marker trait for a type that can produce the variant of a select
+pub trait InterpolateSelect: Fn() -> Self::Variant + Clone + 'static + Send + Sync {
+    /// The returned value, the name of the selected variant
+    type Variant: AsRef<str>;
+}
+
+impl<T: AsRef<str>, F: Fn() -> T + Clone + 'static + Send + Sync> InterpolateSelect for F {
+    type Variant = T;
+}
+
+/// Marker trait for a type that can produce the `bool` of a condition
+pub trait InterpolateCondition: Fn() -> bool + Clone + 'static + Send + Sync {}
+
+impl<F: Fn() -> bool + Clone + 'static + Send + Sync> InterpolateCondition for F {}
@@ -12,9 +12,10 @@ use leptos::IntoView;
 pub use scope::*;
 
 #[doc(hidden)]
-pub trait Literal: Sized + Display + IntoView + Copy {
+pub trait Literal: Sized + Display + Copy {
     type AsStr;
     fn into_str(self) -> Self::AsStr;
+    fn into_view(self) -> impl IntoView + Copy;
 }
 
 impl Literal for &'static str {
@@ -22,6 +23,9 @@ impl Literal for &'static str {
     fn into_str(self) -> Self::AsStr {
         self
     }
+    fn into_view(self) -> impl IntoView + Copy {
+        self
+    }
 }
 
 impl Literal for bool {
@@ -32,6 +36,9 @@ impl Literal for bool {
             false => "false",
         }
     }
+    fn into_view(self) -> impl IntoView + Copy {
+        self
+    }
 }
 
 macro_rules! impl_build_lit_nums {
@@ -41,6 +48,9 @@ macro_rules! impl_build_lit_nums {
             fn into_str(self) -> Self::AsStr {
                 self.to_string()
             }
+            fn into_view(self) -> impl IntoView + Copy {
+                self
+            }
         }
     };
     ($t:ty, $($tt:tt)*) => {
@@ -51,6 +61,115 @@ macro_rules! impl_build_lit_nums {
 
 impl_build_lit_nums!(u64, i64, f64);
 
+#[cfg(any(not(feature = "dynamic_load"), feature = "ssr"))]
+type LitListTranslations = &'static [&'static str];
+#[cfg(not(any(not(feature = "dynamic_load"), feature = "ssr")))]
+type LitListTranslations = &'static [Box<str>];
+
+/// A list of strings declared as an array in the locale files.
+///
+/// Displays as its items separated by `", "`.
+#[derive(Debug, Clone, Copy)]
+pub struct LitList {
+    translations: LitListTranslations,
+    indices: &'static [usize],
+}
+
+impl LitList {
+    #[doc(hidden)]
+    pub const fn new(translations: LitListTranslations, indices: &'static [usize]) -> Self {
+        LitList {
+            translations,
+            indices,
+        }
+    }
+
+    /// Returns the number of items in the list.
+    pub const fn len(self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if the list has no items.
+    pub const fn is_empty(self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the item at `index`, or `None` if out of bounds.
+    pub fn get(self, index: usize) -> Option<&'static str> {
+        self.indices
+            .get(index)
+            .map(|&index| &*self.translations[index])
+    }
+
+    /// Returns an iterator over the items of the list.
+    pub fn iter(self) -> LitListIter {
+        LitListIter {
+            translations: self.translations,
+            indices: self.indices.iter(),
+        }
+    }
+}
+
+/// Iterator over the items of a [`LitList`].
+#[derive(Debug, Clone)]
+pub struct LitListIter {
+    translations: LitListTranslations,
+    indices: std::slice::Iter<'static, usize>,
+}
+
+impl Iterator for LitListIter {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.next().map(|&index| &*self.translations[index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for LitListIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.indices
+            .next_back()
+            .map(|&index| &*self.translations[index])
+    }
+}
+
+impl ExactSizeIterator for LitListIter {}
+
+impl IntoIterator for LitList {
+    type Item = &'static str;
+    type IntoIter = LitListIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for LitList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, item) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(item)?;
+        }
+        Ok(())
+    }
+}
+
+impl Literal for LitList {
+    type AsStr = String;
+    fn into_str(self) -> Self::AsStr {
+        self.to_string()
+    }
+    fn into_view(self) -> impl IntoView + Copy {
+        move || self.to_string()
+    }
+}
+
 #[doc(hidden)]
 #[diagnostic::on_unimplemented(
     message = "Interpolated values can't be used inside t_string/t_display without the \"interpolate_display\" option enabled."
@@ -86,8 +205,12 @@ impl<T: Literal> LitWrapper<T> {
         self
     }
 
+    pub const fn fill_defaults(self) -> Self {
+        self
+    }
+
     pub fn into_view(self) -> impl IntoView + Copy {
-        self.0
+        Literal::into_view(self.0)
     }
 
     pub fn build_string(self) -> T::AsStr {
@@ -124,6 +247,10 @@ impl<T: Literal, F: Future<Output = LitWrapper<T>>> LitWrapperFut<F> {
         self
     }
 
+    pub const fn fill_defaults(self) -> Self {
+        self
+    }
+
     pub async fn into_view(self) -> impl IntoView + Copy {
         self.0.await.into_view()
     }
@@ -154,6 +281,10 @@ impl<T: Literal> LitWrapperFut<LitWrapper<T>> {
         self
     }
 
+    pub const fn fill_defaults(self) -> Self {
+        self
+    }
+
     pub fn into_view(self) -> impl IntoView + Copy {
         self.0.into_view()
     }
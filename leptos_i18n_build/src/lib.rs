@@ -4,7 +4,9 @@
 //! This crate provide `build.rs` utilities for the `leptos_i18n` crate.
 
 pub use datamarker::FormatterOptions;
-pub use leptos_i18n_parser::parse_locales::options::{Config, FileFormat, ParseOptions, parser};
+pub use leptos_i18n_parser::parse_locales::options::{
+    Config, FileFormat, KeyMapping, ParseOptions, parser,
+};
 
 use icu_locale::LocaleFallbacker;
 use icu_provider::{DataError, DataMarkerInfo};
@@ -24,12 +26,15 @@ use std::{
     fmt::{Display, Write},
     fs::{File, create_dir_all},
     io::BufWriter,
-    path::PathBuf,
+    path::{Path, PathBuf},
     rc::Rc,
 };
 
 mod datamarker;
 pub mod options;
+pub mod parsers;
+mod xliff;
+mod xml;
 
 use crate::options::CodegenOptions;
 
@@ -39,6 +44,14 @@ pub mod formatter {
     pub use leptos_i18n_parser::utils::Key;
 }
 
+/// Module for custom parse functions.
+pub mod parse_fn {
+    pub use leptos_i18n_parser::formatters::VarBounds;
+    pub use leptos_i18n_parser::parse_locales::error::{Error, Result};
+    pub use leptos_i18n_parser::parse_locales::parsed_value::{Literal, ParsedValue};
+    pub use leptos_i18n_parser::utils::{Key, ParseContext, ParseFn};
+}
+
 #[derive(Clone)]
 enum EitherIter<A, B> {
     Iter1(A),
@@ -80,7 +93,7 @@ impl TranslationsInfos {
         Self::parse_inner(Some(dir_path.into()), cfg)
     }
 
-    /// Paths to all files containing translations.
+    /// Paths to all files containing translations, and to the directories of locales split in multiple files.
     pub fn files_paths(&self) -> Option<&[String]> {
         self.parsed_locales.tracked_files.as_deref()
     }
@@ -284,6 +297,40 @@ impl TranslationsInfos {
         Ok(())
     }
 
+    /// Export the translations of the default locale to a XLIFF 2.0 document,
+    /// with the translations of `target_locale` as targets if given.
+    ///
+    /// Each key path is exported as a `<unit>`, plurals have one `<segment>` per form,
+    /// variables and components are exported as `<ph>` and `<pc>` placeholders.
+    /// Ranges can't be represented and are skipped.
+    pub fn export_xliff(&self, target_locale: Option<&str>) -> Result<String> {
+        xliff::export(&self.parsed_locales, target_locale)
+    }
+
+    /// Same as `export_xliff` but write the document at the given path.
+    pub fn write_xliff<P: AsRef<Path>>(&self, path: P, target_locale: Option<&str>) -> Result<()> {
+        let xliff = self.export_xliff(target_locale)?;
+        std::fs::write(path, xliff)?;
+        Ok(())
+    }
+
+    /// Import a translated XLIFF 2.0 document and write the targets to the files the locale is read from in `locales_dir`,
+    /// or to a new file with the configured `FileFormat`. Fluent and custom formats can't be written.
+    ///
+    /// The target locale is read from the `trgLang` attribute and must be one of the configured locales.
+    /// Segments without targets are left out, so they fall back to the default locale.
+    pub fn import_xliff<P: AsRef<Path>, Q: Into<PathBuf>>(
+        &self,
+        xliff_path: P,
+        locales_dir: Q,
+    ) -> Result<()> {
+        xliff::import(
+            &self.parsed_locales,
+            xliff_path.as_ref(),
+            locales_dir.into(),
+        )
+    }
+
     /// Emit the warnings generated when parsing the translations
     pub fn emit_warnings(&self) {
         let warnings = self.parsed_locales.diag.warnings();
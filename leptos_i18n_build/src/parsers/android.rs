@@ -0,0 +1,295 @@
+//! Android string resources.
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use leptos_i18n_parser::parse_locales::options::parser::{Parser, Seed, SerdeError, Value};
+use serde_json::Map;
+
+use super::{Placeholders, custom_error};
+use crate::xml::{self, Element, Node};
+
+/// Parser for Android string resources, read from `{locales_path}/values-{qualifier}/strings.xml`,
+/// or `{namespace}.xml` with namespaces.
+///
+/// - `<string>` elements are keys.
+/// - `<plurals>` elements are plurals, each `<item>` being a form.
+/// - `<string-array>` elements are subkeys, the items being named `item_0`, `item_1`, ...
+///
+/// The locale `fr-CA` is read from `values-fr-rCA`, and locales with scripts or variants use the `values-b+` form.
+/// The default locale can be read from the unqualified `values` directory with `default_locale`.
+#[derive(Debug, Clone, Default)]
+pub struct AndroidStrings {
+    default_locale: Option<String>,
+    placeholders: Placeholders,
+}
+
+impl AndroidStrings {
+    /// Create a new parser.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the given locale from the unqualified `values` directory.
+    pub fn default_locale(self, locale: &str) -> Self {
+        Self {
+            default_locale: Some(locale.to_string()),
+            ..self
+        }
+    }
+
+    /// Set the names given to the placeholders.
+    pub fn placeholders(self, placeholders: Placeholders) -> Self {
+        Self {
+            placeholders,
+            ..self
+        }
+    }
+
+    fn to_json(&self, src: &str) -> Result<serde_json::Value, SerdeError> {
+        let root = xml::parse(src).map_err(|err| custom_error(format!("invalid XML, {err}")))?;
+        if root.name != "resources" {
+            return Err(custom_error(format!(
+                "expected a <resources> root element, found <{}>",
+                root.name
+            )));
+        }
+
+        let mut keys = Map::new();
+        for element in root.elements() {
+            let Some(name) = element.attr("name") else {
+                continue;
+            };
+            match element.name.as_str() {
+                "string" => {
+                    let value = self.string_value(name, element, false);
+                    keys.insert(name.to_string(), value.into());
+                }
+                "plurals" => {
+                    for item in items(element) {
+                        let Some(quantity) = item.attr("quantity") else {
+                            return Err(custom_error(format!(
+                                "missing \"quantity\" attribute in plurals {name:?}"
+                            )));
+                        };
+                        let value = self.string_value(name, item, true);
+                        keys.insert(format!("{name}_{quantity}"), value.into());
+                    }
+                }
+                "string-array" => {
+                    let values = items(element)
+                        .enumerate()
+                        .map(|(i, item)| {
+                            let value = self.string_value(name, item, false);
+                            (format!("item_{i}"), value.into())
+                        })
+                        .collect();
+                    keys.insert(name.to_string(), serde_json::Value::Object(values));
+                }
+                _ => {}
+            }
+        }
+        Ok(serde_json::Value::Object(keys))
+    }
+
+    fn string_value(&self, key: &str, element: &Element, in_plural: bool) -> String {
+        let mut text = AndroidText::default();
+        text.push_children(&element.children);
+        if element.attr("formatted") == Some("false") {
+            text.out
+        } else {
+            self.placeholders.convert(key, &text.out, in_plural)
+        }
+    }
+}
+
+fn items(element: &Element) -> impl Iterator<Item = &Element> {
+    element.elements().filter(|item| item.name == "item")
+}
+
+/// Android resource qualifier of a locale: `fr`, `fr-rCA` or `b+sr+Latn`.
+fn locale_qualifier(locale: &str) -> String {
+    let parts = locale.split(['-', '_']).collect::<Vec<_>>();
+    match parts.as_slice() {
+        [language] => language.to_string(),
+        [language, region]
+            if (region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()))
+                || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit())) =>
+        {
+            format!("{language}-r{}", region.to_ascii_uppercase())
+        }
+        parts => format!("b+{}", parts.join("+")),
+    }
+}
+
+/// Text of a string resource, with the Android quoting, escaping and whitespace rules applied.
+#[derive(Default)]
+struct AndroidText {
+    out: String,
+    in_quotes: bool,
+    pending_space: bool,
+}
+
+impl AndroidText {
+    fn flush_space(&mut self) {
+        if self.pending_space && !self.out.is_empty() {
+            self.out.push(' ');
+        }
+        self.pending_space = false;
+    }
+
+    fn push_children(&mut self, children: &[Node]) {
+        for child in children {
+            match child {
+                Node::Text(text) => self.push_text(text),
+                // `<xliff:g>` only marks untranslatable content.
+                Node::Element(element) if element.name == "g" => {
+                    self.push_children(&element.children)
+                }
+                Node::Element(element) => self.push_element(element),
+            }
+        }
+    }
+
+    fn push_element(&mut self, element: &Element) {
+        self.flush_space();
+        self.out.push('<');
+        self.out.push_str(&element.name);
+        for (name, value) in &element.attributes {
+            self.out.push(' ');
+            self.out.push_str(name);
+            self.out.push_str("=\"");
+            self.out.push_str(value);
+            self.out.push('"');
+        }
+        if element.children.is_empty() {
+            self.out.push_str(" />");
+            return;
+        }
+        self.out.push('>');
+        self.push_children(&element.children);
+        self.out.push_str("</");
+        self.out.push_str(&element.name);
+        self.out.push('>');
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    self.flush_space();
+                    match chars.next() {
+                        Some('n') => self.out.push('\n'),
+                        Some('t') => self.out.push('\t'),
+                        Some('u') => {
+                            let hex = chars.clone().take(4).collect::<String>();
+                            match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                                Some(c) if hex.len() == 4 => {
+                                    self.out.push(c);
+                                    chars.nth(3);
+                                }
+                                _ => self.out.push_str("\\u"),
+                            }
+                        }
+                        Some(c) => self.out.push(c),
+                        None => self.out.push('\\'),
+                    }
+                }
+                '"' => self.in_quotes = !self.in_quotes,
+                c if c.is_whitespace() && !self.in_quotes => self.pending_space = true,
+                c => {
+                    self.flush_space();
+                    self.out.push(c);
+                }
+            }
+        }
+    }
+}
+
+impl Parser for AndroidStrings {
+    fn deserialize(
+        &self,
+        reader: &mut dyn Read,
+        _path: &Path,
+        seed: Seed,
+    ) -> Result<Value, SerdeError> {
+        let mut src = String::new();
+        reader.read_to_string(&mut src).map_err(SerdeError::Io)?;
+        let value = self.to_json(&src)?;
+        serde::de::DeserializeSeed::deserialize(seed, value).map_err(SerdeError::Json)
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["xml"]
+    }
+
+    fn locale_file_path(&self, locale: &str, namespace: Option<&str>) -> Option<PathBuf> {
+        let dir = if self.default_locale.as_deref() == Some(locale) {
+            "values".to_string()
+        } else {
+            format!("values-{}", locale_qualifier(locale))
+        };
+        let file_name = format!("{}.xml", namespace.unwrap_or("strings"));
+        Some(Path::new(&dir).join(file_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_resources() {
+        let src = r#"<?xml version="1.0" encoding="utf-8"?>
+<resources xmlns:xliff="urn:oasis:names:tc:xliff:document:1.2">
+    <string name="app_name">My   App</string>
+    <string name="greeting">Hello <b><xliff:g id="name">%1$s</xliff:g></b>!</string>
+    <string name="escaped">It\'s \"quoted\"\nline é</string>
+    <string name="spaces">"  keep   spaces "</string>
+    <string name="percent" formatted="false">100%</string>
+    <plurals name="items">
+        <item quantity="one">%d item</item>
+        <item quantity="other">%d items</item>
+    </plurals>
+    <string-array name="planets">
+        <item>Mercury</item>
+        <item>Venus</item>
+    </string-array>
+    <color name="ignored">#fff</color>
+</resources>"#;
+        let value = AndroidStrings::new().to_json(src).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "app_name": "My App",
+                "greeting": "Hello <b>{{ arg1 }}</b>!",
+                "escaped": "It's \"quoted\"\nline é",
+                "spaces": "  keep   spaces ",
+                "percent": "100%",
+                "items_one": "{{ count }} item",
+                "items_other": "{{ count }} items",
+                "planets": { "item_0": "Mercury", "item_1": "Venus" },
+            })
+        );
+    }
+
+    #[test]
+    fn locale_paths() {
+        let parser = AndroidStrings::new().default_locale("en");
+        let path = |locale, namespace| parser.locale_file_path(locale, namespace).unwrap();
+        assert_eq!(path("en", None), Path::new("values/strings.xml"));
+        assert_eq!(path("fr", None), Path::new("values-fr/strings.xml"));
+        assert_eq!(
+            path("fr-CA", Some("home")),
+            Path::new("values-fr-rCA/home.xml")
+        );
+        assert_eq!(
+            path("sr-Latn", None),
+            Path::new("values-b+sr+Latn/strings.xml")
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Parsers for translation files shared with native mobile apps, to use with `FileFormat::Custom`.
+//!
+//! - [`AndroidStrings`] reads Android string resources, `res/values-{qualifier}/strings.xml`.
+//! - [`AppleStringCatalog`] reads Apple String Catalogs, `Localizable.xcstrings`.
+//!
+//! Both map printf style placeholders such as `%1$s`, `%d` or `%@` to named variables with [`Placeholders`].
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use leptos_i18n_parser::parse_locales::options::parser::SerdeError;
+
+mod android;
+mod xcstrings;
+
+pub use android::AndroidStrings;
+pub use xcstrings::AppleStringCatalog;
+
+/// Names given to the positional placeholders such as `%1$s` or `%@`.
+///
+/// A placeholder at position `n` (starting at 1) is named after the `n`th name given for its key,
+/// then the `n`th name given for every key, and defaults to `argn`.
+/// In plural forms the first placeholder defaults to `count`, the variable used to select the form.
+#[derive(Debug, Clone, Default)]
+pub struct Placeholders {
+    names: Vec<String>,
+    key_names: BTreeMap<String, Vec<String>>,
+}
+
+impl Placeholders {
+    /// Create a new mapping, naming the placeholders `arg1`, `arg2`, ...
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names of the placeholders by position, for every key.
+    pub fn names<I, S>(self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+            ..self
+        }
+    }
+
+    /// Names of the placeholders by position for the given key, they take precedence over `names`.
+    pub fn key_names<I, S>(mut self, key: &str, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let names = names.into_iter().map(Into::into).collect();
+        self.key_names.insert(key.to_string(), names);
+        self
+    }
+
+    fn name(&self, key: &str, position: usize, in_plural: bool) -> String {
+        let index = position.wrapping_sub(1);
+        self.key_names
+            .get(key)
+            .and_then(|names| names.get(index))
+            .or_else(|| self.names.get(index))
+            .cloned()
+            .unwrap_or_else(|| {
+                if in_plural && position == 1 {
+                    "count".to_string()
+                } else {
+                    format!("arg{position}")
+                }
+            })
+    }
+
+    /// Replace the placeholders of `text` with `{{ name }}` variables.
+    pub(crate) fn convert(&self, key: &str, text: &str, in_plural: bool) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut next_position = 1;
+        let mut rest = text;
+        while let Some(index) = rest.find('%') {
+            out.push_str(&rest[..index]);
+            rest = &rest[index + 1..];
+            if let Some(after) = rest.strip_prefix('%') {
+                out.push('%');
+                rest = after;
+                continue;
+            }
+            let Some((position, len)) = parse_specifier(rest) else {
+                out.push('%');
+                continue;
+            };
+            let position = position.unwrap_or_else(|| {
+                next_position += 1;
+                next_position - 1
+            });
+            let _ = write!(out, "{{{{ {} }}}}", self.name(key, position, in_plural));
+            rest = &rest[len..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Parse a format specifier following a `%`, returning its explicit position if any and its length.
+///
+/// Spaces are not accepted as flags, so a literal `%` followed by a word is left untouched.
+fn parse_specifier(s: &str) -> Option<(Option<usize>, usize)> {
+    let bytes = s.as_bytes();
+    let digits = |start: usize| {
+        bytes[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+
+    let mut pos = 0;
+    let mut position = None;
+    let len = digits(0);
+    if len > 0 && bytes.get(len) == Some(&b'$') {
+        position = s[..len].parse().ok();
+        pos = len + 1;
+    }
+    pos += bytes[pos..]
+        .iter()
+        .take_while(|b| matches!(b, b'-' | b'+' | b'#' | b'0' | b',' | b'(' | b'\''))
+        .count();
+    pos += digits(pos);
+    if bytes.get(pos) == Some(&b'.') {
+        pos += 1 + digits(pos + 1);
+    }
+    for modifier in ["hh", "ll", "h", "l", "q", "L", "z", "t", "j"] {
+        if s[pos..].starts_with(modifier) {
+            pos += modifier.len();
+            break;
+        }
+    }
+    match bytes.get(pos) {
+        Some(
+            b'@' | b's' | b'S' | b'd' | b'D' | b'i' | b'u' | b'U' | b'x' | b'X' | b'o' | b'O'
+            | b'f' | b'F' | b'e' | b'E' | b'g' | b'G' | b'a' | b'A' | b'c' | b'C' | b'p',
+        ) => Some((position, pos + 1)),
+        _ => None,
+    }
+}
+
+fn custom_error(message: impl Into<String>) -> SerdeError {
+    SerdeError::Custom(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_placeholders() {
+        let placeholders = Placeholders::new()
+            .names(["name"])
+            .key_names("invite", ["host", "guest"]);
+
+        assert_eq!(
+            placeholders.convert("greeting", "Hello %1$s, you have %2$d messages", false),
+            "Hello {{ name }}, you have {{ arg2 }} messages"
+        );
+        assert_eq!(
+            placeholders.convert("invite", "%@ invited %@", false),
+            "{{ host }} invited {{ guest }}"
+        );
+        assert_eq!(
+            placeholders.convert("items", "%lld items", true),
+            "{{ name }} items"
+        );
+        assert_eq!(
+            Placeholders::new().convert("items", "%d items, %.2f%% done", true),
+            "{{ count }} items, {{ arg2 }}% done"
+        );
+        assert_eq!(
+            Placeholders::new().convert("key", "100% sure, %", false),
+            "100% sure, %"
+        );
+    }
+}
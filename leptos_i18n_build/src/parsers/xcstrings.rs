@@ -0,0 +1,207 @@
+//! Apple String Catalogs.
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use leptos_i18n_parser::parse_locales::options::parser::{Parser, Seed, SerdeError, Value};
+use serde_json::Map;
+
+use super::{Placeholders, custom_error};
+
+const DEFAULT_FILE_NAME: &str = "Localizable.xcstrings";
+
+/// Parser for Apple String Catalogs, every locale being read from `{locales_path}/Localizable.xcstrings`,
+/// or `{namespace}.xcstrings` with namespaces.
+///
+/// - Keys are read from the `stringUnit` of the locale.
+/// - Plural variations are plurals.
+/// - Keys without a localization for the source language use the key as the value.
+///
+/// Device variations and substitutions are not supported.
+#[derive(Debug, Clone)]
+pub struct AppleStringCatalog {
+    file_name: String,
+    placeholders: Placeholders,
+}
+
+impl Default for AppleStringCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AppleStringCatalog {
+    /// Create a new parser.
+    pub fn new() -> Self {
+        Self {
+            file_name: DEFAULT_FILE_NAME.to_string(),
+            placeholders: Placeholders::new(),
+        }
+    }
+
+    /// Set the name of the catalog when not using namespaces, default to `Localizable.xcstrings`.
+    pub fn file_name(self, file_name: &str) -> Self {
+        Self {
+            file_name: file_name.to_string(),
+            ..self
+        }
+    }
+
+    /// Set the names given to the placeholders.
+    pub fn placeholders(self, placeholders: Placeholders) -> Self {
+        Self {
+            placeholders,
+            ..self
+        }
+    }
+
+    fn to_json(&self, src: &str, locale: &str) -> Result<serde_json::Value, SerdeError> {
+        let catalog: serde_json::Value = serde_json::from_str(src).map_err(SerdeError::Json)?;
+        let source_language = catalog
+            .get("sourceLanguage")
+            .and_then(serde_json::Value::as_str);
+        let Some(strings) = catalog
+            .get("strings")
+            .and_then(serde_json::Value::as_object)
+        else {
+            return Err(custom_error("missing \"strings\" in the string catalog"));
+        };
+
+        let mut keys = Map::new();
+        for (key, entry) in strings {
+            match entry.get("localizations").and_then(|l| l.get(locale)) {
+                Some(localization) => self.push_localization(&mut keys, key, localization)?,
+                None if source_language == Some(locale) => {
+                    let value = self.placeholders.convert(key, key, false);
+                    keys.insert(key.clone(), value.into());
+                }
+                None => {}
+            }
+        }
+        Ok(serde_json::Value::Object(keys))
+    }
+
+    fn push_localization(
+        &self,
+        keys: &mut Map<String, serde_json::Value>,
+        key: &str,
+        localization: &serde_json::Value,
+    ) -> Result<(), SerdeError> {
+        if localization.get("substitutions").is_some() {
+            return Err(custom_error(format!(
+                "substitutions are not supported, found in key {key:?}"
+            )));
+        }
+        if let Some(value) = string_unit_value(localization) {
+            let value = self.placeholders.convert(key, value, false);
+            keys.insert(key.to_string(), value.into());
+            return Ok(());
+        }
+        let Some(variations) = localization.get("variations") else {
+            return Ok(());
+        };
+        let Some(plural) = variations
+            .get("plural")
+            .and_then(serde_json::Value::as_object)
+        else {
+            return Err(custom_error(format!(
+                "only plural variations are supported, found other variations in key {key:?}"
+            )));
+        };
+        for (category, variation) in plural {
+            if let Some(value) = string_unit_value(variation) {
+                let value = self.placeholders.convert(key, value, true);
+                keys.insert(format!("{key}_{category}"), value.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn string_unit_value(value: &serde_json::Value) -> Option<&str> {
+    value.get("stringUnit")?.get("value")?.as_str()
+}
+
+impl Parser for AppleStringCatalog {
+    fn deserialize(
+        &self,
+        reader: &mut dyn Read,
+        _path: &Path,
+        seed: Seed,
+    ) -> Result<Value, SerdeError> {
+        let mut src = String::new();
+        reader.read_to_string(&mut src).map_err(SerdeError::Io)?;
+        let value = self.to_json(&src, seed.locale())?;
+        serde::de::DeserializeSeed::deserialize(seed, value).map_err(SerdeError::Json)
+    }
+
+    fn file_extensions(&self) -> &'static [&'static str] {
+        &["xcstrings"]
+    }
+
+    fn locale_file_path(&self, _locale: &str, namespace: Option<&str>) -> Option<PathBuf> {
+        match namespace {
+            Some(namespace) => Some(format!("{namespace}.xcstrings").into()),
+            None => Some(self.file_name.clone().into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CATALOG: &str = r#"{
+  "sourceLanguage" : "en",
+  "strings" : {
+    "greeting" : {
+      "localizations" : {
+        "fr" : { "stringUnit" : { "state" : "translated", "value" : "Bonjour %@" } }
+      }
+    },
+    "items" : {
+      "localizations" : {
+        "en" : {
+          "variations" : {
+            "plural" : {
+              "one" : { "stringUnit" : { "state" : "translated", "value" : "%lld item" } },
+              "other" : { "stringUnit" : { "state" : "translated", "value" : "%lld items" } }
+            }
+          }
+        }
+      }
+    }
+  },
+  "version" : "1.0"
+}"#;
+
+    #[test]
+    fn parse_catalog() {
+        let parser = AppleStringCatalog::new()
+            .placeholders(Placeholders::new().key_names("greeting", ["name"]));
+
+        assert_eq!(
+            parser.to_json(CATALOG, "en").unwrap(),
+            json!({
+                "greeting": "greeting",
+                "items_one": "{{ count }} item",
+                "items_other": "{{ count }} items",
+            })
+        );
+        assert_eq!(
+            parser.to_json(CATALOG, "fr").unwrap(),
+            json!({ "greeting": "Bonjour {{ name }}" })
+        );
+    }
+
+    #[test]
+    fn unsupported_variations() {
+        let catalog = r#"{ "sourceLanguage": "en", "strings": { "key": { "localizations": {
+            "en": { "variations": { "device": { "iphone": { "stringUnit": { "value": "iPhone" } } } } }
+        } } } }"#;
+        assert!(AppleStringCatalog::new().to_json(catalog, "en").is_err());
+    }
+}
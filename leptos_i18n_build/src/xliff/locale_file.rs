@@ -0,0 +1,303 @@
+//! Write imported translations as locale files.
+
+use leptos_i18n_parser::{
+    parse_locales::{
+        error::{Error, Result},
+        options::FileFormat,
+        plurals::{PluralForm, PluralRuleType},
+    },
+    utils::Key,
+};
+use std::{collections::BTreeMap, fmt::Write};
+
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    String(String),
+    Plurals(PluralRuleType, BTreeMap<PluralForm, String>),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Node {
+    Value(Value),
+    Subkeys(Tree),
+}
+
+pub type Tree = BTreeMap<String, Node>;
+
+/// Quote a string with the escapes common to JSON, JSON5, YAML, TOML and PO.
+fn quote(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Key of a plural form when plurals are declared with suffixes, e.g. `key_one` or `key_ordinal_one`.
+fn plural_key(key: &str, rule_type: PluralRuleType, form: PluralForm) -> String {
+    match rule_type {
+        PluralRuleType::Cardinal => format!("{key}{form}"),
+        PluralRuleType::Ordinal => format!("{key}_ordinal{form}"),
+    }
+}
+
+fn write_key(key: &str, out: &mut String) {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        out.push_str(key);
+    } else {
+        quote(key, out);
+    }
+}
+
+/// Iterate over the values of a map as they should be written, plurals being expanded to suffixed keys.
+fn flat_values(tree: &Tree) -> impl Iterator<Item = (String, &str)> {
+    tree.iter().flat_map(|(key, node)| {
+        let values: Vec<(String, &str)> = match node {
+            Node::Value(Value::String(s)) => vec![(key.clone(), s)],
+            Node::Value(Value::Plurals(rule_type, forms)) => forms
+                .iter()
+                .map(|(form, s)| (plural_key(key, *rule_type, *form), s.as_str()))
+                .collect(),
+            Node::Subkeys(_) => vec![],
+        };
+        values
+    })
+}
+
+fn subkeys(tree: &Tree) -> impl Iterator<Item = (&str, &Tree)> {
+    tree.iter().filter_map(|(key, node)| match node {
+        Node::Subkeys(tree) => Some((key.as_str(), tree)),
+        Node::Value(_) => None,
+    })
+}
+
+fn write_json(tree: &Tree, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth + 1);
+    out.push('{');
+    let mut first = true;
+    let mut sep = |out: &mut String| {
+        if !std::mem::replace(&mut first, false) {
+            out.push(',');
+        }
+        out.push('\n');
+        out.push_str(&indent);
+    };
+    for (key, node) in tree {
+        match node {
+            Node::Value(Value::String(s)) => {
+                sep(out);
+                quote(key, out);
+                out.push_str(": ");
+                quote(s, out);
+            }
+            Node::Value(Value::Plurals(rule_type, forms)) => {
+                for (form, s) in forms {
+                    sep(out);
+                    quote(&plural_key(key, *rule_type, *form), out);
+                    out.push_str(": ");
+                    quote(s, out);
+                }
+            }
+            Node::Subkeys(tree) => {
+                sep(out);
+                quote(key, out);
+                out.push_str(": ");
+                write_json(tree, depth + 1, out);
+            }
+        }
+    }
+    if !first {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    }
+    out.push('}');
+}
+
+fn write_yaml(tree: &Tree, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    for (key, node) in tree {
+        match node {
+            Node::Value(Value::String(s)) => {
+                out.push_str(&indent);
+                write_key(key, out);
+                out.push_str(": ");
+                quote(s, out);
+                out.push('\n');
+            }
+            Node::Value(Value::Plurals(rule_type, forms)) => {
+                for (form, s) in forms {
+                    out.push_str(&indent);
+                    write_key(&plural_key(key, *rule_type, *form), out);
+                    out.push_str(": ");
+                    quote(s, out);
+                    out.push('\n');
+                }
+            }
+            Node::Subkeys(tree) => {
+                out.push_str(&indent);
+                write_key(key, out);
+                out.push_str(":\n");
+                write_yaml(tree, depth + 1, out);
+            }
+        }
+    }
+}
+
+fn write_toml(tree: &Tree, path: &mut Vec<String>, out: &mut String) {
+    for (key, s) in flat_values(tree) {
+        write_key(&key, out);
+        out.push_str(" = ");
+        quote(s, out);
+        out.push('\n');
+    }
+    for (key, tree) in subkeys(tree) {
+        let mut header = String::new();
+        path.push(key.to_string());
+        for (i, key) in path.iter().enumerate() {
+            if i != 0 {
+                header.push('.');
+            }
+            write_key(key, &mut header);
+        }
+        let _ = write!(out, "\n[{header}]\n");
+        write_toml(tree, path, out);
+        path.pop();
+    }
+}
+
+fn write_po_entries(
+    tree: &Tree,
+    path: &mut Vec<String>,
+    categories: &[PluralForm],
+    out: &mut String,
+) {
+    for (key, node) in tree {
+        path.push(key.clone());
+        let msgid = path.join(".");
+        match node {
+            Node::Value(Value::String(s)) => {
+                out.push_str("\nmsgid ");
+                quote(&msgid, out);
+                out.push_str("\nmsgstr ");
+                quote(s, out);
+                out.push('\n');
+            }
+            // gettext only knows cardinal plurals, suffixed keys are merged back into ordinal plurals when parsed.
+            Node::Value(Value::Plurals(PluralRuleType::Ordinal, forms)) => {
+                for (form, s) in forms {
+                    out.push_str("\nmsgid ");
+                    quote(&plural_key(&msgid, PluralRuleType::Ordinal, *form), out);
+                    out.push_str("\nmsgstr ");
+                    quote(s, out);
+                    out.push('\n');
+                }
+            }
+            Node::Value(Value::Plurals(PluralRuleType::Cardinal, forms)) => {
+                out.push_str("\nmsgid ");
+                quote(&msgid, out);
+                out.push_str("\nmsgid_plural ");
+                quote(&msgid, out);
+                out.push('\n');
+                let other = forms.get(&PluralForm::Other);
+                for (i, form) in categories.iter().enumerate() {
+                    let s = forms.get(form).or(other).map_or("", String::as_str);
+                    let _ = write!(out, "msgstr[{i}] ");
+                    quote(s, out);
+                    out.push('\n');
+                }
+            }
+            Node::Subkeys(tree) => write_po_entries(tree, path, categories, out),
+        }
+        path.pop();
+    }
+}
+
+fn write_po(tree: &Tree, locale: &Key, out: &mut String) -> Result<()> {
+    let categories = PluralRuleType::Cardinal.categories(locale)?;
+    let _ = write!(
+        out,
+        "msgid \"\"\nmsgstr \"\"\n\"Language: {locale}\\n\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n"
+    );
+    write_po_entries(tree, &mut vec![], &categories, out);
+    Ok(())
+}
+
+/// Serialize the tree in the given file format.
+pub fn to_string(tree: &Tree, locale: &Key, file_format: &FileFormat) -> Result<String> {
+    let mut out = String::new();
+    match file_format {
+        FileFormat::Json | FileFormat::Json5 => {
+            write_json(tree, 0, &mut out);
+            out.push('\n');
+        }
+        FileFormat::Yaml => write_yaml(tree, 0, &mut out),
+        FileFormat::Toml => write_toml(tree, &mut vec![], &mut out),
+        FileFormat::Po => write_po(tree, locale, &mut out)?,
+        other => {
+            return Err(Error::UnsupportedXliffFileFormat(format!("{other:?}")).into());
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Tree {
+        let mut subkeys = Tree::new();
+        subkeys.insert(
+            "b".to_string(),
+            Node::Value(Value::String("say \"hi\"\n".to_string())),
+        );
+        let mut forms = BTreeMap::new();
+        forms.insert(PluralForm::One, "one".to_string());
+        forms.insert(PluralForm::Other, "{{ count }}".to_string());
+        let mut tree = Tree::new();
+        tree.insert(
+            "a".to_string(),
+            Node::Value(Value::Plurals(PluralRuleType::Cardinal, forms)),
+        );
+        tree.insert("sub".to_string(), Node::Subkeys(subkeys));
+        tree
+    }
+
+    #[test]
+    fn write_formats() {
+        let locale = Key::new("en").unwrap();
+        let json = to_string(&tree(), &locale, &FileFormat::Json).unwrap();
+        assert_eq!(
+            json,
+            "{\n  \"a_one\": \"one\",\n  \"a_other\": \"{{ count }}\",\n  \"sub\": {\n    \"b\": \"say \\\"hi\\\"\\n\"\n  }\n}\n"
+        );
+        let yaml = to_string(&tree(), &locale, &FileFormat::Yaml).unwrap();
+        assert_eq!(
+            yaml,
+            "a_one: \"one\"\na_other: \"{{ count }}\"\nsub:\n  b: \"say \\\"hi\\\"\\n\"\n"
+        );
+        let toml = to_string(&tree(), &locale, &FileFormat::Toml).unwrap();
+        assert_eq!(
+            toml,
+            "a_one = \"one\"\na_other = \"{{ count }}\"\n\n[sub]\nb = \"say \\\"hi\\\"\\n\"\n"
+        );
+        let po = to_string(&tree(), &locale, &FileFormat::Po).unwrap();
+        assert!(po.ends_with(
+            "\nmsgid \"a\"\nmsgid_plural \"a\"\nmsgstr[0] \"one\"\nmsgstr[1] \"{{ count }}\"\n\nmsgid \"sub.b\"\nmsgstr \"say \\\"hi\\\"\\n\"\n"
+        ));
+        assert!(to_string(&tree(), &locale, &FileFormat::Fluent).is_err());
+    }
+}
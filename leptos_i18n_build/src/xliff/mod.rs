@@ -0,0 +1,1023 @@
+//! XLIFF 2.0 import and export of the translations.
+
+use leptos_i18n_parser::{
+    formatters::VarBounds,
+    parse_locales::{
+        ParsedLocales, VAR_COUNT_KEY,
+        error::{Error, Result},
+        locale::{
+            BuildersKeys, BuildersKeysInner, InterpolOrLit, LiteralType, Locale, LocaleValue,
+            locale_files,
+        },
+        metadata::KeyMetadata,
+        options::ParseOptions,
+        parsed_value::{self, AttributeValue, Attributes, ForeignKey, Literal, ParsedValue},
+        plurals::{PluralForm, PluralRuleType, Plurals},
+    },
+    utils::Key,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+mod locale_file;
+
+use crate::xml::{self, Element};
+
+/// Id of the `<file>` element when the translations are not split in namespaces.
+const DEFAULT_FILE_ID: &str = "translations";
+
+const XLIFF_NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:2.0";
+
+fn var_name(key: &Key) -> &str {
+    key.name.strip_prefix("var_").unwrap_or(&key.name)
+}
+
+fn comp_name(key: &Key) -> &str {
+    key.name.strip_prefix("comp_").unwrap_or(&key.name)
+}
+
+fn variable_text(key: &Key, bounds: &VarBounds, default: Option<&str>) -> String {
+    match (bounds, default) {
+        (VarBounds::Formatted { formatter_name, .. }, _) => {
+            format!("{{{{ {}, {formatter_name} }}}}", var_name(key))
+        }
+        (VarBounds::Typed(ty), _) => format!("{{{{ {}: {ty} }}}}", var_name(key)),
+        (_, Some(default)) => format!("{{{{ {} = \"{default}\" }}}}", var_name(key)),
+        _ => format!("{{{{ {} }}}}", var_name(key)),
+    }
+}
+
+fn component_tag(key: &Key, attributes: &Attributes, self_closed: bool) -> String {
+    let mut tag = format!("<{}", comp_name(key));
+    for attribute in &attributes.0 {
+        let _ = match &attribute.value {
+            None | Some(AttributeValue::Literal(Literal::Bool(true))) => {
+                write!(tag, " {}", attribute.key)
+            }
+            Some(AttributeValue::Literal(Literal::String(s, _))) => {
+                write!(tag, " {}=\"{s}\"", attribute.key)
+            }
+            Some(AttributeValue::Literal(lit)) => write!(tag, " {}={lit} ", attribute.key),
+            Some(AttributeValue::Variable(key)) => {
+                write!(tag, " {}={{{{ {} }}}}", attribute.key, var_name(key))
+            }
+        };
+    }
+    tag.push_str(if self_closed { " />" } else { ">" });
+    tag
+}
+
+/// Writes the inline content of a `<source>` or `<target>` element.
+///
+/// Placeholders ids are derived from the variable or component name and its occurrence,
+/// so the same placeholder gets the same id in the source and the target.
+struct InlineWriter<'a> {
+    out: &'a mut String,
+    segment_id: Option<&'a str>,
+    occurences: HashMap<Key, usize>,
+}
+
+impl<'a> InlineWriter<'a> {
+    fn new(out: &'a mut String, segment_id: Option<&'a str>) -> Self {
+        InlineWriter {
+            out,
+            segment_id,
+            occurences: HashMap::new(),
+        }
+    }
+
+    fn placeholder_id(&mut self, key: &Key) -> String {
+        let occurence = self.occurences.entry(key.clone()).or_default();
+        *occurence += 1;
+        let mut id = match self.segment_id {
+            Some(segment_id) => format!("{segment_id}.{key}"),
+            None => key.to_string(),
+        };
+        if *occurence > 1 {
+            let _ = write!(id, "_{occurence}");
+        }
+        id
+    }
+
+    fn write_attr(&mut self, name: &str, value: &str) {
+        let _ = write!(self.out, " {name}=\"");
+        xml::escape(value, self.out);
+        self.out.push('"');
+    }
+
+    /// Returns `false` if the value can't be represented as inline content.
+    fn write_value(&mut self, value: &ParsedValue) -> bool {
+        match value {
+            ParsedValue::Literal(Literal::String(s, _)) => {
+                xml::escape(s, self.out);
+                true
+            }
+            ParsedValue::Literal(lit) => {
+                let _ = write!(self.out, "{lit}");
+                true
+            }
+            ParsedValue::Variable {
+                key,
+                bounds,
+                default,
+            } => {
+                let id = self.placeholder_id(key);
+                self.out.push_str("<ph");
+                self.write_attr("id", &id);
+                self.write_attr("disp", &variable_text(key, bounds, default.as_deref()));
+                self.out.push_str("/>");
+                true
+            }
+            ParsedValue::Component {
+                key,
+                inner: None,
+                attributes,
+            } => {
+                let id = self.placeholder_id(key);
+                self.out.push_str("<ph");
+                self.write_attr("id", &id);
+                self.write_attr("disp", &component_tag(key, attributes, true));
+                self.out.push_str("/>");
+                true
+            }
+            ParsedValue::Component {
+                key,
+                inner: Some(inner),
+                attributes,
+            } => {
+                let id = self.placeholder_id(key);
+                self.out.push_str("<pc");
+                self.write_attr("id", &id);
+                self.write_attr("dispStart", &component_tag(key, attributes, false));
+                self.write_attr("dispEnd", &format!("</{}>", comp_name(key)));
+                self.out.push('>');
+                let ok = self.write_value(inner);
+                self.out.push_str("</pc>");
+                ok
+            }
+            ParsedValue::Bloc(values) => values.iter().all(|value| self.write_value(value)),
+            // foreign keys are exported resolved.
+            ParsedValue::ForeignKey(foreign_key) => match &*foreign_key.borrow() {
+                ForeignKey::Set(value) => self.write_value(value),
+                ForeignKey::NotSet(..) => false,
+            },
+            ParsedValue::Default
+            | ParsedValue::Ranges(_)
+            | ParsedValue::Subkeys(_)
+            | ParsedValue::List(_)
+            | ParsedValue::Plurals(_)
+            | ParsedValue::Select(_)
+            | ParsedValue::Condition(_)
+            | ParsedValue::Dummy(_) => false,
+        }
+    }
+}
+
+enum Forms<'a> {
+    Single(&'a ParsedValue),
+    Plurals(&'a Plurals),
+}
+
+impl<'a> Forms<'a> {
+    fn new(value: &'a ParsedValue) -> Option<Self> {
+        match value {
+            // only plurals on `count` can be written back as suffixed keys,
+            // the segments being the categories of the locale, exact forms and offsets can't be exported.
+            ParsedValue::Plurals(plurals)
+                if &*plurals.count_key.name != VAR_COUNT_KEY
+                    || plurals.offset != 0
+                    || plurals.forms.keys().any(PluralForm::is_exact) =>
+            {
+                None
+            }
+            ParsedValue::Plurals(plurals) => Some(Forms::Plurals(plurals)),
+            ParsedValue::Default | ParsedValue::Dummy(_) => None,
+            value => Some(Forms::Single(value)),
+        }
+    }
+
+    fn get(&self, form: PluralForm) -> &'a ParsedValue {
+        match self {
+            Forms::Single(value) => value,
+            Forms::Plurals(plurals) => plurals.forms.get(&form).unwrap_or(&plurals.other),
+        }
+    }
+}
+
+fn segment_id(rule_type: PluralRuleType, form: PluralForm) -> String {
+    let form = form.to_string();
+    let form = form.trim_start_matches('_');
+    match rule_type {
+        PluralRuleType::Cardinal => form.to_string(),
+        PluralRuleType::Ordinal => format!("ordinal_{form}"),
+    }
+}
+
+fn parse_segment_id(id: &str) -> Option<(PluralRuleType, PluralForm)> {
+    match id.strip_prefix("ordinal_") {
+        Some(form) => Some((PluralRuleType::Ordinal, PluralForm::try_from_str(form)?)),
+        None => Some((PluralRuleType::Cardinal, PluralForm::try_from_str(id)?)),
+    }
+}
+
+struct Exporter<'a> {
+    out: String,
+    target_index: Option<usize>,
+    key_path: Vec<&'a str>,
+}
+
+impl<'a> Exporter<'a> {
+    fn write_notes(&mut self, interpol_or_lit: &InterpolOrLit, metadata: Option<&KeyMetadata>) {
+        let mut notes = vec![];
+        if let Some(metadata) = metadata {
+            if let Some(description) = &metadata.description {
+                notes.push(("description", description.clone()));
+            }
+            if let Some(max_length) = metadata.max_length {
+                notes.push(("max-length", max_length.to_string()));
+            }
+            if let Some(screenshot) = &metadata.screenshot {
+                notes.push(("screenshot", screenshot.clone()));
+            }
+            for (name, placeholder) in &metadata.placeholders {
+                let mut note = name.clone();
+                if let Some(description) = &placeholder.description {
+                    let _ = write!(note, ": {description}");
+                }
+                if let Some(example) = &placeholder.example {
+                    let _ = write!(note, " (e.g. {example})");
+                }
+                notes.push(("placeholder", note));
+            }
+        }
+        if let InterpolOrLit::Interpol(keys) = interpol_or_lit {
+            let mut placeholders = keys
+                .iter_vars()
+                .map(|(key, _)| format!("{{{{ {} }}}}", var_name(&key)))
+                .collect::<Vec<_>>();
+            placeholders.extend(keys.iter_comps().map(|key| {
+                let name = comp_name(&key);
+                format!("<{name}></{name}>")
+            }));
+            placeholders.extend(
+                keys.iter_comps_self_closed()
+                    .map(|key| format!("<{} />", comp_name(&key))),
+            );
+            if !placeholders.is_empty() {
+                notes.push(("placeholders", placeholders.join(", ")));
+            }
+        }
+        if notes.is_empty() {
+            return;
+        }
+        self.out.push_str("      <notes>\n");
+        for (category, note) in notes {
+            let _ = write!(self.out, "        <note category=\"{category}\">");
+            xml::escape(&note, &mut self.out);
+            self.out.push_str("</note>\n");
+        }
+        self.out.push_str("      </notes>\n");
+    }
+
+    /// Write a segment, return `false` if the value can't be exported.
+    fn write_segment(
+        out: &mut String,
+        segment_id: Option<&str>,
+        source: &ParsedValue,
+        target: Option<&ParsedValue>,
+    ) -> bool {
+        out.push_str("      <segment");
+        if let Some(id) = segment_id {
+            let _ = write!(out, " id=\"{id}\"");
+        }
+        if target.is_some() {
+            out.push_str(" state=\"translated\"");
+        }
+        out.push_str(">\n        <source>");
+        if !InlineWriter::new(out, segment_id).write_value(source) {
+            return false;
+        }
+        out.push_str("</source>\n");
+        if let Some(target) = target {
+            out.push_str("        <target>");
+            if !InlineWriter::new(out, segment_id).write_value(target) {
+                return false;
+            }
+            out.push_str("</target>\n");
+        }
+        out.push_str("      </segment>\n");
+        true
+    }
+
+    fn write_unit(
+        &mut self,
+        interpol_or_lit: &InterpolOrLit,
+        metadata: Option<&KeyMetadata>,
+        source: &ParsedValue,
+        target: Option<(&Key, &ParsedValue)>,
+    ) -> Result<()> {
+        if matches!(interpol_or_lit, InterpolOrLit::Lit(lit_type) if *lit_type != LiteralType::String)
+        {
+            // not a translatable string.
+            return Ok(());
+        }
+        let Some(source_forms) = Forms::new(source) else {
+            return Ok(());
+        };
+        let target_forms = target.and_then(|(_, value)| Forms::new(value));
+
+        let rule_type = match (&source_forms, &target_forms) {
+            (Forms::Plurals(plurals), _) | (_, Some(Forms::Plurals(plurals))) => {
+                Some(plurals.rule_type)
+            }
+            _ => None,
+        };
+
+        let mut unit = String::new();
+        let ok = match rule_type {
+            None => Self::write_segment(
+                &mut unit,
+                None,
+                source_forms.get(PluralForm::Other),
+                target_forms
+                    .as_ref()
+                    .map(|forms| forms.get(PluralForm::Other)),
+            ),
+            Some(rule_type) => {
+                // use the categories of the target locale, so the translators are asked for every form it needs.
+                let categories = match (target, &source_forms) {
+                    (Some((locale, _)), _) => rule_type.categories(locale)?,
+                    (None, Forms::Plurals(plurals)) => {
+                        let mut forms = plurals.forms.keys().copied().collect::<Vec<_>>();
+                        forms.push(PluralForm::Other);
+                        forms
+                    }
+                    (None, Forms::Single(_)) => vec![PluralForm::Other],
+                };
+                categories.into_iter().all(|form| {
+                    let id = segment_id(rule_type, form);
+                    Self::write_segment(
+                        &mut unit,
+                        Some(&id),
+                        source_forms.get(form),
+                        target_forms.as_ref().map(|forms| forms.get(form)),
+                    )
+                })
+            }
+        };
+
+        if !ok {
+            // ranges or values with nested plurals can't be represented, skip them.
+            return Ok(());
+        }
+
+        self.out.push_str("    <unit id=\"");
+        xml::escape(&self.key_path.join("."), &mut self.out);
+        self.out.push_str("\">\n");
+        self.write_notes(interpol_or_lit, metadata);
+        self.out.push_str(&unit);
+        self.out.push_str("    </unit>\n");
+        Ok(())
+    }
+
+    fn write_keys(&mut self, keys: &'a BuildersKeysInner, locales: &'a [Locale]) -> Result<()> {
+        let Some((source_locale, _)) = locales.split_first() else {
+            return Ok(());
+        };
+        let target_locale = self.target_index.and_then(|index| locales.get(index));
+        for (key, value) in &keys.0 {
+            self.key_path.push(&key.name);
+            match value {
+                LocaleValue::Value { value, .. } => {
+                    let source = source_locale.keys.get(key);
+                    let target = target_locale.and_then(|locale| {
+                        let value = locale.keys.get(key)?;
+                        Some((&locale.top_locale_name, value))
+                    });
+                    if let Some(source) = source {
+                        let metadata = KeyMetadata::find(locales, key);
+                        self.write_unit(value, metadata, source, target)?;
+                    }
+                }
+                LocaleValue::Subkeys { locales, keys } => self.write_keys(keys, locales)?,
+            }
+            self.key_path.pop();
+        }
+        Ok(())
+    }
+
+    fn write_file(
+        &mut self,
+        id: &str,
+        keys: &'a BuildersKeysInner,
+        locales: &'a [Locale],
+    ) -> Result<()> {
+        self.out.push_str("  <file id=\"");
+        xml::escape(id, &mut self.out);
+        self.out.push_str("\" xml:space=\"preserve\">\n");
+        self.write_keys(keys, locales)?;
+        self.out.push_str("  </file>\n");
+        Ok(())
+    }
+}
+
+fn top_locales(builder_keys: &BuildersKeys) -> &[Locale] {
+    match builder_keys {
+        BuildersKeys::NameSpaces { namespaces, .. } => namespaces
+            .first()
+            .map(|ns| ns.locales.as_slice())
+            .unwrap_or_default(),
+        BuildersKeys::Locales { locales, .. } => locales,
+    }
+}
+
+fn find_locale<'a>(locales: &'a [Locale], locale: &str) -> Result<(usize, &'a Key)> {
+    locales
+        .iter()
+        .enumerate()
+        .find(|(_, l)| &*l.top_locale_name.name == locale)
+        .map(|(index, l)| (index, &l.top_locale_name))
+        .ok_or_else(|| Error::UnknownXliffLocale(locale.to_string()).into())
+}
+
+pub fn export(parsed_locales: &ParsedLocales, target_locale: Option<&str>) -> Result<String> {
+    let builder_keys = &parsed_locales.builder_keys;
+    let locales = top_locales(builder_keys);
+    let source_locale = &parsed_locales.cfg.default_locale;
+    let target = target_locale
+        .map(|locale| find_locale(locales, locale))
+        .transpose()?;
+
+    let mut exporter = Exporter {
+        out: String::new(),
+        target_index: target.map(|(index, _)| index),
+        key_path: vec![],
+    };
+
+    exporter
+        .out
+        .push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = write!(
+        exporter.out,
+        "<xliff xmlns=\"{XLIFF_NAMESPACE}\" version=\"2.0\" srcLang=\"{source_locale}\""
+    );
+    if let Some((_, target_locale)) = target {
+        let _ = write!(exporter.out, " trgLang=\"{target_locale}\"");
+    }
+    exporter.out.push_str(">\n");
+
+    match builder_keys {
+        BuildersKeys::NameSpaces { namespaces, keys } => {
+            for namespace in namespaces {
+                if let Some(keys) = keys.get(&namespace.key) {
+                    exporter.write_file(&namespace.key.name, keys, &namespace.locales)?;
+                }
+            }
+        }
+        BuildersKeys::Locales { locales, keys } => {
+            exporter.write_file(DEFAULT_FILE_ID, keys, locales)?;
+        }
+    }
+
+    exporter.out.push_str("</xliff>\n");
+    Ok(exporter.out)
+}
+
+enum SourceCode<'a> {
+    Placeholder(&'a str),
+    Paired(&'a str, &'a str),
+}
+
+/// Collect the display text of the codes, codes already collected are not overwritten.
+fn collect_codes<'a>(element: &'a Element, codes: &mut HashMap<&'a str, SourceCode<'a>>) {
+    for child in element.elements() {
+        let code = match child.name.as_str() {
+            "ph" => child.attr("disp").map(SourceCode::Placeholder),
+            "pc" => child
+                .attr("dispStart")
+                .zip(child.attr("dispEnd"))
+                .map(|(start, end)| SourceCode::Paired(start, end)),
+            _ => None,
+        };
+        if let Some((id, code)) = child.attr("id").zip(code) {
+            codes.entry(id).or_insert(code);
+        }
+        collect_codes(child, codes);
+    }
+}
+
+struct Importer<'a> {
+    path: &'a Path,
+}
+
+impl Importer<'_> {
+    fn error<T>(&self, err: impl ToString) -> Result<T> {
+        Err(Error::InvalidXliff {
+            path: self.path.to_owned(),
+            err: err.to_string(),
+        }
+        .into())
+    }
+
+    fn code<'b>(
+        &self,
+        codes: &'b HashMap<&str, SourceCode<'b>>,
+        element: &Element,
+        id_attr: &str,
+    ) -> Result<&'b SourceCode<'b>> {
+        let Some(id) = element.attr(id_attr) else {
+            return self.error(format!("<{}> element without \"{id_attr}\"", element.name));
+        };
+        match codes.get(id) {
+            Some(code) => Ok(code),
+            None => self.error(format!(
+                "<{}> element with id {id:?} does not match any code with a display text",
+                element.name
+            )),
+        }
+    }
+
+    /// Convert the content of a `<target>` to the leptos_i18n syntax.
+    fn write_target(
+        &self,
+        element: &Element,
+        codes: &HashMap<&str, SourceCode>,
+        out: &mut String,
+    ) -> Result<()> {
+        for child in &element.children {
+            let child = match child {
+                xml::Node::Text(text) => {
+                    parsed_value::escape(text, out);
+                    continue;
+                }
+                xml::Node::Element(child) => child,
+            };
+            match child.name.as_str() {
+                "ph" => match self.code(codes, child, "id")? {
+                    SourceCode::Placeholder(disp) => out.push_str(disp),
+                    SourceCode::Paired(start, end) => {
+                        out.push_str(start);
+                        out.push_str(end);
+                    }
+                },
+                "pc" => {
+                    let SourceCode::Paired(start, end) = self.code(codes, child, "id")? else {
+                        return self.error("<pc> element matching a <ph> of the source");
+                    };
+                    out.push_str(start);
+                    self.write_target(child, codes, out)?;
+                    out.push_str(end);
+                }
+                "sc" => {
+                    if let SourceCode::Paired(start, _) = self.code(codes, child, "id")? {
+                        out.push_str(start);
+                    }
+                }
+                "ec" => {
+                    if let SourceCode::Paired(_, end) = self.code(codes, child, "startRef")? {
+                        out.push_str(end);
+                    }
+                }
+                "cp" => {
+                    let c = child
+                        .attr("hex")
+                        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                        .and_then(char::from_u32);
+                    match c {
+                        Some(c) => out.push(c),
+                        None => return self.error("invalid <cp> element"),
+                    }
+                }
+                // annotations, only keep the content.
+                "mrk" => self.write_target(child, codes, out)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn import_unit(&self, unit: &Element) -> Result<Option<locale_file::Value>> {
+        let mut translations = Vec::new();
+        for segment in unit.elements().filter(|e| e.name == "segment") {
+            let Some(target) = segment.child("target") else {
+                continue;
+            };
+            // codes of the target can differ from the source ones (e.g. component attributes),
+            // the source is only used for codes without display text in the target.
+            let mut codes = HashMap::new();
+            collect_codes(target, &mut codes);
+            if let Some(source) = segment.child("source") {
+                collect_codes(source, &mut codes);
+            }
+            let mut translation = String::new();
+            self.write_target(target, &codes, &mut translation)?;
+            translations.push((segment.attr("id"), translation));
+        }
+
+        if translations.is_empty() {
+            return Ok(None);
+        }
+
+        let mut rule_types = Vec::new();
+        let forms = translations
+            .iter()
+            .map(|(id, translation)| {
+                let (rule_type, form) = parse_segment_id((*id)?)?;
+                rule_types.push(rule_type);
+                Some((form, translation.clone()))
+            })
+            .collect::<Option<BTreeMap<_, _>>>();
+        rule_types.dedup();
+        let rule_type = rule_types.pop().unwrap_or(PluralRuleType::Cardinal);
+
+        let value = match forms {
+            // a locale with a single plural category does not need plurals.
+            Some(mut forms) if forms.len() == 1 && forms.contains_key(&PluralForm::Other) => {
+                locale_file::Value::String(forms.remove(&PluralForm::Other).unwrap_or_default())
+            }
+            Some(forms) if rule_types.is_empty() => locale_file::Value::Plurals(rule_type, forms),
+            Some(_) => return self.error("unit mixes cardinal and ordinal plural segments"),
+            None => locale_file::Value::String(
+                translations
+                    .into_iter()
+                    .map(|(_, translation)| translation)
+                    .collect(),
+            ),
+        };
+        Ok(Some(value))
+    }
+
+    fn insert(
+        &self,
+        tree: &mut locale_file::Tree,
+        id: &str,
+        value: locale_file::Value,
+    ) -> Result<()> {
+        let mut path = id.split('.').collect::<Vec<_>>();
+        let Some(last) = path.pop() else {
+            return self.error("unit with an empty id");
+        };
+        let mut tree = tree;
+        for key in path {
+            let node = tree
+                .entry(key.to_string())
+                .or_insert_with(|| locale_file::Node::Subkeys(BTreeMap::new()));
+            let locale_file::Node::Subkeys(inner) = node else {
+                return self.error(format!("unit {id:?} conflicts with another unit"));
+            };
+            tree = inner;
+        }
+        if tree
+            .insert(last.to_string(), locale_file::Node::Value(value))
+            .is_some()
+        {
+            return self.error(format!("unit {id:?} is declared multiple times"));
+        }
+        Ok(())
+    }
+
+    fn import_units(&self, element: &Element, tree: &mut locale_file::Tree) -> Result<()> {
+        for child in element.elements() {
+            match child.name.as_str() {
+                "unit" => {
+                    let Some(id) = child.attr("id") else {
+                        return self.error("<unit> element without \"id\"");
+                    };
+                    if let Some(value) = self.import_unit(child)? {
+                        self.insert(tree, id, value)?;
+                    }
+                }
+                "group" => self.import_units(child, tree)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Remove the subkeys at `prefix` from the tree.
+fn take_subtree(tree: &mut locale_file::Tree, prefix: &[Key]) -> Option<locale_file::Tree> {
+    let Some((last, path)) = prefix.split_last() else {
+        return Some(std::mem::take(tree));
+    };
+    let mut tree = tree;
+    for key in path {
+        match tree.get_mut(&*key.name) {
+            Some(locale_file::Node::Subkeys(inner)) => tree = inner,
+            _ => return None,
+        }
+    }
+    match tree.remove(&*last.name) {
+        Some(locale_file::Node::Subkeys(subtree)) => Some(subtree),
+        Some(node) => {
+            tree.insert(last.name.to_string(), node);
+            None
+        }
+        None => None,
+    }
+}
+
+/// Ids of the units left in the tree, ignoring the subkeys emptied by `take_subtree`.
+fn remaining_ids(tree: &locale_file::Tree, path: &mut Vec<String>, ids: &mut Vec<String>) {
+    for (key, node) in tree {
+        path.push(key.clone());
+        match node {
+            locale_file::Node::Value(_) => ids.push(path.join(".")),
+            locale_file::Node::Subkeys(tree) => remaining_ids(tree, path, ids),
+        }
+        path.pop();
+    }
+}
+
+/// Write the tree to the files the locale is read from, or to a new file in the configured format.
+fn write_locale_files(
+    mut tree: locale_file::Tree,
+    locales_dir: &Path,
+    locale: &Key,
+    namespace: Option<&Key>,
+    options: &ParseOptions,
+) -> Result<()> {
+    let files = match locale_files(locales_dir, locale, namespace, options) {
+        Ok(files) => files,
+        Err(err) => match err.into_inner() {
+            Error::LocaleFileNotFound(_) => {
+                let file_format = &options.file_format;
+                let namespace_name = namespace.map(|namespace| &*namespace.name);
+                let path = match file_format.locale_file_path(&locale.name, namespace_name) {
+                    Some(path) => locales_dir.join(path),
+                    None => {
+                        let Some(ext) = file_format.get_files_exts().first() else {
+                            return Err(Error::UnsupportedXliffFileFormat(format!(
+                                "{file_format:?}"
+                            ))
+                            .into());
+                        };
+                        let mut path = locales_dir.join(&*locale.name);
+                        if let Some(namespace) = namespace {
+                            path.push(&*namespace.name);
+                        }
+                        path.set_extension(ext);
+                        path
+                    }
+                };
+                vec![(path, vec![], file_format)]
+            }
+            err => return Err(err.into()),
+        },
+    };
+
+    // when the locale is split in a directory, each file gets the units under its subkeys.
+    let mut contents = vec![];
+    for (path, prefix, file_format) in files {
+        let subtree = take_subtree(&mut tree, &prefix).unwrap_or_default();
+        let content = locale_file::to_string(&subtree, locale, file_format)?;
+        contents.push((path, content));
+    }
+    let mut ids = vec![];
+    remaining_ids(&tree, &mut vec![], &mut ids);
+    if !ids.is_empty() {
+        return Err(Error::XliffUnitsWithoutFile {
+            locale_dir: locales_dir.join(&*locale.name),
+            keys: ids,
+        }
+        .into());
+    }
+
+    for (path, content) in contents {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content)?;
+    }
+    Ok(())
+}
+
+pub fn import(
+    parsed_locales: &ParsedLocales,
+    xliff_path: &Path,
+    locales_dir: PathBuf,
+) -> Result<()> {
+    let importer = Importer { path: xliff_path };
+    let src = std::fs::read_to_string(xliff_path)?;
+    let root = match xml::parse(&src) {
+        Ok(root) => root,
+        Err(err) => return importer.error(err),
+    };
+    if root.name != "xliff" || !root.attr("version").is_some_and(|v| v.starts_with("2.")) {
+        return importer.error("not a XLIFF 2 document");
+    }
+    let Some(target_locale) = root.attr("trgLang") else {
+        return importer.error("missing \"trgLang\" attribute");
+    };
+    let cfg = &parsed_locales.cfg;
+    let (_, target_locale) = find_locale(top_locales(&parsed_locales.builder_keys), target_locale)?;
+
+    for file in root.elements().filter(|e| e.name == "file") {
+        let namespace = if cfg.namespaces.is_empty() {
+            None
+        } else {
+            let Some(namespace) = file
+                .attr("id")
+                .and_then(|id| cfg.namespaces.iter().find(|ns| &*ns.name == id))
+            else {
+                return importer.error(format!(
+                    "<file> id {:?} does not match any namespace",
+                    file.attr("id")
+                ));
+            };
+            Some(namespace)
+        };
+        let mut tree = locale_file::Tree::new();
+        importer.import_units(file, &mut tree)?;
+        write_locale_files(tree, &locales_dir, target_locale, namespace, &cfg.options)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, FileFormat, TranslationsInfos};
+
+    fn temp_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("leptos_i18n_xliff_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for (path, content) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn read(dir: &Path, path: &str) -> String {
+        std::fs::read_to_string(dir.join(path)).unwrap()
+    }
+
+    /// Export the "fr" translations, translate with `translate`, import them back and check that
+    /// exporting the parsed result gives the translated document.
+    fn round_trip(dir: &Path, cfg: impl Fn() -> Config, translate: &[(&str, &str)]) {
+        let infos = TranslationsInfos::parse_at_dir(dir, cfg()).unwrap();
+        let mut xliff = infos.export_xliff(Some("fr")).unwrap();
+        for (from, to) in translate {
+            assert!(xliff.contains(from), "{from:?} not found in:\n{xliff}");
+            xliff = xliff.replace(from, to);
+        }
+        let xliff_path = dir.join("fr.xlf");
+        std::fs::write(&xliff_path, &xliff).unwrap();
+        infos
+            .import_xliff(&xliff_path, dir.join("locales"))
+            .unwrap();
+
+        let infos = TranslationsInfos::parse_at_dir(dir, cfg()).unwrap();
+        assert_eq!(infos.export_xliff(Some("fr")).unwrap(), xliff);
+    }
+
+    #[test]
+    fn round_trip_namespaces() {
+        let dir = temp_dir(
+            "namespaces",
+            &[
+                (
+                    "locales/en/common.json",
+                    r#"{ "greeting": "Hello {{ name }}", "news": "<b>Important</b> news" }"#,
+                ),
+                (
+                    "locales/en/home.json",
+                    r#"{ "title": "Home", "help": "Help" }"#,
+                ),
+                (
+                    "locales/fr/common.json",
+                    r#"{ "greeting": "Bonjour {{ name }}" }"#,
+                ),
+                ("locales/fr/home.json", r#"{ "title": "Accueil" }"#),
+            ],
+        );
+        let cfg = || {
+            Config::new("en")
+                .unwrap()
+                .add_locale("fr")
+                .unwrap()
+                .add_namespaces(["common", "home"])
+                .unwrap()
+        };
+        round_trip(
+            &dir,
+            cfg,
+            &[(
+                "<segment>\n        <source><pc id=\"comp_b\" dispStart=\"&lt;b&gt;\" dispEnd=\"&lt;/b&gt;\">Important</pc> news</source>\n",
+                "<segment state=\"translated\">\n        <source><pc id=\"comp_b\" dispStart=\"&lt;b&gt;\" dispEnd=\"&lt;/b&gt;\">Important</pc> news</source>\n        <target>Nouvelles <pc id=\"comp_b\" dispStart=\"&lt;b&gt;\" dispEnd=\"&lt;/b&gt;\">importantes</pc></target>\n",
+            )],
+        );
+
+        assert_eq!(
+            read(&dir, "locales/fr/common.json"),
+            "{\n  \"greeting\": \"Bonjour {{ name }}\",\n  \"news\": \"Nouvelles <b>importantes</b>\"\n}\n"
+        );
+        // "help" has no target and is left out.
+        assert_eq!(
+            read(&dir, "locales/fr/home.json"),
+            "{\n  \"title\": \"Accueil\"\n}\n"
+        );
+        assert!(!dir.join("locales/common").exists());
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn round_trip_plurals() {
+        let dir = temp_dir(
+            "plurals",
+            &[
+                (
+                    "locales/en.json",
+                    r#"{ "items_one": "one item", "items_other": "{{ count }} items" }"#,
+                ),
+                ("locales/fr.json", r#"{}"#),
+            ],
+        );
+        let cfg = || Config::new("en").unwrap().add_locale("fr").unwrap();
+        round_trip(
+            &dir,
+            cfg,
+            &[
+                (
+                    "<segment id=\"one\">\n        <source>one item</source>\n",
+                    "<segment id=\"one\" state=\"translated\">\n        <source>one item</source>\n        <target><ph id=\"one.var_count\" disp=\"{{ count }}\"/> élément</target>\n",
+                ),
+                (
+                    "<segment id=\"many\">\n        <source><ph id=\"many.var_count\" disp=\"{{ count }}\"/> items</source>\n",
+                    "<segment id=\"many\" state=\"translated\">\n        <source><ph id=\"many.var_count\" disp=\"{{ count }}\"/> items</source>\n        <target><ph id=\"many.var_count\" disp=\"{{ count }}\"/> d'éléments</target>\n",
+                ),
+                (
+                    "<segment id=\"other\">\n        <source><ph id=\"other.var_count\" disp=\"{{ count }}\"/> items</source>\n",
+                    "<segment id=\"other\" state=\"translated\">\n        <source><ph id=\"other.var_count\" disp=\"{{ count }}\"/> items</source>\n        <target><ph id=\"other.var_count\" disp=\"{{ count }}\"/> éléments</target>\n",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            read(&dir, "locales/fr.json"),
+            "{\n  \"items_one\": \"{{ count }} élément\",\n  \"items_many\": \"{{ count }} d'éléments\",\n  \"items_other\": \"{{ count }} éléments\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn import_into_locale_dir() {
+        let dir = temp_dir(
+            "locale_dir",
+            &[
+                (
+                    "locales/en.json",
+                    r#"{ "common": { "greeting": "Hello" }, "home": { "title": "Home" } }"#,
+                ),
+                ("locales/fr/common.json", r#"{ "greeting": "Bonjour" }"#),
+                ("locales/fr/home.yaml", "title: Maison\n"),
+            ],
+        );
+        let cfg = || {
+            Config::new("en")
+                .unwrap()
+                .add_locale("fr")
+                .unwrap()
+                .parse_options(
+                    crate::ParseOptions::default()
+                        .file_format(FileFormat::Json)
+                        .add_file_format(FileFormat::Yaml),
+                )
+        };
+        round_trip(
+            &dir,
+            cfg,
+            &[("<target>Maison</target>", "<target>Accueil</target>")],
+        );
+
+        // each file of the directory is overwritten in its own format.
+        assert_eq!(
+            read(&dir, "locales/fr/common.json"),
+            "{\n  \"greeting\": \"Bonjour\"\n}\n"
+        );
+        assert_eq!(read(&dir, "locales/fr/home.yaml"), "title: \"Accueil\"\n");
+        assert!(!dir.join("locales/fr.json").exists());
+
+        // a unit outside of the files can't be written.
+        let infos = TranslationsInfos::parse_at_dir(&dir, cfg()).unwrap();
+        let xliff = infos
+            .export_xliff(Some("fr"))
+            .unwrap()
+            .replace("</file>", "    <unit id=\"footer\"><segment><source>Footer</source><target>Pied de page</target></segment></unit>\n  </file>");
+        std::fs::write(dir.join("fr.xlf"), xliff).unwrap();
+        let err = infos
+            .import_xliff(dir.join("fr.xlf"), dir.join("locales"))
+            .unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::XliffUnitsWithoutFile { keys, .. } if keys == ["footer"]
+        ));
+        assert_eq!(read(&dir, "locales/fr/home.yaml"), "title: \"Accueil\"\n");
+    }
+}
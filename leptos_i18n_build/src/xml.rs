@@ -0,0 +1,302 @@
+//! Minimal XML reader, only what is needed to read back XLIFF files and Android string resources.
+
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    /// Local name of the element, without the namespace prefix.
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+#[derive(Debug)]
+pub struct XmlError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for XmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Element {
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.name == name)
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+pub fn escape(s: &str, out: &mut String) {
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+struct Reader<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn error<T>(&self, message: impl ToString) -> Result<T, XmlError> {
+        let line = self.src[..self.pos].matches('\n').count() + 1;
+        Err(XmlError {
+            line,
+            message: message.to_string(),
+        })
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn take_until(&mut self, end: &str) -> Result<&'a str, XmlError> {
+        match self.rest().find(end) {
+            Some(index) => {
+                let s = &self.rest()[..index];
+                self.pos += index + end.len();
+                Ok(s)
+            }
+            None => self.error(format!("expected {end:?}")),
+        }
+    }
+
+    fn take_name(&mut self) -> Result<&'a str, XmlError> {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '=' | '?'))
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return self.error("expected a name");
+        }
+        self.pos += end;
+        Ok(&rest[..end])
+    }
+
+    fn decode(&self, s: &str) -> Result<String, XmlError> {
+        let mut out = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(index) = rest.find('&') {
+            out.push_str(&rest[..index]);
+            rest = &rest[index + 1..];
+            let Some(end) = rest.find(';') else {
+                return self.error("unterminated entity");
+            };
+            let entity = &rest[..end];
+            rest = &rest[end + 1..];
+            let c = match entity {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = if let Some(hex) = entity.strip_prefix("#x") {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else if let Some(dec) = entity.strip_prefix('#') {
+                        dec.parse().ok()
+                    } else {
+                        None
+                    };
+                    match code.and_then(char::from_u32) {
+                        Some(c) => c,
+                        None => return self.error(format!("unknown entity \"&{entity};\"")),
+                    }
+                }
+            };
+            out.push(c);
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    /// Skip the XML declaration, processing instructions, comments and doctype.
+    fn skip_misc(&mut self) -> Result<(), XmlError> {
+        loop {
+            self.skip_whitespace();
+            if self.eat("<?") {
+                self.take_until("?>")?;
+            } else if self.eat("<!--") {
+                self.take_until("-->")?;
+            } else if self.eat("<!") {
+                self.take_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_element(&mut self) -> Result<Element, XmlError> {
+        if !self.eat("<") {
+            return self.error("expected an element");
+        }
+        let name = self.take_name()?;
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(Element {
+                    name: local_name(name).to_string(),
+                    attributes,
+                    children: vec![],
+                });
+            }
+            if self.eat(">") {
+                break;
+            }
+            let key = self.take_name()?;
+            self.skip_whitespace();
+            if !self.eat("=") {
+                return self.error(format!("expected a value for attribute {key:?}"));
+            }
+            self.skip_whitespace();
+            let value = if self.eat("\"") {
+                self.take_until("\"")?
+            } else if self.eat("'") {
+                self.take_until("'")?
+            } else {
+                return self.error(format!("expected a quoted value for attribute {key:?}"));
+            };
+            attributes.push((key.to_string(), self.decode(value)?));
+        }
+
+        let mut children = Vec::new();
+        let mut text = String::new();
+        loop {
+            if self.rest().is_empty() {
+                return self.error(format!("unclosed element {name:?}"));
+            }
+            if self.eat("</") {
+                let end_name = self.take_name()?;
+                self.skip_whitespace();
+                if end_name != name || !self.eat(">") {
+                    return self.error(format!("expected closing tag for {name:?}"));
+                }
+                break;
+            } else if self.eat("<!--") {
+                self.take_until("-->")?;
+            } else if self.eat("<![CDATA[") {
+                text.push_str(self.take_until("]]>")?);
+            } else if self.eat("<?") {
+                self.take_until("?>")?;
+            } else if self.rest().starts_with('<') {
+                if !text.is_empty() {
+                    children.push(Node::Text(std::mem::take(&mut text)));
+                }
+                children.push(Node::Element(self.parse_element()?));
+            } else {
+                let rest = self.rest();
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.pos += end;
+                text.push_str(&self.decode(&rest[..end])?);
+            }
+        }
+        if !text.is_empty() {
+            children.push(Node::Text(text));
+        }
+
+        Ok(Element {
+            name: local_name(name).to_string(),
+            attributes,
+            children,
+        })
+    }
+}
+
+/// Parse an XML document and return its root element.
+pub fn parse(src: &str) -> Result<Element, XmlError> {
+    let mut reader = Reader { src, pos: 0 };
+    reader.skip_misc()?;
+    let root = reader.parse_element()?;
+    reader.skip_misc()?;
+    if !reader.rest().is_empty() {
+        return reader.error("unexpected content after the root element");
+    }
+    Ok(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_document() {
+        let root = parse(
+            r#"<?xml version="1.0"?>
+<!-- comment -->
+<xliff:root xmlns:xliff="urn:test" a='1' b="&lt;b&gt;">
+  <unit id="x">te&amp;xt <ph id="1"/><![CDATA[<raw>]]></unit>
+</xliff:root>"#,
+        )
+        .unwrap();
+        assert_eq!(root.name, "root");
+        assert_eq!(root.attr("a"), Some("1"));
+        assert_eq!(root.attr("b"), Some("<b>"));
+        let unit = root.child("unit").unwrap();
+        assert_eq!(
+            unit.children,
+            vec![
+                Node::Text("te&xt ".to_string()),
+                Node::Element(Element {
+                    name: "ph".to_string(),
+                    attributes: vec![("id".to_string(), "1".to_string())],
+                    children: vec![]
+                }),
+                Node::Text("<raw>".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse("<a>\n<b></a>").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(parse("<a>&unknown;</a>").is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use leptos_i18n_parser::{
+    parse_locales::{
+        condition::Condition,
+        locale::{InterpolOrLit, LiteralType},
+        parsed_value::{Literal, ParsedValue},
+    },
+    utils::{KeyPath, UnwrapAt},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{load_locales::parsed_value, utils::EitherOfWrapper};
+
+pub fn as_string_impl(this: &Condition, strings_count: usize) -> TokenStream {
+    let then = parsed_value::as_string_impl(&this.then, strings_count);
+    let otherwise = parsed_value::as_string_impl(&this.otherwise, strings_count);
+
+    let condition_key = &this.condition_key;
+
+    quote! {
+        if *core::borrow::Borrow::<bool>::borrow(#condition_key) {
+            #then
+        } else {
+            #otherwise
+        }
+    }
+}
+
+/// An empty branch, such as a missing `{{else}}`, renders nothing, as an empty text node renders as a space.
+fn branch_to_token_stream(value: &ParsedValue, strings_count: usize) -> TokenStream {
+    match value {
+        ParsedValue::Literal(Literal::String(s, _)) if s.is_empty() => quote!(()),
+        value => parsed_value::to_token_stream(value, strings_count),
+    }
+}
+
+pub fn to_token_stream(this: &Condition, strings_count: usize) -> TokenStream {
+    let either_of = EitherOfWrapper::new(2);
+
+    let mut captured_values = InterpolOrLit::Lit(LiteralType::String);
+    let mut key_path = KeyPath::new(None);
+
+    for value in this.branches() {
+        value
+            .get_keys_inner(&mut key_path, &mut captured_values, false)
+            .unwrap_at("condition::to_token_stream_1");
+    }
+
+    let captured_values = captured_values.is_interpol().map(|keys| {
+        let keys = keys
+            .iter_keys()
+            .map(|key| quote!(let #key = core::clone::Clone::clone(&#key);));
+        quote!(#(#keys)*)
+    });
+
+    let condition_key = &this.condition_key;
+
+    let then = either_of.wrap(0, branch_to_token_stream(&this.then, strings_count));
+    let otherwise = either_of.wrap(1, branch_to_token_stream(&this.otherwise, strings_count));
+
+    quote! {
+        {
+            #captured_values
+            let #condition_key = core::clone::Clone::clone(&#condition_key);
+            move || {
+                if #condition_key() {
+                    #then
+                } else {
+                    #otherwise
+                }
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@ use leptos_i18n_parser::{
     formatters::VarBounds,
     parse_locales::{
         locale::{DefaultedLocales, InterpolationKeys, Locale},
+        metadata::KeyMetadata,
         options::ParseOptions,
         parsed_value::ParsedValue,
     },
@@ -47,6 +48,8 @@ pub struct Interpolation {
 enum RangeOrPlural {
     Range(RangeType),
     Plural,
+    /// The count of plurals with an offset
+    OffsetPlural,
 }
 
 impl From<leptos_i18n_parser::parse_locales::locale::RangeOrPlural> for RangeOrPlural {
@@ -71,6 +74,9 @@ impl RangeOrPlural {
             RangeOrPlural::Plural => {
                 quote!(l_i18n_crate::__private::InterpolatePluralCount)
             }
+            RangeOrPlural::OffsetPlural => {
+                quote!(l_i18n_crate::__private::InterpolatePluralOffsetCount)
+            }
         }
     }
 }
@@ -79,6 +85,9 @@ enum VarOrComp {
     Var {
         bounds: Vec<VarBounds>,
         plural: Option<RangeOrPlural>,
+        select: bool,
+        condition: bool,
+        default: Option<String>,
     },
     Comp {
         into_view: syn::Ident,
@@ -97,10 +106,18 @@ impl Field {
         generic: &syn::Ident,
         bounds: &[VarBounds],
         plural: Option<RangeOrPlural>,
+        select: bool,
+        condition: bool,
     ) -> TokenStream {
         let bounds = bounds.iter().map(VarBounds::view_bounds);
         let plural_bound = plural.map(RangeOrPlural::to_bound);
-        let bounds = bounds.chain(plural_bound);
+        let select_bound = select.then(|| quote!(l_i18n_crate::__private::InterpolateSelect));
+        let condition_bound =
+            condition.then(|| quote!(l_i18n_crate::__private::InterpolateCondition));
+        let bounds = bounds
+            .chain(plural_bound)
+            .chain(select_bound)
+            .chain(condition_bound);
 
         quote!(#generic: 'static + ::core::clone::Clone #(+ #bounds)*)
     }
@@ -109,8 +126,18 @@ impl Field {
         generic: &syn::Ident,
         bounds: &[VarBounds],
         range: Option<RangeOrPlural>,
+        select: bool,
+        condition: bool,
     ) -> Option<TokenStream> {
         match range {
+            None if condition => {
+                let bounds = bounds.iter().map(VarBounds::fmt_bounds);
+                Some(quote!(#generic: #(#bounds +)* core::borrow::Borrow<bool>))
+            }
+            None if select => {
+                let bounds = bounds.iter().map(VarBounds::fmt_bounds);
+                Some(quote!(#generic: #(#bounds +)* core::convert::AsRef<str>))
+            }
             None => {
                 let bounds = bounds.iter().map(VarBounds::fmt_bounds);
                 Some(quote!(#generic: #(#bounds +)*))
@@ -122,14 +149,26 @@ impl Field {
                     quote!(#generic: #(#bounds +)* Clone + Into<l_i18n_crate::reexports::icu::plurals::PluralOperands>),
                 )
             }
+            Some(RangeOrPlural::OffsetPlural) => {
+                let bounds = bounds.iter().map(VarBounds::fmt_bounds);
+                Some(
+                    quote!(#generic: #(#bounds +)* Clone + Into<l_i18n_crate::reexports::icu::plurals::PluralOperands> + l_i18n_crate::__private::PluralCountOffset),
+                )
+            }
         }
     }
 
     pub fn as_bounded_generic(&self) -> impl Iterator<Item = TokenStream> {
         let generic = &self.generic;
         match &self.var_or_comp {
-            VarOrComp::Var { bounds, plural } => {
-                let ts = Self::get_var_generics(generic, bounds, *plural);
+            VarOrComp::Var {
+                bounds,
+                plural,
+                select,
+                condition,
+                ..
+            } => {
+                let ts = Self::get_var_generics(generic, bounds, *plural, *select, *condition);
                 EitherIter::Iter1(std::iter::once(ts))
             }
             VarOrComp::Comp {
@@ -152,8 +191,14 @@ impl Field {
     pub fn as_fmt_bounded_generic(&self) -> impl Iterator<Item = TokenStream> {
         let generic = &self.generic;
         match &self.var_or_comp {
-            VarOrComp::Var { bounds, plural } => {
-                let ts = Self::get_fmt_var_generics(generic, bounds, *plural);
+            VarOrComp::Var {
+                bounds,
+                plural,
+                select,
+                condition,
+                ..
+            } => {
+                let ts = Self::get_fmt_var_generics(generic, bounds, *plural, *select, *condition);
                 EitherIter::Iter1(ts.into_iter())
             }
             VarOrComp::Comp {
@@ -206,6 +251,13 @@ impl Field {
         quote!(#key: #generic)
     }
 
+    pub fn default(&self) -> Option<&str> {
+        match &self.var_or_comp {
+            VarOrComp::Var { default, .. } => default.as_deref(),
+            VarOrComp::Comp { .. } => None,
+        }
+    }
+
     pub fn as_into_view_generic(&self) -> Option<&syn::Ident> {
         match &self.var_or_comp {
             VarOrComp::Var { .. } => None,
@@ -221,7 +273,13 @@ impl Interpolation {
             bounds.sort(); // the sort is to have consistent codegen
             let var_or_comp = VarOrComp::Var {
                 bounds,
-                plural: infos.range_count.map(Into::into),
+                plural: infos.range_count.map(|ty| match ty.into() {
+                    RangeOrPlural::Plural if infos.plural_offset => RangeOrPlural::OffsetPlural,
+                    ty => ty,
+                }),
+                select: infos.select_variants.is_some(),
+                condition: infos.condition,
+                default: infos.default.clone(),
             };
             let generic = format_ident!("__{}__", key);
             Field {
@@ -277,6 +335,8 @@ impl Interpolation {
         options: &ParseOptions,
         gen_docs: bool,
     ) -> Self {
+        let metadata = KeyMetadata::find(locales, key);
+
         // filter defaulted locales
         let locales = locales
             .iter()
@@ -339,6 +399,8 @@ impl Interpolation {
 
         let debug_impl = Self::debug_impl(&builder_name, &ident, &fields);
 
+        let fill_defaults_impl = Self::fill_defaults_impl(&ident, &typed_builder_name, &fields);
+
         let (display_impl, builder_display) = if options.interpolate_display {
             let display_impl = Self::display_impl(
                 key,
@@ -369,6 +431,8 @@ impl Interpolation {
 
             #debug_impl
 
+            #fill_defaults_impl
+
             #display_impl
 
             #builder_display
@@ -378,7 +442,11 @@ impl Interpolation {
 
         let docs = if gen_docs {
             let path = key_path.to_string_with_key(key);
-            let mut docs = format!("Full path: `{}`\n", path);
+            let mut docs = String::new();
+            if let Some(metadata) = metadata {
+                metadata.write_docs(&mut docs).unwrap();
+            }
+            docs.push_str(&format!("Full path: `{}`\n", path));
             Self::gen_fields_docs(&mut docs, &fields).unwrap();
             quote! {
                 #[doc = #docs]
@@ -399,9 +467,22 @@ impl Interpolation {
         let mut variables = fields
             .iter()
             .filter_map(|field| match &field.var_or_comp {
-                VarOrComp::Var { bounds, plural } => {
+                VarOrComp::Var {
+                    bounds,
+                    plural,
+                    select,
+                    condition,
+                    default,
+                } => {
                     let key = field.key.name.strip_prefix("var_")?;
-                    Some((key, bounds.as_slice(), plural.as_ref()))
+                    Some((
+                        key,
+                        bounds.as_slice(),
+                        plural.as_ref(),
+                        *select,
+                        *condition,
+                        default.as_deref(),
+                    ))
                 }
                 VarOrComp::Comp { .. } => None,
             })
@@ -409,11 +490,19 @@ impl Interpolation {
 
         if variables.peek().is_some() {
             writeln!(docs, "## Vars :")?;
-            for (key, bounds, plural) in variables {
-                let _ = bounds;
-                match plural {
-                    Some(_) => writeln!(docs, "- `{}` (plural count)", key)?,
-                    None => writeln!(docs, "- `{}`", key)?,
+            for (key, bounds, plural, select, condition, default) in variables {
+                write!(docs, "- `{}`", key)?;
+                if let Some(ty) = bounds.iter().find_map(VarBounds::var_type) {
+                    write!(docs, ": `{}`", ty)?;
+                }
+                match (plural, default) {
+                    (Some(_), _) => writeln!(docs, " (plural count)")?,
+                    (None, _) if select => writeln!(docs, " (select)")?,
+                    (None, _) if condition => writeln!(docs, " (condition)")?,
+                    (None, Some(default)) => {
+                        writeln!(docs, " (optional, defaults to {:?})", default)?
+                    }
+                    (None, None) => writeln!(docs)?,
                 }
             }
         }
@@ -639,6 +728,112 @@ impl Interpolation {
         }
     }
 
+    /// `fill_defaults` sets the variables with a default value that were not given,
+    /// through a trait per variable implemented for the builder states where the variable is set or not.
+    fn fill_defaults_impl(
+        ident: &syn::Ident,
+        typed_builder_name: &syn::Ident,
+        fields: &[Field],
+    ) -> TokenStream {
+        let right_generics = fields
+            .iter()
+            .flat_map(Field::as_right_generics)
+            .collect::<Vec<_>>();
+        // the builder state has the locale and into views marker fields first.
+        let states = (0..fields.len() + 2)
+            .map(|i| format_ident!("__state_{}__", i))
+            .collect::<Vec<_>>();
+
+        let mut traits_impls = quote!();
+        let mut output = quote!(Self);
+        let mut fill = quote!(self);
+        let mut where_clauses = vec![];
+
+        for (i, field) in fields.iter().enumerate() {
+            let Some(default) = field.default() else {
+                continue;
+            };
+            let Field { key, generic, .. } = field;
+            let trait_ident = format_ident!("{}_fill_{}", ident, key);
+            let state_index = i + 2;
+            let with_state = |state: TokenStream| {
+                states.iter().enumerate().map(move |(j, s)| {
+                    if j == state_index {
+                        state.clone()
+                    } else {
+                        s.to_token_stream()
+                    }
+                })
+            };
+            let other_states = states
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != state_index)
+                .map(|(_, s)| s);
+            let other_generics = right_generics.iter().filter(|g| **g != generic);
+            let defaulted_generics = right_generics.iter().map(|g| {
+                if *g == generic {
+                    quote!(&'static str)
+                } else {
+                    g.to_token_stream()
+                }
+            });
+            let unset_states = with_state(quote!(()));
+            let filled_states = with_state(quote!((&'static str,)));
+            let set_states = with_state(quote!((#generic,)));
+            let defaulted_generics = defaulted_generics.collect::<Vec<_>>();
+            let other_generics = other_generics.collect::<Vec<_>>();
+            let other_states = other_states.collect::<Vec<_>>();
+
+            traits_impls.extend(quote! {
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                pub trait #trait_ident {
+                    type Output;
+                    fn fill(self) -> Self::Output;
+                }
+
+                #[allow(non_camel_case_types)]
+                impl<#(#other_generics,)* #(#other_states,)*> #trait_ident for #typed_builder_name<#(#defaulted_generics,)* (#(#unset_states,)*)> {
+                    type Output = #typed_builder_name<#(#defaulted_generics,)* (#(#filled_states,)*)>;
+                    #[inline]
+                    fn fill(self) -> Self::Output {
+                        self.#key(#default)
+                    }
+                }
+
+                #[allow(non_camel_case_types)]
+                impl<#(#right_generics,)* #(#other_states,)*> #trait_ident for #typed_builder_name<#(#right_generics,)* (#(#set_states,)*)> {
+                    type Output = Self;
+                    #[inline]
+                    fn fill(self) -> Self::Output {
+                        self
+                    }
+                }
+            });
+
+            where_clauses.push(quote!(#output: #trait_ident));
+            fill = quote!(#trait_ident::fill(#fill));
+            output = quote!(<#output as #trait_ident>::Output);
+        }
+
+        quote! {
+            #traits_impls
+
+            #[allow(non_camel_case_types)]
+            impl<#(#right_generics,)* __state__> #typed_builder_name<#(#right_generics,)* __state__>
+            where
+                #(#where_clauses,)*
+            {
+                /// Set the variables with a default value that were not given.
+                #[inline]
+                pub fn fill_defaults(self) -> #output {
+                    #fill
+                }
+            }
+        }
+    }
+
     fn display_impl(
         key: &Key,
         ident: &syn::Ident,
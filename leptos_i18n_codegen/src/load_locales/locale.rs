@@ -7,6 +7,7 @@ pub enum LiteralType {
     Signed,
     Unsigned,
     Float,
+    List,
 }
 
 impl From<leptos_i18n_parser::parse_locales::locale::LiteralType> for LiteralType {
@@ -17,6 +18,7 @@ impl From<leptos_i18n_parser::parse_locales::locale::LiteralType> for LiteralTyp
             leptos_i18n_parser::parse_locales::locale::LiteralType::Signed => Self::Signed,
             leptos_i18n_parser::parse_locales::locale::LiteralType::Unsigned => Self::Unsigned,
             leptos_i18n_parser::parse_locales::locale::LiteralType::Float => Self::Float,
+            leptos_i18n_parser::parse_locales::locale::LiteralType::List(_) => Self::List,
         }
     }
 }
@@ -29,6 +31,7 @@ impl ToTokens for LiteralType {
             LiteralType::Signed => quote!(i64),
             LiteralType::Unsigned => quote!(u64),
             LiteralType::Float => quote!(f64),
+            LiteralType::List => quote!(l_i18n_crate::__private::LitList),
         }
     }
 
@@ -1,10 +1,15 @@
-use std::{collections::BTreeMap, ops::Not};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Not,
+};
 
+pub mod condition;
 pub mod interpolate;
 pub mod locale;
 pub mod parsed_value;
 pub mod plurals;
 pub mod ranges;
+pub mod select;
 
 use interpolate::Interpolation;
 use leptos_i18n_parser::{
@@ -12,8 +17,10 @@ use leptos_i18n_parser::{
         ParsedLocales,
         error::{Error, Result},
         locale::{BuildersKeys, BuildersKeysInner, InterpolOrLit, Locale, LocaleValue, Namespace},
+        metadata::KeyMetadata,
         options::ParseOptions,
         parsed_value::ParsedValue,
+        select::SELECT_OTHER,
     },
     utils::{
         UnwrapAt,
@@ -83,6 +90,7 @@ pub fn load_locales(
         gen_docs,
     )?;
     let scopes_mod = create_scopes_module(builder_keys);
+    let selects_mod = create_selects_module(builder_keys)?;
 
     let mut macros_reexport = vec![
         quote!(t),
@@ -246,6 +254,8 @@ pub fn load_locales(
 
             #scopes_mod
 
+            #selects_mod
+
             #[inline]
             #[track_caller]
             pub fn use_i18n() -> l_i18n_crate::I18nContext<#enum_ident> {
@@ -587,6 +597,7 @@ struct Subkeys<'a> {
     locales: &'a [Locale],
     keys: &'a BuildersKeysInner,
     docs: TokenStream,
+    deprecated: Option<TokenStream>,
 }
 
 impl<'a> Subkeys<'a> {
@@ -603,13 +614,19 @@ impl<'a> Subkeys<'a> {
         key_path: &KeyPath,
         locales: &'a [Locale],
         keys: &'a BuildersKeysInner,
+        metadata: Option<&KeyMetadata>,
         gen_docs: bool,
     ) -> Self {
         let mod_key = Self::mod_ident(&key);
         let new_key = Self::item_ident(&key);
         let docs = if gen_docs {
+            use core::fmt::Write;
             let path = key_path.to_string_with_key(&key);
-            let mut docs = format!("Full path: `{}`\n", path);
+            let mut docs = String::new();
+            if let Some(metadata) = metadata {
+                metadata.write_docs(&mut docs).unwrap();
+            }
+            writeln!(docs, "Full path: `{}`", path).unwrap();
             gen_keys_doc(&mut docs, &keys.0).unwrap();
             quote! {
                 #[doc = #docs]
@@ -624,6 +641,7 @@ impl<'a> Subkeys<'a> {
             locales,
             keys,
             docs,
+            deprecated: deprecated_attr(metadata),
         }
     }
 }
@@ -680,6 +698,128 @@ fn create_scopes_module(keys: &BuildersKeys) -> TokenStream {
     }
 }
 
+fn collect_select_variants(
+    keys: &BTreeMap<Key, LocaleValue>,
+    selects: &mut BTreeMap<Key, BTreeSet<Key>>,
+) {
+    for value in keys.values() {
+        match value {
+            LocaleValue::Subkeys { keys, .. } => collect_select_variants(&keys.0, selects),
+            LocaleValue::Value {
+                value: InterpolOrLit::Interpol(keys),
+                ..
+            } => {
+                for (key, infos) in keys.iter_vars() {
+                    if let Some(variants) = &infos.select_variants {
+                        selects
+                            .entry(key)
+                            .or_default()
+                            .extend(variants.iter().cloned());
+                    }
+                }
+            }
+            LocaleValue::Value { .. } => {}
+        }
+    }
+}
+
+/// The `PascalCase` identifier for a select enum or variant, built from the identifier of its key.
+fn to_pascal_case(ident: &str) -> Ident {
+    let ident = ident.strip_prefix("r#").unwrap_or(ident);
+    let mut name = ident
+        .split('_')
+        .flat_map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars))
+                .into_iter()
+                .flatten()
+        })
+        .collect::<String>();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    // `Self` is the only keyword in PascalCase
+    if syn::parse_str::<Ident>(&name).is_err() {
+        name.push('_');
+    }
+    Ident::new(&name, Span::call_site())
+}
+
+fn create_selects_module(keys: &BuildersKeys) -> Result<Option<TokenStream>> {
+    let mut selects = BTreeMap::new();
+    match keys {
+        BuildersKeys::NameSpaces { keys, .. } => {
+            for keys in keys.values() {
+                collect_select_variants(&keys.0, &mut selects);
+            }
+        }
+        BuildersKeys::Locales { keys, .. } => collect_select_variants(&keys.0, &mut selects),
+    }
+
+    if selects.is_empty() {
+        return Ok(None);
+    }
+
+    let other = Key::new(SELECT_OTHER).unwrap_at("SELECT_OTHER");
+
+    let mut enums = Vec::with_capacity(selects.len());
+    for (key, variants) in &selects {
+        let var_name = key.name.strip_prefix("var_").unwrap_or(&key.name);
+        let enum_ident = to_pascal_case(var_name);
+        let mut variants_by_ident = BTreeMap::<String, &Key>::new();
+        let mut variants_idents = Vec::with_capacity(variants.len() + 1);
+        for variant in variants.iter().chain(Some(&other)) {
+            let ident = to_pascal_case(&variant.ident.to_string());
+            if let Some(first) = variants_by_ident.insert(ident.to_string(), variant) {
+                return Err(Error::SelectVariantCollision {
+                    select: var_name.to_string(),
+                    variant: variant.clone(),
+                    other: first.clone(),
+                    ident: ident.to_string(),
+                }
+                .into());
+            }
+            variants_idents.push((ident, &*variant.name));
+        }
+        let variant_names = variants_idents.iter().map(|(ident, _)| ident);
+        let match_arms = variants_idents
+            .iter()
+            .map(|(ident, name)| quote!(#enum_ident::#ident => #name));
+        let docs = format!("Possible values for the `{}` select.", var_name);
+        enums.push(quote! {
+            #[doc = #docs]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #[allow(non_camel_case_types)]
+            pub enum #enum_ident {
+                #(#variant_names,)*
+            }
+
+            impl #enum_ident {
+                /// Return the name of the variant as written in the translations.
+                pub const fn as_str(self) -> &'static str {
+                    match self {
+                        #(#match_arms,)*
+                    }
+                }
+            }
+
+            impl core::convert::AsRef<str> for #enum_ident {
+                fn as_ref(&self) -> &str {
+                    self.as_str()
+                }
+            }
+        });
+    }
+
+    Ok(Some(quote! {
+        pub mod selects {
+            #(#enums)*
+        }
+    }))
+}
+
 fn create_scopes_module_inner(
     keys: &BTreeMap<Key, LocaleValue>,
 ) -> impl Iterator<Item = TokenStream> {
@@ -742,8 +882,15 @@ fn create_locale_type_inner<const IS_TOP: bool>(
     let literal_accessors = literal_keys
         .iter()
         .map(|(key, literal_type, defaults)| {
+            let metadata = KeyMetadata::find(locales, key);
+            let deprecated = deprecated_attr(metadata);
+            let docs = metadata.filter(|_| gen_docs).map(|metadata| {
+                let mut docs = String::new();
+                metadata.write_docs(&mut docs).unwrap();
+                quote!(#[doc = #docs])
+            });
             let computed_defaults= defaults.compute();
-            if options.show_keys_only {
+            let accessor = if options.show_keys_only {
                 let key_str = key_path.to_string_with_key(key);
                 if cfg!(all(feature = "dynamic_load", not(feature = "ssr"))) {
                     quote! {
@@ -771,7 +918,7 @@ fn create_locale_type_inner<const IS_TOP: bool>(
                 let match_arms = locales.iter().filter_map(|locale| {
                     let lit = locale
                         .keys
-                        .get(key)
+                        .get(*key)
                         .unwrap_at("create_locale_type_inner_1");
                     if matches!(lit, ParsedValue::Default) {
                         return None;
@@ -784,7 +931,7 @@ fn create_locale_type_inner<const IS_TOP: bool>(
                         }).collect::<TokenStream>()
                     });
                     let lit = parsed_value::to_token_stream(lit, locale.top_locale_string_count);
-                    let ts = if *literal_type == LiteralType::String {
+                    let ts = if matches!(literal_type, LiteralType::String | LiteralType::List) {
                         let strings_count = locale.top_locale_string_count;
                         if cfg!(all(feature = "dynamic_load", not(feature = "ssr"))) {
                             quote! {
@@ -860,6 +1007,11 @@ fn create_locale_type_inner<const IS_TOP: bool>(
                         }
                     }
                 }
+            };
+            quote! {
+                #docs
+                #deprecated
+                #accessor
             }
         })
         .collect::<Vec<_>>();
@@ -867,8 +1019,19 @@ fn create_locale_type_inner<const IS_TOP: bool>(
     let subkeys = keys
         .iter()
         .filter_map(|(key, value)| match value {
-            LocaleValue::Subkeys { locales, keys } => {
-                Some(Subkeys::new(key.clone(), key_path, locales, keys, gen_docs))
+            LocaleValue::Subkeys {
+                locales: subkeys_locales,
+                keys,
+            } => {
+                let metadata = KeyMetadata::find(locales, key);
+                Some(Subkeys::new(
+                    key.clone(),
+                    key_path,
+                    subkeys_locales,
+                    keys,
+                    metadata,
+                    gen_docs,
+                ))
             }
             _ => None,
         })
@@ -905,8 +1068,10 @@ fn create_locale_type_inner<const IS_TOP: bool>(
         let key = &sk.key;
         let mod_ident = &sk.mod_key;
         let docs = &sk.docs;
+        let deprecated = &sk.deprecated;
         quote! {
             #docs
+            #deprecated
             pub const fn #original_key(self) -> subkeys::#mod_ident::#key {
                 subkeys::#mod_ident::#key::__new_internal(self.0)
             }
@@ -945,8 +1110,10 @@ fn create_locale_type_inner<const IS_TOP: bool>(
     let builder_accessors = builders.iter().map(|(key, inter)| {
         let inter_ident = &inter.ident;
         let docs = &inter.docs;
+        let deprecated = deprecated_attr(KeyMetadata::find(locales, key));
         quote! {
             #docs
+            #deprecated
             pub const fn #key(self) -> builders::#inter_ident {
                 builders::#inter_ident::new(self.0)
             }
@@ -1579,6 +1746,13 @@ fn create_locale_type(
     }
 }
 
+fn deprecated_attr(metadata: Option<&KeyMetadata>) -> Option<TokenStream> {
+    match metadata?.deprecated.as_deref()? {
+        "" => Some(quote!(#[deprecated])),
+        note => Some(quote!(#[deprecated(note = #note)])),
+    }
+}
+
 fn gen_keys_doc(docs: &mut String, keys: &BTreeMap<Key, LocaleValue>) -> core::fmt::Result {
     use core::fmt::Write;
     let mut keys_iter = keys
@@ -1613,3 +1787,46 @@ fn gen_keys_doc(docs: &mut String, keys: &BTreeMap<Key, LocaleValue>) -> core::f
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos_i18n_parser::parse_locales::{
+        options::{Config, KeyMapping},
+        parse_locales,
+    };
+
+    #[test]
+    fn pascal_case_idents() {
+        assert_eq!(to_pascal_case("magic_link").to_string(), "MagicLink");
+        assert_eq!(to_pascal_case("_2fa").to_string(), "_2fa");
+        assert_eq!(to_pascal_case("r#type").to_string(), "Type");
+        assert_eq!(to_pascal_case("self").to_string(), "Self_");
+    }
+
+    #[test]
+    fn select_enum_from_mapped_variants() {
+        let dir = std::env::temp_dir().join(format!(
+            "leptos_i18n_codegen_selects_{}",
+            std::process::id()
+        ));
+        let locales_dir = dir.join("locales");
+        std::fs::create_dir_all(&locales_dir).unwrap();
+        std::fs::write(
+            locales_dir.join("en.json"),
+            r#"{ "login": { "$select": "method", "2fa": "Enter the code", "magic-link": "Check your inbox", "other": "Enter your password" } }"#,
+        )
+        .unwrap();
+        let options = ParseOptions::default().key_mapping(KeyMapping::new().sanitize(true));
+        let cfg = Config::new("en").unwrap().parse_options(options);
+        let parsed = parse_locales(Some(dir), cfg).unwrap();
+
+        let selects = create_selects_module(&parsed.builder_keys)
+            .unwrap()
+            .unwrap()
+            .to_string();
+        assert!(selects.contains("pub enum Method { _2fa , MagicLink , Other , }"));
+        assert!(selects.contains("Method :: MagicLink => \"magic-link\""));
+        assert!(selects.contains("Method :: _2fa => \"2fa\""));
+    }
+}
@@ -9,7 +9,7 @@ use leptos_i18n_parser::{
     utils::{Key, KeyPath, UnwrapAt},
 };
 
-use super::{interpolate::LOCALE_FIELD_KEY, plurals, ranges};
+use super::{condition, interpolate::LOCALE_FIELD_KEY, plurals, ranges, select};
 
 pub const TRANSLATIONS_KEY: &str = if cfg!(feature = "dynamic_load") {
     "__i18n_translations__"
@@ -80,8 +80,18 @@ fn flatten(
         ParsedValue::Default => unreachable!("defaulted value should never have been rendered"),
         ParsedValue::Subkeys(_) => unreachable!("subkeys should never have been rendered"),
         ParsedValue::Literal(lit) => tokens.push(Literal::from(lit).to_token_stream(strings_count)),
+        ParsedValue::List(items) => {
+            let translations_key = Key::new(TRANSLATIONS_KEY).unwrap_at("TRANSLATIONS_KEY");
+            let indices = items.iter().map(|item| match item {
+                leptos_i18n_parser::parse_locales::parsed_value::Literal::String(_, index) => index,
+                _ => unreachable!("list items are always strings"),
+            });
+            tokens.push(
+                quote!(l_i18n_crate::__private::LitList::new(#translations_key, &[#(#indices),*])),
+            )
+        }
         ParsedValue::Ranges(ranges) => tokens.push(ranges::to_token_stream(ranges, strings_count)),
-        ParsedValue::Variable { key, bounds } => {
+        ParsedValue::Variable { key, bounds, .. } => {
             let ts = bounds.var_to_view(&key.ident, &locale_field.ident);
             tokens.push(quote! {{
                     let #key = core::clone::Clone::clone(&#key);
@@ -144,6 +154,10 @@ fn flatten(
         ParsedValue::Plurals(plurals) => {
             tokens.push(plurals::to_token_stream(plurals, strings_count))
         }
+        ParsedValue::Select(select) => tokens.push(select::to_token_stream(select, strings_count)),
+        ParsedValue::Condition(condition) => {
+            tokens.push(condition::to_token_stream(condition, strings_count))
+        }
         // don't emit any code for dummies, it will default to "" just for compiling
         ParsedValue::Dummy(_) => {}
     }
@@ -158,12 +172,13 @@ fn flatten_string(
     match this {
         ParsedValue::Default => unreachable!("defaulted value should never have been rendered"),
         ParsedValue::Subkeys(_) => unreachable!("subkeys should never have been rendered"),
+        ParsedValue::List(_) => unreachable!("lists are only rendered as literals"),
         ParsedValue::Literal(lit) => {
             let ts = Literal::from(lit).to_token_stream(strings_count);
             tokens.push(quote!(core::fmt::Display::fmt(&#ts, __formatter)))
         }
         ParsedValue::Ranges(ranges) => tokens.push(ranges::as_string_impl(ranges, strings_count)),
-        ParsedValue::Variable { key, bounds } => {
+        ParsedValue::Variable { key, bounds, .. } => {
             let ts = bounds.var_fmt(key, locale_field);
             tokens.push(ts);
         }
@@ -206,6 +221,10 @@ fn flatten_string(
             &plurals.count_key,
             strings_count,
         )),
+        ParsedValue::Select(select) => tokens.push(select::as_string_impl(select, strings_count)),
+        ParsedValue::Condition(condition) => {
+            tokens.push(condition::as_string_impl(condition, strings_count))
+        }
         // Same as for view
         ParsedValue::Dummy(_) => {}
     }
@@ -57,12 +57,16 @@ impl From<leptos_i18n_parser::parse_locales::plurals::PluralForm> for PluralForm
             leptos_i18n_parser::parse_locales::plurals::PluralForm::Few => PluralForm::Few,
             leptos_i18n_parser::parse_locales::plurals::PluralForm::Many => PluralForm::Many,
             leptos_i18n_parser::parse_locales::plurals::PluralForm::Other => PluralForm::Other,
+            leptos_i18n_parser::parse_locales::plurals::PluralForm::Exact(value) => {
+                PluralForm::Exact(value)
+            }
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PluralForm {
+    Exact(u64),
     Zero,
     One,
     Two,
@@ -86,12 +90,41 @@ impl ToTokens for PluralForm {
             PluralForm::Other => {
                 quote!(l_i18n_crate::reexports::icu::plurals::PluralCategory::Other)
             }
+            // exact forms are compared to the operands of the count
+            PluralForm::Exact(value) => {
+                quote!(l_i18n_crate::reexports::icu::plurals::PluralOperands::from(#value))
+            }
         }
     }
 }
 
+/// The exact forms compared to the count before its category, as `if .. else` branches.
+fn exact_branches(
+    get_operands: TokenStream,
+    branches: impl Iterator<Item = (PluralForm, TokenStream)>,
+) -> (Option<TokenStream>, Vec<TokenStream>) {
+    let branches = branches
+        .map(|(form, ts)| quote!(if _operands == #form { #ts } else))
+        .collect::<Vec<_>>();
+    let operands = (!branches.is_empty()).then(|| {
+        quote!(let _operands: l_i18n_crate::reexports::icu::plurals::PluralOperands = core::convert::Into::into(#get_operands);)
+    });
+    (operands, branches)
+}
+
 pub fn as_string_impl(this: &Plurals, count_key: &Key, strings_count: usize) -> TokenStream {
-    let match_arms = this.forms.iter().map(|(form, value)| {
+    let (exact_forms, forms): (Vec<_>, Vec<_>) =
+        this.forms.iter().partition(|(form, _)| form.is_exact());
+
+    let (operands, exact_branches) = exact_branches(
+        quote!(core::clone::Clone::clone(#count_key)),
+        exact_forms.into_iter().map(|(form, value)| {
+            let ts = parsed_value::as_string_impl(value, strings_count);
+            (PluralForm::from(*form), ts)
+        }),
+    );
+
+    let match_arms = forms.into_iter().map(|(form, value)| {
         let form = PluralForm::from(*form);
         let ts = parsed_value::as_string_impl(value, strings_count);
         quote!(#form => { #ts })
@@ -103,21 +136,47 @@ pub fn as_string_impl(this: &Plurals, count_key: &Key, strings_count: usize) ->
 
     let rule_type = PluralRuleType::from(this.rule_type);
 
+    let offset = this.offset;
+    let offset_count = (offset != 0).then(|| {
+        quote!(let #count_key = &l_i18n_crate::__private::PluralCountOffset::offset_by(core::clone::Clone::clone(#count_key), #offset);)
+    });
+
     quote! {{
         let _plural_rules = l_i18n_crate::__private::get_plural_rules(*#locale_field, #rule_type);
-        match _plural_rules.category_for(core::clone::Clone::clone(#count_key)) {
-            #(#match_arms,)*
-            _ => #other,
+        #operands
+        #offset_count
+        #(#exact_branches)* {
+            match _plural_rules.category_for(core::clone::Clone::clone(#count_key)) {
+                #(#match_arms,)*
+                _ => #other,
+            }
         }
     }}
 }
 
 pub fn to_token_stream(this: &Plurals, strings_count: usize) -> TokenStream {
     let either_of = EitherOfWrapper::new(this.forms.len() + 1);
-    let match_arms = this.forms.iter().enumerate().map(|(i, (form, value))| {
+    let (exact_forms, forms): (Vec<_>, Vec<_>) =
+        this.forms.iter().partition(|(form, _)| form.is_exact());
+    let exact_count = exact_forms.len();
+
+    let count_key = &this.count_key;
+
+    let (operands, exact_branches) = exact_branches(
+        quote!(#count_key()),
+        exact_forms
+            .into_iter()
+            .enumerate()
+            .map(|(i, (form, value))| {
+                let ts = parsed_value::to_token_stream(value, strings_count);
+                (PluralForm::from(*form), either_of.wrap(i, ts))
+            }),
+    );
+
+    let match_arms = forms.into_iter().enumerate().map(|(i, (form, value))| {
         let form = PluralForm::from(*form);
         let ts = parsed_value::to_token_stream(value, strings_count);
-        let ts = either_of.wrap(i, ts);
+        let ts = either_of.wrap(exact_count + i, ts);
         quote!(#form => { #ts })
     });
 
@@ -142,7 +201,16 @@ pub fn to_token_stream(this: &Plurals, strings_count: usize) -> TokenStream {
 
     let rule_type = PluralRuleType::from(this.rule_type);
 
-    let count_key = &this.count_key;
+    let offset = this.offset;
+    // the branches see the count minus the offset
+    let offset_count = (offset != 0).then(|| {
+        quote! {
+            let #count_key = {
+                let #count_key = core::clone::Clone::clone(&#count_key);
+                move || l_i18n_crate::__private::PluralCountOffset::offset_by(#count_key(), #offset)
+            };
+        }
+    });
 
     let other_ts = parsed_value::to_token_stream(&this.other, strings_count);
     let other = either_of.wrap(this.forms.len(), other_ts);
@@ -152,9 +220,13 @@ pub fn to_token_stream(this: &Plurals, strings_count: usize) -> TokenStream {
             #captured_values
             let _plural_rules = l_i18n_crate::__private::get_plural_rules(#locale_field, #rule_type);
             move || {
-                match _plural_rules.category_for(#count_key()) {
-                    #(#match_arms,)*
-                    _ => #other,
+                #operands
+                #offset_count
+                #(#exact_branches)* {
+                    match _plural_rules.category_for(#count_key()) {
+                        #(#match_arms,)*
+                        _ => #other,
+                    }
                 }
             }
         }
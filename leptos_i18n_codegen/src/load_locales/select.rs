@@ -0,0 +1,80 @@
+use leptos_i18n_parser::{
+    parse_locales::{
+        locale::{InterpolOrLit, LiteralType},
+        select::Select,
+    },
+    utils::{KeyPath, UnwrapAt},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{load_locales::parsed_value, utils::EitherOfWrapper};
+
+pub fn as_string_impl(this: &Select, strings_count: usize) -> TokenStream {
+    let match_arms = this.variants.iter().map(|(variant, value)| {
+        let variant = &*variant.name;
+        let ts = parsed_value::as_string_impl(value, strings_count);
+        quote!(#variant => { #ts })
+    });
+
+    let other = parsed_value::as_string_impl(&this.other, strings_count);
+
+    let select_key = &this.select_key;
+
+    quote! {
+        match core::convert::AsRef::<str>::as_ref(#select_key) {
+            #(#match_arms,)*
+            _ => #other,
+        }
+    }
+}
+
+pub fn to_token_stream(this: &Select, strings_count: usize) -> TokenStream {
+    let either_of = EitherOfWrapper::new(this.variants.len() + 1);
+    let match_arms = this
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(i, (variant, value))| {
+            let variant = &*variant.name;
+            let ts = parsed_value::to_token_stream(value, strings_count);
+            let ts = either_of.wrap(i, ts);
+            quote!(#variant => { #ts })
+        });
+
+    let other = &*this.other;
+
+    let mut captured_values = InterpolOrLit::Lit(LiteralType::String);
+    let mut key_path = KeyPath::new(None);
+
+    for value in this.variants.values().chain(Some(other)) {
+        value
+            .get_keys_inner(&mut key_path, &mut captured_values, false)
+            .unwrap_at("select::to_token_stream_1");
+    }
+
+    let captured_values = captured_values.is_interpol().map(|keys| {
+        let keys = keys
+            .iter_keys()
+            .map(|key| quote!(let #key = core::clone::Clone::clone(&#key);));
+        quote!(#(#keys)*)
+    });
+
+    let select_key = &this.select_key;
+
+    let other_ts = parsed_value::to_token_stream(other, strings_count);
+    let other = either_of.wrap(this.variants.len(), other_ts);
+
+    quote! {
+        {
+            #captured_values
+            let #select_key = core::clone::Clone::clone(&#select_key);
+            move || {
+                match core::convert::AsRef::<str>::as_ref(&#select_key()) {
+                    #(#match_arms,)*
+                    _ => #other,
+                }
+            }
+        }
+    }
+}
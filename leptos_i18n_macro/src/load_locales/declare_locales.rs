@@ -8,7 +8,8 @@ use leptos_i18n_parser::{
         error::Diagnostics,
         locale::{Locale, LocalesOrNamespaces},
         make_builder_keys,
-        options::{Config, ParseOptions},
+        options::{Config, KeyMapping, ParseOptions},
+        overlay::OverlayChecks,
         parsed_value::ParsedValue,
         ranges::{
             ParseRanges, Range, RangeNumber, Ranges, RangesInner, TypeOrRange, UntypedRangesInner,
@@ -37,7 +38,14 @@ pub fn declare_locales(tokens: proc_macro::TokenStream) -> proc_macro::TokenStre
 
     cfg.options = ParseOptions::default().interpolate_display(interpolate_display);
 
-    let builder_keys = make_builder_keys(locales, &cfg, foreign_keys_paths, &diag).unwrap();
+    let builder_keys = make_builder_keys(
+        locales,
+        &cfg,
+        foreign_keys_paths,
+        OverlayChecks::default(),
+        &diag,
+    )
+    .unwrap();
 
     let parsed_locales = ParsedLocales {
         cfg,
@@ -106,6 +114,7 @@ fn parse_str_value(
         formatters,
         diag: &diag,
         parse_fns: ParsedValue::DEFAULT_FNS,
+        key_mapping: &KeyMapping::new(),
     };
 
     match ParsedValue::new(&ctx, &value) {
@@ -148,6 +157,7 @@ fn parse_map_values(
         keys,
         strings: vec![],
         top_locale_string_count: 0,
+        metadata: BTreeMap::new(),
     }))))
 }
 
@@ -277,6 +287,7 @@ fn parse_ranges(
             inner: UntypedRangesInner::I32(vec![range]),
             count_key: Key::count(),
         },
+        TypeOrRange::Item(_) => unreachable!("parse_range_type never returns list items"),
     };
 
     ranges.deserialize_inner(RangeParseBuffer(content), seed)?;
@@ -391,6 +402,7 @@ fn parse_locale(
         keys,
         strings: vec![],
         top_locale_string_count: 0,
+        metadata: BTreeMap::new(),
     })
 }
 
@@ -472,6 +484,7 @@ impl syn::parse::Parse for ParsedInput {
                 locales: locales_key,
                 name_spaces: None,
                 locales_dir: "".into(),
+                overlay_dirs: vec![],
                 translations_uri: None,
                 extensions: Default::default(),
             },
@@ -90,9 +90,15 @@ pub fn t_macro_inner(
 impl OutputType {
     pub fn build_fns(self) -> (TokenStream, TokenStream) {
         match self {
-            OutputType::View => (quote!(builder), quote!(build().into_view)),
-            OutputType::String => (quote!(display_builder), quote!(build_string)),
-            OutputType::Display => (quote!(display_builder), quote!(build_display)),
+            OutputType::View => (quote!(builder), quote!(fill_defaults().build().into_view)),
+            OutputType::String => (
+                quote!(display_builder),
+                quote!(fill_defaults().build_string),
+            ),
+            OutputType::Display => (
+                quote!(display_builder),
+                quote!(fill_defaults().build_display),
+            ),
         }
     }
 
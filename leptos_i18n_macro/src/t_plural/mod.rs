@@ -4,7 +4,7 @@ use syn::parse_macro_input;
 
 use parsed_input::ParsedInput;
 
-use leptos_i18n_codegen::load_locales::plurals::PluralRuleType;
+use leptos_i18n_codegen::load_locales::plurals::{PluralForm, PluralRuleType};
 
 pub mod parsed_input;
 
@@ -48,6 +48,16 @@ pub fn t_plural_inner(
 
     let get_locale = input_type.get_locale(&ctx);
 
+    // exact forms are checked first, against the operands of the count
+    let (exact_forms, forms): (Vec<_>, Vec<_>) = forms
+        .iter()
+        .partition(|(form, _)| matches!(form, PluralForm::Exact(_)));
+    let operands = (!exact_forms.is_empty()).then(|| {
+        quote!(let _operands: l_i18n_crate::reexports::icu::plurals::PluralOperands = core::convert::Into::into(#count_ident());)
+    });
+    let exact_branches = exact_forms
+        .iter()
+        .map(|(form, block)| quote!(if _operands == #form { #block } else));
     let match_arms = forms.iter().map(|(form, block)| quote!(#form => #block));
     let fallback = fallback.map(|(expr, span)| {
         let fb = quote_spanned! { span => _ };
@@ -55,11 +65,14 @@ pub fn t_plural_inner(
     });
 
     let ts = quote! {
-        match leptos_i18n::__private::get_plural_category_for(#locale_ident, &#count_ident, #plural_type) {
-            #(
-                #match_arms,
-            )*
-            #fallback,
+        #operands
+        #(#exact_branches)* {
+            match leptos_i18n::__private::get_plural_category_for(#locale_ident, &#count_ident, #plural_type) {
+                #(
+                    #match_arms,
+                )*
+                #fallback,
+            }
         }
     };
 
@@ -1,7 +1,7 @@
 use proc_macro2::Span;
 use quote::ToTokens;
 use std::{collections::BTreeMap, fmt::Display};
-use syn::{Expr, Ident, Token, parse::ParseBuffer, spanned::Spanned, token::Comma};
+use syn::{Expr, Ident, LitInt, Token, parse::ParseBuffer, spanned::Spanned, token::Comma};
 
 use leptos_i18n_codegen::load_locales::plurals::PluralForm;
 
@@ -25,6 +25,14 @@ fn parse_plural_form(input: &ParseBuffer) -> syn::Result<(Option<PluralForm>, Ex
         let span = token.span();
         return Ok((None, block, span));
     }
+    if input.peek(Token![=]) && !input.peek(Token![=>]) {
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<LitInt>()?;
+        input.parse::<Token![=>]>()?;
+        let block = input.parse::<Expr>()?;
+        let form = PluralForm::Exact(value.base10_parse()?);
+        return Ok((Some(form), block, value.span()));
+    }
     let ident = input.parse::<Ident>()?;
     input.parse::<Token![=>]>()?;
     let block = input.parse::<Expr>()?;
@@ -43,7 +51,7 @@ fn parse_plural_form(input: &ParseBuffer) -> syn::Result<(Option<PluralForm>, Ex
     } else {
         return emit_err(
             ident,
-            "Unknown form. Allowed forms are \"zero\", \"one\", \"two\", \"few\", \"many\", \"other\" and \"_\" fallback.",
+            "Unknown form. Allowed forms are \"zero\", \"one\", \"two\", \"few\", \"many\", \"other\", exact values such as \"=0\" and \"_\" fallback.",
         );
     };
     Ok((Some(form), block, ident.span()))
@@ -72,13 +80,14 @@ impl syn::parse::Parse for ParsedInput {
 
             if already_exist.is_some() {
                 let form = match form {
-                    None => "_",
-                    Some(PluralForm::Zero) => "zero",
-                    Some(PluralForm::One) => "one",
-                    Some(PluralForm::Two) => "two",
-                    Some(PluralForm::Few) => "few",
-                    Some(PluralForm::Many) => "many",
-                    Some(PluralForm::Other) => "other",
+                    None => "_".to_string(),
+                    Some(PluralForm::Zero) => "zero".to_string(),
+                    Some(PluralForm::One) => "one".to_string(),
+                    Some(PluralForm::Two) => "two".to_string(),
+                    Some(PluralForm::Few) => "few".to_string(),
+                    Some(PluralForm::Many) => "many".to_string(),
+                    Some(PluralForm::Other) => "other".to_string(),
+                    Some(PluralForm::Exact(value)) => format!("={value}"),
                 };
                 let msg = format!("Duplicate form {form}.");
                 return Err(syn::Error::new(span, msg));
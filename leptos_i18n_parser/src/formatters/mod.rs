@@ -262,6 +262,59 @@ pub trait FormatterToTokens: Any {
     }
 }
 
+/// The type of a `{{ var: type }}` variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarType(&'static str);
+
+impl VarType {
+    /// The types a variable can be declared with, `date`, `time` and `datetime` are parsed as their formatter instead.
+    pub const TYPES: &[&str] = &[
+        "str", "bool", "char", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+        "i64", "i128", "isize", "f32", "f64",
+    ];
+
+    pub fn new(name: &str) -> Option<Self> {
+        Self::TYPES
+            .iter()
+            .find(|ty| **ty == name)
+            .map(|ty| VarType(ty))
+    }
+
+    pub fn name(self) -> &'static str {
+        self.0
+    }
+
+    /// If the count of plurals with an offset can be of this type, it must be a primitive integer implementing `PluralCountOffset`.
+    pub fn can_be_offset(self) -> bool {
+        matches!(
+            self.0,
+            "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize"
+        )
+    }
+
+    /// The type returned by the closure of the variable in views, `str` is an owned `String` there.
+    fn view_type(self) -> TokenStream {
+        match self.0 {
+            "str" => quote!(::std::string::String),
+            ty => {
+                let ty = Ident::new(ty, proc_macro2::Span::call_site());
+                quote!(#ty)
+            }
+        }
+    }
+
+    fn fmt_type(self) -> TokenStream {
+        let ty = Ident::new(self.0, proc_macro2::Span::call_site());
+        quote!(#ty)
+    }
+}
+
+impl Display for VarType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
 #[derive(Default, Clone)]
 pub enum VarBounds {
     /// NOT A FORMATTER, this formatter will emit no bound, this is for dummy code to reduce errors
@@ -269,6 +322,8 @@ pub enum VarBounds {
     #[default]
     None,
     AttributeValue,
+    /// A variable declared with a type, `{{ count: u32 }}`.
+    Typed(VarType),
     Formatted {
         formatter_name: &'static str,
         to_tokens: Rc<dyn FormatterToTokens>,
@@ -283,11 +338,22 @@ impl VarBounds {
         }
     }
 
+    pub fn var_type(&self) -> Option<VarType> {
+        match self {
+            Self::Typed(ty) => Some(*ty),
+            _ => None,
+        }
+    }
+
     pub fn view_bounds(&self) -> TokenStream {
         match self {
             Self::None => quote!(l_i18n_crate::__private::InterpolateVar),
             Self::AttributeValue => quote!(l_i18n_crate::reexports::leptos::attr::AttributeValue),
             Self::Dummy => quote!(l_i18n_crate::__private::AnyBound),
+            Self::Typed(ty) => {
+                let ty = ty.view_type();
+                quote!(l_i18n_crate::__private::InterpolateTypedVar<#ty>)
+            }
             Self::Formatted { to_tokens, .. } => to_tokens.view_bounds(),
         }
     }
@@ -296,6 +362,10 @@ impl VarBounds {
             Self::None => quote!(::std::fmt::Display),
             Self::AttributeValue => quote!(l_i18n_crate::display::AttributeValue),
             Self::Dummy => quote!(l_i18n_crate::__private::AnyBound),
+            Self::Typed(ty) => {
+                let ty = ty.fmt_type();
+                quote!(core::borrow::Borrow<#ty>)
+            }
             Self::Formatted { to_tokens, .. } => to_tokens.fmt_bounds(),
         }
     }
@@ -305,7 +375,7 @@ impl VarBounds {
             Self::AttributeValue => {
                 unreachable!("attributes values should be rendered by the component renderer.")
             }
-            Self::None => {
+            Self::None | Self::Typed(_) => {
                 quote!(#key)
             }
             Self::Dummy => {
@@ -322,6 +392,10 @@ impl VarBounds {
             Self::None => {
                 quote!(core::fmt::Display::fmt(#key, __formatter))
             }
+            Self::Typed(ty) => {
+                let ty = ty.fmt_type();
+                quote!(core::fmt::Display::fmt(core::borrow::Borrow::<#ty>::borrow(#key), __formatter))
+            }
             Self::Dummy => {
                 quote!({ let _ = #key; core::unimplemented!("Dummy formatter, parsing of a formatter must have failed.") })
             }
@@ -333,7 +407,7 @@ impl VarBounds {
             Self::AttributeValue => {
                 unreachable!("attributes values should be rendered by the component renderer.")
             }
-            Self::None => unreachable!(
+            Self::None | Self::Typed(_) => unreachable!(
                 "var_to_display function should not have been called on a variable with no formatter."
             ),
             Self::Dummy => {
@@ -351,12 +425,19 @@ impl Ord for VarBounds {
         match (self, other) {
             (VarBounds::Dummy, VarBounds::Dummy) => Ordering::Equal,
             (VarBounds::Dummy, VarBounds::None) => Ordering::Less,
+            (VarBounds::Dummy, VarBounds::Typed(_)) => Ordering::Less,
             (VarBounds::Dummy, VarBounds::Formatted { .. }) => Ordering::Less,
             (VarBounds::None, VarBounds::Dummy) => Ordering::Greater,
             (VarBounds::None, VarBounds::None) => Ordering::Equal,
+            (VarBounds::None, VarBounds::Typed(_)) => Ordering::Less,
             (VarBounds::None, VarBounds::Formatted { .. }) => Ordering::Less,
+            (VarBounds::Typed(_), VarBounds::Dummy) => Ordering::Greater,
+            (VarBounds::Typed(_), VarBounds::None) => Ordering::Greater,
+            (VarBounds::Typed(self_ty), VarBounds::Typed(other_ty)) => self_ty.cmp(other_ty),
+            (VarBounds::Typed(_), VarBounds::Formatted { .. }) => Ordering::Less,
             (VarBounds::Formatted { .. }, VarBounds::Dummy) => Ordering::Greater,
             (VarBounds::Formatted { .. }, VarBounds::None) => Ordering::Greater,
+            (VarBounds::Formatted { .. }, VarBounds::Typed(_)) => Ordering::Greater,
             (
                 VarBounds::Formatted {
                     formatter_name: self_name,
@@ -386,6 +467,7 @@ impl Debug for VarBounds {
             VarBounds::Dummy => f.write_str("Dummy"),
             VarBounds::AttributeValue => f.write_str("AttributeValue"),
             VarBounds::None => f.write_str("None"),
+            VarBounds::Typed(ty) => f.debug_tuple("Typed").field(ty).finish(),
             VarBounds::Formatted { formatter_name, .. } => f
                 .debug_struct("Formatted")
                 .field("formatter_name", &formatter_name)
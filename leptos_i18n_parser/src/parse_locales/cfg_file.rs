@@ -15,6 +15,7 @@ pub struct ConfigFile {
     pub locales: Vec<Key>,
     pub name_spaces: Option<Vec<Key>>,
     pub locales_dir: Cow<'static, str>,
+    pub overlay_dirs: Vec<String>,
     pub translations_uri: Option<String>,
     pub extensions: BTreeMap<Key, Key>,
 }
@@ -103,6 +104,12 @@ impl Into<Config> for ConfigFile {
             default_locale: self.default,
             locales: self.locales,
             locales_path,
+            translations_table: None,
+            overlay_paths: self
+                .overlay_dirs
+                .into_iter()
+                .map(|dir| Cow::Owned(dir.into()))
+                .collect(),
             namespaces,
             translations_uri,
             extensions: self.extensions,
@@ -132,6 +139,7 @@ pub enum Field {
     Locales,
     Namespaces,
     LocalesDir,
+    OverlayDirs,
     TranslationsUri,
     Extensions,
     Unknown,
@@ -142,6 +150,7 @@ impl Field {
     pub const LOCALES: &'static str = "locales";
     pub const NAMESPACES: &'static str = "namespaces";
     pub const LOCALES_DIR: &'static str = "locales-dir";
+    pub const OVERLAY_DIRS: &'static str = "overlay-dirs";
     pub const TRANSLATIONS_URI: &'static str = "translations-path";
     pub const EXTENSIONS: &'static str = "inherits";
     pub const FIELDS: &'static [&'static str] = &[
@@ -149,6 +158,7 @@ impl Field {
         Self::LOCALES,
         Self::NAMESPACES,
         Self::LOCALES_DIR,
+        Self::OVERLAY_DIRS,
         Self::TRANSLATIONS_URI,
         Self::EXTENSIONS,
     ];
@@ -185,6 +195,7 @@ impl serde::de::Visitor<'_> for FieldVisitor {
             Field::LOCALES => Ok(Field::Locales),
             Field::NAMESPACES => Ok(Field::Namespaces),
             Field::LOCALES_DIR => Ok(Field::LocalesDir),
+            Field::OVERLAY_DIRS => Ok(Field::OverlayDirs),
             Field::TRANSLATIONS_URI => Ok(Field::TranslationsUri),
             Field::EXTENSIONS => Ok(Field::Extensions),
             _ => Ok(Field::Unknown), // skip unknown fields
@@ -218,6 +229,7 @@ impl<'de> serde::de::Visitor<'de> for CfgFileVisitor {
         let mut locales: Option<Vec<Key>> = None;
         let mut name_spaces = None;
         let mut locales_dir = None;
+        let mut overlay_dirs = None;
         let mut translations_uri = None;
         let mut extensions: Option<BTreeMap<Key, Key>> = None;
         while let Some(field) = map.next_key::<Field>()? {
@@ -226,6 +238,9 @@ impl<'de> serde::de::Visitor<'de> for CfgFileVisitor {
                 Field::Locales => deser_field(&mut locales, &mut map, Field::LOCALES)?,
                 Field::Namespaces => deser_field(&mut name_spaces, &mut map, Field::NAMESPACES)?,
                 Field::LocalesDir => deser_field(&mut locales_dir, &mut map, Field::LOCALES_DIR)?,
+                Field::OverlayDirs => {
+                    deser_field(&mut overlay_dirs, &mut map, Field::OVERLAY_DIRS)?
+                }
                 Field::TranslationsUri => {
                     deser_field(&mut translations_uri, &mut map, Field::TRANSLATIONS_URI)?
                 }
@@ -266,6 +281,7 @@ impl<'de> serde::de::Visitor<'de> for CfgFileVisitor {
             locales,
             name_spaces,
             locales_dir,
+            overlay_dirs: overlay_dirs.unwrap_or_default(),
             translations_uri,
             extensions,
         })
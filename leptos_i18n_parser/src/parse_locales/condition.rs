@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use super::{
+    StringIndexer,
+    error::{Error, Result},
+    parsed_value::{Literal, ParsedValue},
+    plurals::Plurals,
+};
+use crate::utils::{Key, KeyPath, Loc};
+
+/// Opening of a conditional segment, followed by the name of the `bool` variable: `{{#if premium}}`
+pub const IF_OPEN: &str = "{{#if";
+
+/// Separates the two branches of a conditional segment.
+pub const IF_ELSE: &str = "{{else}}";
+
+/// Closing of a conditional segment.
+pub const IF_CLOSE: &str = "{{/if}}";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub condition_key: Key,
+    // Box for the same reason as `Plurals::other`
+    pub then: Box<ParsedValue>,
+    /// The `{{else}}` branch, an empty string if none.
+    pub otherwise: Box<ParsedValue>,
+}
+
+impl Condition {
+    pub fn get_branch(&self, condition: bool) -> &ParsedValue {
+        if condition {
+            &self.then
+        } else {
+            &self.otherwise
+        }
+    }
+
+    pub fn branches(&self) -> [&ParsedValue; 2] {
+        [&self.then, &self.otherwise]
+    }
+
+    pub fn branches_mut(&mut self) -> [&mut ParsedValue; 2] {
+        [&mut self.then, &mut self.otherwise]
+    }
+
+    fn populate_with_new_key(
+        &self,
+        new_key: Key,
+        args: &BTreeMap<String, ParsedValue>,
+        foreign_key: &KeyPath,
+        loc: &Loc,
+    ) -> Result<ParsedValue> {
+        Ok(ParsedValue::Condition(Condition {
+            condition_key: new_key,
+            then: Box::new(self.then.populate(args, foreign_key, loc)?),
+            otherwise: Box::new(self.otherwise.populate(args, foreign_key, loc)?),
+        }))
+    }
+
+    pub fn populate(
+        &self,
+        args: &BTreeMap<String, ParsedValue>,
+        foreign_key: &KeyPath,
+        loc: &Loc,
+    ) -> Result<ParsedValue> {
+        let Some(condition_arg) = args.get(&*self.condition_key.name) else {
+            return self.populate_with_new_key(self.condition_key.clone(), args, foreign_key, loc);
+        };
+
+        match condition_arg {
+            ParsedValue::Literal(Literal::Bool(condition)) => {
+                self.get_branch(*condition).populate(args, foreign_key, loc)
+            }
+            ParsedValue::Variable { key, .. } => {
+                self.populate_with_new_key(key.clone(), args, foreign_key, loc)
+            }
+            ParsedValue::Bloc(values) => {
+                let new_key = Plurals::find_variable(values, loc, foreign_key)?;
+                self.populate_with_new_key(new_key, args, foreign_key, loc)
+            }
+            _ => Err(Error::InvalidConditionArg {
+                loc: loc.into(),
+                foreign_key: foreign_key.to_owned(),
+            }
+            .into()),
+        }
+    }
+
+    pub fn index_strings(&mut self, strings: &mut StringIndexer) {
+        self.then.index_strings(strings);
+        self.otherwise.index_strings(strings);
+    }
+}
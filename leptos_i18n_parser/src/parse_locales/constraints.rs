@@ -0,0 +1,259 @@
+use std::fmt::Display;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{
+    error::{Diagnostics, Error, Warning},
+    locale::Locale,
+    metadata::KeyMetadata,
+    options::ParseOptions,
+    parsed_value::{ForeignKey, Literal, ParsedValue},
+};
+use crate::utils::{Key, KeyPath, Location};
+
+/// A value not respecting the constraints given by the metadata of its key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    TooLong { length: usize, max_length: u64 },
+    ForbiddenCharacter(char),
+    MissingTrailingPunctuation { expected: String },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::TooLong { length, max_length } => write!(
+                f,
+                "the value is {length} characters long but the max length is {max_length}"
+            ),
+            Violation::ForbiddenCharacter(c) => {
+                write!(f, "the value contains the forbidden character {c:?}")
+            }
+            Violation::MissingTrailingPunctuation { expected } => {
+                write!(f, "the value must end with one of {expected:?}")
+            }
+        }
+    }
+}
+
+impl KeyMetadata {
+    fn has_constraints(&self) -> bool {
+        self.max_length.is_some()
+            || self.forbidden_characters.is_some()
+            || self.trailing_punctuation.is_some()
+    }
+
+    /// Length counted for a variable of an interpolated value.
+    fn variable_length(&self, key: &Key, options: &ParseOptions) -> usize {
+        let name = key.name.strip_prefix("var_").unwrap_or(&key.name);
+        self.placeholders
+            .get(name)
+            .and_then(|placeholder| placeholder.max_length)
+            .map_or(options.variable_length_allowance, |max_length| {
+                max_length as usize
+            })
+    }
+}
+
+/// Number of user-perceived characters (extended grapheme clusters).
+pub fn graphemes_count(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+#[derive(Default)]
+struct Measure {
+    length: usize,
+    text: String,
+    /// `None` if the value ends with a variable.
+    last_char: Option<char>,
+}
+
+fn measure(value: &ParsedValue, metadata: &KeyMetadata, options: &ParseOptions) -> Measure {
+    match value {
+        ParsedValue::Literal(Literal::String(s, _)) => Measure {
+            length: graphemes_count(s),
+            text: s.clone(),
+            last_char: s.chars().last(),
+        },
+        ParsedValue::Variable { key, .. } => Measure {
+            length: metadata.variable_length(key, options),
+            ..Default::default()
+        },
+        ParsedValue::Component { inner, .. } => inner
+            .as_deref()
+            .map(|inner| measure(inner, metadata, options))
+            .unwrap_or_default(),
+        ParsedValue::Bloc(values) => {
+            let mut acc = Measure::default();
+            for value in values {
+                let measure = measure(value, metadata, options);
+                acc.length += measure.length;
+                acc.text.push_str(&measure.text);
+                let is_empty_literal =
+                    matches!(value, ParsedValue::Literal(Literal::String(s, _)) if s.is_empty());
+                if !is_empty_literal {
+                    acc.last_char = measure.last_char;
+                }
+            }
+            acc
+        }
+        ParsedValue::ForeignKey(foreign_key) => match &*foreign_key.borrow() {
+            ForeignKey::Set(inner) => measure(inner, metadata, options),
+            ForeignKey::NotSet(..) => Measure::default(),
+        },
+        ParsedValue::Plurals(_)
+        | ParsedValue::Select(_)
+        | ParsedValue::Condition(_)
+        | ParsedValue::Ranges(_) => {
+            // nested in a bloc, keep the longest branch.
+            let mut acc = Measure::default();
+            for_each_branch(value, &mut |branch| {
+                let measure = measure(branch, metadata, options);
+                acc.length = acc.length.max(measure.length);
+                acc.text.push_str(&measure.text);
+            });
+            acc
+        }
+        ParsedValue::Literal(_)
+        | ParsedValue::List(_)
+        | ParsedValue::Default
+        | ParsedValue::Subkeys(_)
+        | ParsedValue::Dummy(_) => Measure::default(),
+    }
+}
+
+/// Call `f` with every string a value can produce, each plural form, select variant, condition branch and range being a different string.
+fn for_each_branch(value: &ParsedValue, f: &mut dyn FnMut(&ParsedValue)) {
+    match value {
+        ParsedValue::Plurals(plurals) => {
+            for value in plurals.forms.values().chain(Some(&*plurals.other)) {
+                for_each_branch(value, f);
+            }
+        }
+        ParsedValue::Select(select) => {
+            for value in select.variants.values().chain(Some(&*select.other)) {
+                for_each_branch(value, f);
+            }
+        }
+        ParsedValue::Condition(condition) => {
+            for value in condition.branches() {
+                for_each_branch(value, f);
+            }
+        }
+        ParsedValue::Ranges(ranges) => {
+            let _ = ranges.try_for_each_value::<_, ()>(|value| {
+                for_each_branch(value, f);
+                Ok(())
+            });
+        }
+        ParsedValue::ForeignKey(foreign_key) => {
+            if let ForeignKey::Set(inner) = &*foreign_key.borrow() {
+                for_each_branch(inner, f);
+            }
+        }
+        value => f(value),
+    }
+}
+
+fn check_value(
+    value: &ParsedValue,
+    metadata: &KeyMetadata,
+    options: &ParseOptions,
+) -> Vec<Violation> {
+    let mut violations = vec![];
+    for_each_branch(value, &mut |branch| {
+        let Measure {
+            length,
+            text,
+            last_char,
+        } = measure(branch, metadata, options);
+        if let Some(max_length) = metadata.max_length
+            && length as u64 > max_length
+        {
+            violations.push(Violation::TooLong { length, max_length });
+        }
+        if let Some(forbidden) = &metadata.forbidden_characters
+            && let Some(c) = text.chars().find(|c| forbidden.contains(*c))
+        {
+            violations.push(Violation::ForbiddenCharacter(c));
+        }
+        if let Some(expected) = &metadata.trailing_punctuation
+            && !last_char.is_some_and(|c| expected.contains(c))
+        {
+            violations.push(Violation::MissingTrailingPunctuation {
+                expected: expected.clone(),
+            });
+        }
+    });
+    violations.dedup();
+    violations
+}
+
+/// Check the values of every locale against the constraints of their key,
+/// the metadata of a locale taking precedence over the ones of the default locale.
+pub fn check_locales(
+    locales: &[&Locale],
+    key_path: &mut KeyPath,
+    diag: &Diagnostics,
+    options: &ParseOptions,
+) {
+    let Some(default_locale) = locales.first() else {
+        return;
+    };
+    for (key, default_value) in &default_locale.keys {
+        let mut pushed_key = key_path.push_key(key.clone());
+        if let ParsedValue::Subkeys(Some(_)) = default_value {
+            let subkeys = locales
+                .iter()
+                .filter_map(|locale| match locale.keys.get(key) {
+                    Some(ParsedValue::Subkeys(Some(subkeys))) => Some(subkeys),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            check_locales(&subkeys, &mut pushed_key, diag, options);
+            continue;
+        }
+        let default_metadata = default_locale
+            .metadata
+            .get(key)
+            .filter(|metadata| metadata.has_constraints());
+        for locale in locales {
+            let Some(metadata) = locale
+                .metadata
+                .get(key)
+                .filter(|metadata| metadata.has_constraints())
+                .or(default_metadata)
+            else {
+                continue;
+            };
+            let Some(value) = locale.keys.get(key) else {
+                continue;
+            };
+            for violation in check_value(value, metadata, options) {
+                let loc = Location::new(locale.top_locale_name.clone(), pushed_key.clone());
+                if options.strict_constraints {
+                    diag.emit_error(Error::ConstraintViolation { loc, violation });
+                } else {
+                    diag.emit_warning(Warning::ConstraintViolation { loc, violation });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_graphemes() {
+        assert_eq!(graphemes_count("hello"), 5);
+        assert_eq!(graphemes_count("e\u{301}te\u{301}"), 3);
+        assert_eq!(graphemes_count("👍🏽 ok"), 4);
+        assert_eq!(graphemes_count("👨‍👩‍👧"), 1);
+        assert_eq!(graphemes_count("🇫🇷🇩🇪"), 2);
+        assert_eq!(graphemes_count("नमस्ते"), 3);
+        assert_eq!(graphemes_count("สวัสดี"), 4);
+        assert_eq!(graphemes_count("\u{1112}\u{1161}\u{11AB}"), 1);
+    }
+}
@@ -5,7 +5,7 @@ use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
 use std::{
     cell::{Cell, Ref, RefCell},
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Debug, Display},
     io,
     num::TryFromIntError,
@@ -15,6 +15,7 @@ use std::{
 
 use super::{locale::SerdeError, ranges::RangeType};
 use crate::{
+    formatters::VarType,
     parse_locales::cfg_file,
     utils::{
         Location,
@@ -40,6 +41,34 @@ pub enum Error {
         path: PathBuf,
         err: SerdeError,
     },
+    DuplicateKeyInLocaleDir {
+        loc: Location,
+        path: PathBuf,
+    },
+    KeyIdentCollision {
+        loc: Location,
+        other: Key,
+    },
+    SelectVariantCollision {
+        select: String,
+        variant: Key,
+        other: Key,
+        ident: String,
+    },
+    OverlayUnknownKey {
+        loc: Location,
+        overlay: PathBuf,
+    },
+    OverlaySignatureMismatch {
+        loc: Location,
+        overlay: PathBuf,
+        expected: String,
+        found: String,
+    },
+    InOverlay {
+        overlay: PathBuf,
+        err: Box<Error>,
+    },
     DuplicateLocalesInConfig(BTreeSet<Key>),
     DuplicateNamespacesInConfig(BTreeSet<Key>),
     SubKeyMissmatch {
@@ -64,6 +93,15 @@ pub enum Error {
     EmptyRange,
     InvalidRangeType(String),
     NestedRanges,
+    ListInRange,
+    InvalidListItem(String),
+    ListInValue {
+        key_path: KeyPath,
+    },
+    ListMismatch {
+        locale: Key,
+        key_path: KeyPath,
+    },
     InvalidFallback,
     MultipleFallbacks,
     MissingFallback(RangeType),
@@ -84,10 +122,17 @@ pub enum Error {
         foreign_key: KeyPath,
         loc: Location,
     },
+    SubkeysAliasInValue {
+        key_path: KeyPath,
+    },
     UnknownFormatter {
         name: String,
         loc: Location,
     },
+    UnknownVarType {
+        name: String,
+        loc: Location,
+    },
     ConflictingPluralRuleType {
         loc: Location,
     },
@@ -120,6 +165,86 @@ pub enum Error {
     PluralsAtNormalKey {
         loc: Location,
     },
+    MissingSelectFallback {
+        loc: Location,
+    },
+    SubkeysInSelect {
+        loc: Location,
+    },
+    SelectAtNormalKey {
+        loc: Location,
+    },
+    InvalidPluralForm {
+        loc: Location,
+        form: String,
+    },
+    MissingPluralFallback {
+        loc: Location,
+    },
+    PluralDimensionsMismatch {
+        loc: Location,
+    },
+    SubkeysInPlurals {
+        loc: Location,
+    },
+    ConflictingMarkers {
+        loc: Location,
+    },
+    OffsetWithoutPlurals {
+        loc: Location,
+    },
+    InvalidAlias {
+        loc: Location,
+        alias: String,
+    },
+    ConstraintViolation {
+        loc: Location,
+        violation: Violation,
+    },
+    MissingPluralForm {
+        locale: Key,
+        key: KeyPath,
+        forms: Vec<PluralForm>,
+    },
+    AliasWithValue {
+        loc: Location,
+    },
+    InvalidSelectArg {
+        loc: Location,
+        foreign_key: KeyPath,
+    },
+    SelectAndCountMix {
+        key_path: KeyPath,
+        key: Key,
+    },
+    VarDefaultMismatch {
+        key_path: KeyPath,
+        key: Key,
+    },
+    InvalidVarDefault {
+        key_path: KeyPath,
+        key: Key,
+    },
+    VarTypeMismatch {
+        key_path: KeyPath,
+        key: Key,
+    },
+    InvalidPluralOffsetType {
+        key_path: KeyPath,
+        key: Key,
+        ty: VarType,
+    },
+    InvalidConditionArg {
+        loc: Location,
+        foreign_key: KeyPath,
+    },
+    ConditionAndSelectMix {
+        key_path: KeyPath,
+        key: Key,
+    },
+    UnclosedCondition {
+        loc: Location,
+    },
     DisabledFormatter {
         loc: Location,
         formatter_err: &'static str,
@@ -128,7 +253,7 @@ pub enum Error {
         loc: Location,
     },
     NoFileFormats,
-    MultipleFilesFormats,
+    MultipleFilesFormats(Vec<PathBuf>),
     MissingTranslationsURI,
     InvalidFormatterArgName {
         loc: Location,
@@ -167,6 +292,31 @@ pub enum Error {
     DefaultLocaleCantInherit {
         loc: &'static panic::Location<'static>,
     },
+    UnsupportedFluentConstruct {
+        loc: Location,
+        construct: String,
+    },
+    DuplicateFluentEntry {
+        loc: Location,
+    },
+    InvalidIcuMessage {
+        loc: Location,
+        message: String,
+    },
+    UnsupportedIcuConstruct {
+        loc: Location,
+        construct: String,
+    },
+    InvalidXliff {
+        path: PathBuf,
+        err: String,
+    },
+    UnknownXliffLocale(String),
+    UnsupportedXliffFileFormat(String),
+    XliffUnitsWithoutFile {
+        locale_dir: PathBuf,
+        keys: Vec<String>,
+    },
 
     Custom(String),
 }
@@ -202,6 +352,27 @@ impl Display for Error {
             Error::LocaleFileDeser { path, err } => {
                 write!(f, "Parsing of file {path:?} failed: {err}")
             }
+            Error::DuplicateKeyInLocaleDir { loc, path } => write!(
+                f,
+                "Key \"{}\" of file {path:?} is already declared by another file of locale {:?}",
+                loc.key_path, loc.locale
+            ),
+            Error::OverlayUnknownKey { loc, overlay } => write!(
+                f,
+                "Overlay {overlay:?} declares key \"{}\" for locale {:?}, but overlays can only override keys of the base translations",
+                loc.key_path, loc.locale
+            ),
+            Error::OverlaySignatureMismatch {
+                loc,
+                overlay,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Overlay {overlay:?} changes the interpolations of key \"{}\" for locale {:?}: the base translations use {expected}, the overlay uses {found}",
+                loc.key_path, loc.locale
+            ),
+            Error::InOverlay { overlay, err } => write!(f, "{err} (in overlay {overlay:?})"),
             Error::RangeParse { range, range_type } => {
                 write!(f, "error parsing {range:?} as {range_type}")
             }
@@ -236,6 +407,21 @@ impl Display for Error {
                 f,
                 "Conflicting range value type at key \"{key_path}\", found type {type1} but also type {type2}."
             ),
+            Error::KeyIdentCollision { loc, other } => write!(
+                f,
+                "At {loc}, the key maps to the identifier `{}`, already used by key {other:?}",
+                other.ident
+            ),
+            Error::SelectVariantCollision {
+                select,
+                variant,
+                other,
+                ident,
+            } => write!(
+                f,
+                "The variants {:?} and {:?} of the select on \"{select}\" both map to the enum variant `{ident}`",
+                other.name, variant.name
+            ),
             Error::InvalidKey(key) => write!(
                 f,
                 "invalid key {key:?}, it can't be used as a rust identifier, try removing whitespaces and special characters."
@@ -243,6 +429,18 @@ impl Display for Error {
             Error::EmptyRange => write!(f, "empty ranges are not allowed"),
             Error::InvalidRangeType(t) => write!(f, "invalid range type {t:?}"),
             Error::NestedRanges => write!(f, "nested ranges are not allowed"),
+            Error::ListInRange => write!(f, "lists are not allowed inside ranges"),
+            Error::InvalidListItem(item) => {
+                write!(f, "list items can only be plain text, found {item:?}")
+            }
+            Error::ListInValue { key_path } => write!(
+                f,
+                "Invalid foreign key in \"{key_path}\": a foreign key to a list must be the whole value of a key."
+            ),
+            Error::ListMismatch { locale, key_path } => write!(
+                f,
+                "Missmatch value type beetween locale {locale:?} and default at key \"{key_path}\": one is a list and the other is not."
+            ),
             Error::InvalidFallback => write!(f, "fallbacks are only allowed in last position"),
             Error::MultipleFallbacks => write!(f, "only one fallback is allowed"),
             Error::MissingFallback(t) => write!(
@@ -274,11 +472,20 @@ impl Display for Error {
             ),
             Error::InvalidForeignKey { foreign_key, loc } => write!(
                 f,
-                "Invalid foreign key \"{foreign_key}\" at {loc}, foreign key to subkeys are not allowed."
+                "Invalid foreign key \"{foreign_key}\" at {loc}, foreign keys to subkeys can't take arguments."
+            ),
+            Error::SubkeysAliasInValue { key_path } => write!(
+                f,
+                "Invalid foreign key in \"{key_path}\": a foreign key to subkeys must be the whole value of a key."
             ),
             Error::UnknownFormatter { name, loc } => {
                 write!(f, "Unknown formatter {name:?} at {loc}.")
             }
+            Error::UnknownVarType { name, loc } => write!(
+                f,
+                "Unknown variable type {name:?} at {loc}, expected one of {}, date, time or datetime.",
+                crate::formatters::VarType::TYPES.join(", ")
+            ),
             Error::ConflictingPluralRuleType { loc } => {
                 write!(f, "Found both ordinal and cardinal plurals at {loc}.")
             }
@@ -318,6 +525,93 @@ impl Display for Error {
                 f,
                 "At {loc}, Found plurals but a key of that name is already present."
             ),
+            Error::MissingSelectFallback { loc } => write!(
+                f,
+                "Select at {loc} require an \"other\" variant as a fallback"
+            ),
+            Error::SubkeysInSelect { loc } => {
+                write!(f, "subkeys for select variants are not allowed, at {loc}")
+            }
+            Error::SelectAtNormalKey { loc } => write!(
+                f,
+                "At {loc}, Found select but a key of that name is already present."
+            ),
+            Error::InvalidPluralForm { loc, form } => write!(
+                f,
+                "Invalid plural form {form:?} at {loc}, expected one of \"zero\", \"one\", \"two\", \"few\", \"many\" or \"other\""
+            ),
+            Error::MissingPluralFallback { loc } => write!(
+                f,
+                "Plurals at {loc} require an \"other\" form as a fallback"
+            ),
+            Error::PluralDimensionsMismatch { loc } => write!(
+                f,
+                "Plurals with several counts at {loc} must use the same count variables, in the same order, in all of their keys"
+            ),
+            Error::SubkeysInPlurals { loc } => {
+                write!(f, "subkeys for plural forms are not allowed, at {loc}")
+            }
+            Error::ConflictingMarkers { loc } => write!(
+                f,
+                "At {loc}, a map can only have one of the \"$plural\", \"$ordinal\" and \"$select\" markers"
+            ),
+            Error::OffsetWithoutPlurals { loc } => write!(
+                f,
+                "At {loc}, the \"$offset\" key can only be used in a map with the \"$plural\" or \"$ordinal\" marker"
+            ),
+            Error::InvalidAlias { loc, alias } => {
+                write!(f, "Invalid alias {alias:?} at {loc}, expected a key path")
+            }
+            Error::ConstraintViolation { loc, violation } => write!(f, "At {loc}, {violation}"),
+            Error::MissingPluralForm { locale, key, forms } => {
+                write_missing_plural_forms(f, locale, key, forms)
+            }
+            Error::AliasWithValue { loc } => write!(
+                f,
+                "At {loc}, the key is declared as an alias but also has a value, remove one of them"
+            ),
+            Error::InvalidSelectArg { loc, foreign_key } => write!(
+                f,
+                "Invalid select arg at {loc} to foreign key \"{foreign_key}\": it can only be a literal string or a single variable."
+            ),
+            Error::VarDefaultMismatch { key_path, key } => write!(
+                f,
+                "variable \"{}\" of key \"{key_path}\" has different default values, or is optional in some places only: every use of the variable in every locale must declare the same default value",
+                key.name.strip_prefix("var_").unwrap_or(&key.name)
+            ),
+            Error::InvalidVarDefault { key_path, key } => write!(
+                f,
+                "variable \"{}\" of key \"{key_path}\" is used as a condition, to select a variant or as a plural or range count, it can't have a default value",
+                key.name.strip_prefix("var_").unwrap_or(&key.name)
+            ),
+            Error::VarTypeMismatch { key_path, key } => write!(
+                f,
+                "variable \"{}\" of key \"{key_path}\" is declared with different types: every use of the variable in every locale must declare the same type, or none",
+                key.name.strip_prefix("var_").unwrap_or(&key.name)
+            ),
+            Error::InvalidPluralOffsetType { key_path, key, ty } => write!(
+                f,
+                "variable \"{}\" of key \"{key_path}\" is the count of plurals with an offset, it can't be of type {ty}: the count of plurals with an offset must be a primitive integer up to 64 bits",
+                key.name.strip_prefix("var_").unwrap_or(&key.name)
+            ),
+            Error::InvalidConditionArg { loc, foreign_key } => write!(
+                f,
+                "Invalid condition arg at {loc} to foreign key \"{foreign_key}\": it can only be a literal boolean or a single variable."
+            ),
+            Error::ConditionAndSelectMix { key_path, key } => write!(
+                f,
+                "variable \"{}\" is used both as a condition and to select a variant or as a plural or range count, for key \"{key_path}\"",
+                key.name.strip_prefix("var_").unwrap_or(&key.name)
+            ),
+            Error::UnclosedCondition { loc } => write!(
+                f,
+                "At {loc}, a \"{{{{#if}}}}\" condition is not closed by a \"{{{{/if}}}}\""
+            ),
+            Error::SelectAndCountMix { key_path, key } => write!(
+                f,
+                "variable \"{}\" is used both to select a variant and as a plural or range count, for key \"{key_path}\"",
+                key.name.strip_prefix("var_").unwrap_or(&key.name)
+            ),
             Error::DisabledFormatter { loc, formatter_err } => {
                 write!(f, "{}, at {loc}", formatter_err)
             }
@@ -327,11 +621,11 @@ impl Display for Error {
             ),
             Error::NoFileFormats => write!(
                 f,
-                "No file formats has been provided for leptos_i18n. Supported formats are: json, json5, yaml and toml."
+                "No file formats has been provided for leptos_i18n. Supported formats are: json, json5, yaml, toml, fluent and po."
             ),
-            Error::MultipleFilesFormats => write!(
+            Error::MultipleFilesFormats(paths) => write!(
                 f,
-                "Multiple file formats have been provided for leptos_i18n, choose only one. Supported formats are: json, json5, yaml and toml."
+                "Found multiple files for the same translations, keep only one of them: {paths:?}"
             ),
             Error::InvalidLocale { locale, err } => {
                 write!(f, "Found invalid locale {locale:?}: {err}")
@@ -398,6 +692,39 @@ impl Display for Error {
                     "Tried to declare inheritance for the default locale at {loc}"
                 )
             }
+            Error::UnsupportedFluentConstruct { loc, construct } => {
+                write!(f, "Unsupported Fluent construct at {loc}: {construct}")
+            }
+            Error::DuplicateFluentEntry { loc } => write!(
+                f,
+                "Duplicate Fluent entry at {loc}, note that \"-\" in identifiers are replaced by \"_\"."
+            ),
+            Error::InvalidIcuMessage { loc, message } => {
+                write!(f, "Invalid ICU message at {loc}: {message}")
+            }
+            Error::UnsupportedIcuConstruct { loc, construct } => {
+                write!(
+                    f,
+                    "Unsupported ICU MessageFormat construct at {loc}: {construct}"
+                )
+            }
+            Error::InvalidXliff { path, err } => {
+                write!(f, "Invalid XLIFF file {path:?}: {err}")
+            }
+            Error::UnknownXliffLocale(locale) => {
+                write!(
+                    f,
+                    "XLIFF locale {locale:?} is not one of the configured locales"
+                )
+            }
+            Error::UnsupportedXliffFileFormat(file_format) => write!(
+                f,
+                "Can't write imported XLIFF translations with file format {file_format}, supported formats are: json, json5, yaml, toml and po."
+            ),
+            Error::XliffUnitsWithoutFile { locale_dir, keys } => write!(
+                f,
+                "Can't import the XLIFF units {keys:?}: the locale is split in the files of {locale_dir:?} and none of them holds these keys."
+            ),
         }
     }
 }
@@ -406,6 +733,59 @@ impl Error {
     pub fn custom(err: impl ToString) -> Self {
         Self::Custom(err.to_string())
     }
+
+    /// The locale and key of the value the error is about, if any.
+    ///
+    /// The overlay errors are not included as they already name their overlay.
+    fn location(&self) -> Option<(&Key, &KeyPath)> {
+        match self {
+            Error::SubKeyMissmatch { locale, key_path }
+            | Error::ListMismatch { locale, key_path } => Some((locale, key_path)),
+            Error::MissingPluralForm { locale, key, .. } => Some((locale, key)),
+            Error::DuplicateKeyInLocaleDir { loc, .. }
+            | Error::KeyIdentCollision { loc, .. }
+            | Error::RecursiveForeignKey { loc }
+            | Error::MissingForeignKey { loc, .. }
+            | Error::InvalidForeignKey { loc, .. }
+            | Error::UnknownFormatter { loc, .. }
+            | Error::UnknownVarType { loc, .. }
+            | Error::ConflictingPluralRuleType { loc }
+            | Error::InvalidForeignKeyArgs { loc, .. }
+            | Error::InvalidCountArg { loc, .. }
+            | Error::InvalidCountArgType { loc, .. }
+            | Error::CountArgOutsideRange { loc, .. }
+            | Error::UnexpectedToken { loc, .. }
+            | Error::PluralsAtNormalKey { loc }
+            | Error::MissingSelectFallback { loc }
+            | Error::SubkeysInSelect { loc }
+            | Error::SelectAtNormalKey { loc }
+            | Error::InvalidPluralForm { loc, .. }
+            | Error::MissingPluralFallback { loc }
+            | Error::PluralDimensionsMismatch { loc }
+            | Error::SubkeysInPlurals { loc }
+            | Error::ConflictingMarkers { loc }
+            | Error::OffsetWithoutPlurals { loc }
+            | Error::InvalidAlias { loc, .. }
+            | Error::ConstraintViolation { loc, .. }
+            | Error::AliasWithValue { loc }
+            | Error::InvalidSelectArg { loc, .. }
+            | Error::InvalidConditionArg { loc, .. }
+            | Error::UnclosedCondition { loc }
+            | Error::DisabledFormatter { loc, .. }
+            | Error::DisabledPlurals { loc }
+            | Error::InvalidFormatterArgName { loc, .. }
+            | Error::InvalidFormatterArg { loc, .. }
+            | Error::InvalidFormatter { loc, .. }
+            | Error::InvalidAttributeName { loc, .. }
+            | Error::InvalidAttribute { loc, .. }
+            | Error::InvalidForeignKeyArgForAttribute { loc, .. }
+            | Error::UnsupportedFluentConstruct { loc, .. }
+            | Error::DuplicateFluentEntry { loc }
+            | Error::InvalidIcuMessage { loc, .. }
+            | Error::UnsupportedIcuConstruct { loc, .. } => Some((&loc.locale, &loc.key_path)),
+            _ => None,
+        }
+    }
 }
 
 impl From<io::Error> for Error {
@@ -460,6 +840,24 @@ pub type Result<T, E = BoxedError> = core::result::Result<T, E>;
 
 impl std::error::Error for Error {}
 
+fn write_missing_plural_forms(
+    f: &mut std::fmt::Formatter<'_>,
+    locale: &Key,
+    key: &KeyPath,
+    forms: &[PluralForm],
+) -> std::fmt::Result {
+    let forms = forms
+        .iter()
+        .map(|form| format!("\"{form}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    write!(
+        f,
+        "At key \"{key}\", locale {locale:?} is missing the plural forms {forms}, their counts fall back to \"_other\"."
+    )
+}
+
+use super::constraints::Violation;
 use super::plurals::{PluralForm, PluralRuleType};
 
 #[derive(Debug)]
@@ -475,6 +873,20 @@ pub enum Warning {
         form: PluralForm,
         rule_type: PluralRuleType,
     },
+    UnreachablePluralForm {
+        loc: Location,
+        form: PluralForm,
+    },
+    MissingPluralForm {
+        locale: Key,
+        key: KeyPath,
+        forms: Vec<PluralForm>,
+    },
+    ListLengthMismatch {
+        loc: Location,
+        len: usize,
+        expected: usize,
+    },
     NonUnicodePath {
         locale: Key,
         namespace: Option<Key>,
@@ -485,6 +897,31 @@ pub enum Warning {
         formatter_name: String,
         chars: String,
     },
+    IgnoredFluentConstruct {
+        loc: Location,
+        construct: String,
+    },
+    SurplusPoPluralForm {
+        loc: Location,
+        index: usize,
+    },
+    PoPluralFormsMismatch {
+        locale: Key,
+        count: u64,
+        form: PluralForm,
+    },
+    IgnoredIcuConstruct {
+        loc: Location,
+        construct: String,
+    },
+    ConstraintViolation {
+        loc: Location,
+        violation: Violation,
+    },
+    InOverlay {
+        overlay: std::path::PathBuf,
+        warning: Box<Warning>,
+    },
     Custom(String),
 }
 
@@ -492,6 +929,24 @@ impl Warning {
     pub fn custom(err: impl ToString) -> Self {
         Warning::Custom(err.to_string())
     }
+
+    /// The locale and key of the value the warning is about, if any.
+    fn location(&self) -> Option<(&Key, &KeyPath)> {
+        match self {
+            Warning::MissingPluralForm { locale, key, .. } => Some((locale, key)),
+            Warning::MissingKey { loc }
+            | Warning::SurplusKey { loc }
+            | Warning::UnusedForm { loc, .. }
+            | Warning::UnreachablePluralForm { loc, .. }
+            | Warning::ListLengthMismatch { loc, .. }
+            | Warning::UnexpectedCharsAfterFormatter { loc, .. }
+            | Warning::IgnoredFluentConstruct { loc, .. }
+            | Warning::SurplusPoPluralForm { loc, .. }
+            | Warning::IgnoredIcuConstruct { loc, .. }
+            | Warning::ConstraintViolation { loc, .. } => Some((&loc.locale, &loc.key_path)),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Warning {
@@ -520,6 +975,19 @@ impl Display for Warning {
                     loc.key_path, loc.locale
                 )
             }
+            Warning::UnreachablePluralForm { loc, form } => write!(
+                f,
+                "At key \"{}\", the plural form \"{form}\" of locale {:?} is never used, as every count it matches is matched by an exact form first.",
+                loc.key_path, loc.locale
+            ),
+            Warning::MissingPluralForm { locale, key, forms } => {
+                write_missing_plural_forms(f, locale, key, forms)
+            }
+            Warning::ListLengthMismatch { loc, len, expected } => write!(
+                f,
+                "List \"{}\" has {len} items in locale {:?} but {expected} in the default locale",
+                loc.key_path, loc.locale
+            ),
             Warning::NonUnicodePath {
                 locale,
                 namespace: None,
@@ -545,6 +1013,31 @@ impl Display for Warning {
                 f,
                 "Unexpected characters {chars:?} after formatter {formatter_name:?} at {loc}"
             ),
+            Warning::IgnoredFluentConstruct { loc, construct } => {
+                write!(f, "Fluent construct ignored at {loc}: {construct}")
+            }
+            Warning::SurplusPoPluralForm { loc, index } => write!(
+                f,
+                "\"msgstr[{index}]\" at {loc} does not match any plural form of the locale, it is ignored."
+            ),
+            Warning::PoPluralFormsMismatch {
+                locale,
+                count,
+                form,
+            } => write!(
+                f,
+                "The \"Plural-Forms\" header of locale {locale:?} does not match its CLDR plural rules, a count of {count} uses the form mapped to \"{form}\" instead of its gettext form."
+            ),
+            Warning::ConstraintViolation { loc, violation } => write!(f, "At {loc}, {violation}"),
+            Warning::InOverlay { overlay, warning } => {
+                write!(f, "{warning} (in overlay {overlay:?})")
+            }
+            Warning::IgnoredIcuConstruct { loc, construct } => {
+                write!(
+                    f,
+                    "ICU MessageFormat construct ignored at {loc}: {construct}"
+                )
+            }
         }
     }
 }
@@ -554,6 +1047,8 @@ pub struct Diagnostics {
     errors: RefCell<Vec<Error>>,
     warnings: RefCell<Vec<Warning>>,
     has_ranges: Cell<bool>,
+    /// The overlay each overridden value comes from.
+    overlay_origins: RefCell<BTreeMap<Location, PathBuf>>,
 }
 
 impl Diagnostics {
@@ -562,6 +1057,7 @@ impl Diagnostics {
     }
 
     pub fn emit_error(&self, error: Error) {
+        let error = self.with_overlay_origin(error);
         self.errors.borrow_mut().push(error);
     }
 
@@ -574,6 +1070,13 @@ impl Diagnostics {
     }
 
     pub fn emit_warning(&self, warning: Warning) {
+        let warning = match self.overlay_origin(warning.location()) {
+            Some(overlay) => Warning::InOverlay {
+                overlay,
+                warning: Box::new(warning),
+            },
+            None => warning,
+        };
         self.warnings.borrow_mut().push(warning);
     }
 
@@ -587,6 +1090,10 @@ impl Diagnostics {
         Ref::map(warnings, Vec::as_slice)
     }
 
+    pub fn into_inner(self) -> (Vec<Error>, Vec<Warning>) {
+        (self.errors.into_inner(), self.warnings.into_inner())
+    }
+
     pub fn borrow(&self) -> (Ref<'_, [Error]>, Ref<'_, [Warning]>) {
         (self.errors(), self.warnings())
     }
@@ -598,6 +1105,38 @@ impl Diagnostics {
     pub fn set_has_ranges(&self) {
         self.has_ranges.set(true);
     }
+
+    /// Record that the value at `loc` comes from the given overlay,
+    /// the diagnostics emitted afterward for it or its subkeys then name the overlay.
+    pub fn set_overlay_origin(&self, loc: Location, overlay: PathBuf) {
+        self.overlay_origins.borrow_mut().insert(loc, overlay);
+    }
+
+    /// The overlay of the deepest overridden value containing the location.
+    fn overlay_origin(&self, location: Option<(&Key, &KeyPath)>) -> Option<PathBuf> {
+        let (locale, key_path) = location?;
+        self.overlay_origins
+            .borrow()
+            .iter()
+            .filter(|(loc, _)| {
+                loc.locale == *locale
+                    && loc.key_path.namespace == key_path.namespace
+                    && key_path.path.starts_with(&loc.key_path.path)
+            })
+            .max_by_key(|(loc, _)| loc.key_path.path.len())
+            .map(|(_, overlay)| overlay.clone())
+    }
+
+    /// Wrap the error in [`Error::InOverlay`] if it is about a value coming from an overlay.
+    pub fn with_overlay_origin(&self, err: Error) -> Error {
+        match self.overlay_origin(err.location()) {
+            Some(overlay) => Error::InOverlay {
+                overlay,
+                err: Box::new(err),
+            },
+            None => err,
+        }
+    }
 }
 
 impl ToTokens for Diagnostics {
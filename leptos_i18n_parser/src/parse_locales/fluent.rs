@@ -0,0 +1,1151 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use super::{
+    error::{Error, Result, Warning},
+    locale::{Locale, LocaleSeed, SerdeError},
+    options::KeyMapping,
+    parsed_value::{ForeignKey, Literal, ParsedValue},
+    plurals::{PluralForm, PluralRuleType, Plurals},
+    select::{SELECT_OTHER, Select},
+};
+use crate::{
+    formatters::VarBounds,
+    utils::{Key, KeyPath, Loc, Location, ParseContext},
+};
+
+/// Key under which the value of a message is stored when the message also has attributes.
+pub const FLUENT_VALUE_KEY: &str = "value";
+
+#[derive(Debug)]
+pub struct FluentError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for FluentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for FluentError {}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    id: String,
+    is_term: bool,
+    value: Option<Pattern>,
+    attributes: Vec<(String, Pattern)>,
+}
+
+type Pattern = Vec<PatternElement>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum PatternElement {
+    Text(String),
+    NewLine { blank_lines: usize, indent: usize },
+    Placeable(Expression),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+    StringLiteral(String),
+    NumberLiteral(String),
+    Variable(String),
+    Message {
+        id: String,
+        attribute: Option<String>,
+    },
+    Term {
+        id: String,
+        attribute: Option<String>,
+        args: Vec<(String, Expression)>,
+    },
+    Function {
+        name: String,
+        positional: Vec<Expression>,
+        named: Vec<(String, Expression)>,
+    },
+    Select {
+        selector: Box<Expression>,
+        variants: Vec<Variant>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Variant {
+    key: String,
+    default: bool,
+    value: Pattern,
+}
+
+struct FluentParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+type ParseResult<T> = core::result::Result<T, FluentError>;
+
+type CallArguments = (Vec<Expression>, Vec<(String, Expression)>);
+
+impl<'a> FluentParser<'a> {
+    fn new(src: &'a str) -> Self {
+        FluentParser { src, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error<T>(&self, message: impl ToString) -> ParseResult<T> {
+        let before = &self.src[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        Err(FluentError {
+            line,
+            column,
+            message: message.to_string(),
+        })
+    }
+
+    fn expect(&mut self, c: char) -> ParseResult<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            match self.peek() {
+                Some(found) => self.error(format!("expected {c:?}, found {found:?}")),
+                None => self.error(format!("expected {c:?}, found end of file")),
+            }
+        }
+    }
+
+    fn eat_newline(&mut self) -> bool {
+        self.eat('\n') || self.eat_str("\r\n")
+    }
+
+    fn is_newline(&self) -> bool {
+        matches!(self.peek(), Some('\n')) || self.rest().starts_with("\r\n")
+    }
+
+    fn skip_blank_inline(&mut self) -> usize {
+        let mut count = 0;
+        while self.eat(' ') {
+            count += 1;
+        }
+        count
+    }
+
+    fn skip_blank(&mut self) {
+        while self.eat(' ') || self.eat_newline() {}
+    }
+
+    fn skip_line(&mut self) {
+        while !self.is_newline() && self.bump().is_some() {}
+        self.eat_newline();
+    }
+
+    fn skip_blank_lines(&mut self) {
+        loop {
+            let start = self.pos;
+            self.skip_blank_inline();
+            if self.eat_newline() {
+                continue;
+            }
+            if self.peek().is_some() {
+                self.pos = start;
+            }
+            break;
+        }
+    }
+
+    fn parse_identifier(&mut self) -> ParseResult<String> {
+        match self.peek() {
+            Some(c) if c.is_ascii_alphabetic() => {}
+            _ => return self.error("expected an identifier"),
+        }
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            self.bump();
+        }
+        Ok(self.src[start..self.pos].to_string())
+    }
+
+    fn parse_resource(mut self) -> ParseResult<Vec<Entry>> {
+        let mut entries = Vec::new();
+        loop {
+            self.skip_blank_lines();
+            match self.peek() {
+                None => return Ok(entries),
+                Some('#') => self.skip_line(),
+                Some('-') => {
+                    self.bump();
+                    entries.push(self.parse_entry(true)?);
+                }
+                Some(c) if c.is_ascii_alphabetic() => entries.push(self.parse_entry(false)?),
+                Some(' ') => return self.error("unexpected indentation"),
+                Some(_) => return self.error("expected a message, a term or a comment"),
+            }
+        }
+    }
+
+    fn parse_entry(&mut self, is_term: bool) -> ParseResult<Entry> {
+        let id = self.parse_identifier()?;
+        self.skip_blank_inline();
+        self.expect('=')?;
+        let value = self.parse_pattern()?;
+        let mut attributes = Vec::new();
+        while let Some(attribute) = self.parse_attribute()? {
+            attributes.push(attribute);
+        }
+        if is_term && value.is_none() {
+            return self.error(format!("term \"-{id}\" must have a value"));
+        }
+        if value.is_none() && attributes.is_empty() {
+            return self.error(format!(
+                "message \"{id}\" must have a value or at least one attribute"
+            ));
+        }
+        Ok(Entry {
+            id,
+            is_term,
+            value,
+            attributes,
+        })
+    }
+
+    fn parse_attribute(&mut self) -> ParseResult<Option<(String, Pattern)>> {
+        let start = self.pos;
+        if !self.eat_newline() {
+            return Ok(None);
+        }
+        self.skip_blank();
+        if !self.eat('.') {
+            self.pos = start;
+            return Ok(None);
+        }
+        let id = self.parse_identifier()?;
+        self.skip_blank_inline();
+        self.expect('=')?;
+        match self.parse_pattern()? {
+            Some(pattern) => Ok(Some((id, pattern))),
+            None => self.error(format!("attribute \".{id}\" must have a value")),
+        }
+    }
+
+    fn parse_pattern(&mut self) -> ParseResult<Option<Pattern>> {
+        self.skip_blank_inline();
+        let mut elements = Vec::new();
+        loop {
+            match self.peek() {
+                None => break,
+                Some('{') => elements.push(PatternElement::Placeable(self.parse_placeable()?)),
+                Some('}') => return self.error("unbalanced closing brace"),
+                Some('\n' | '\r') => match self.parse_continuation() {
+                    Some(new_line) => elements.push(new_line),
+                    None => break,
+                },
+                Some(_) => {
+                    let start = self.pos;
+                    while !matches!(self.peek(), None | Some('{' | '}' | '\n' | '\r')) {
+                        self.bump();
+                    }
+                    elements.push(PatternElement::Text(self.src[start..self.pos].to_string()));
+                }
+            }
+        }
+        Ok(Self::finish_pattern(elements))
+    }
+
+    fn parse_continuation(&mut self) -> Option<PatternElement> {
+        let start = self.pos;
+        let mut blank_lines = 0;
+        while self.eat_newline() {
+            let indent = self.skip_blank_inline();
+            match self.peek() {
+                Some('\n' | '\r') => blank_lines += 1,
+                None | Some('[' | '*' | '.' | '}') => break,
+                Some(_) if indent > 0 => {
+                    return Some(PatternElement::NewLine {
+                        blank_lines,
+                        indent,
+                    });
+                }
+                Some(_) => break,
+            }
+        }
+        self.pos = start;
+        None
+    }
+
+    fn finish_pattern(elements: Pattern) -> Option<Pattern> {
+        let common_indent = elements
+            .iter()
+            .filter_map(|element| match element {
+                PatternElement::NewLine { indent, .. } => Some(*indent),
+                _ => None,
+            })
+            .min()
+            .unwrap_or(0);
+
+        let mut pattern: Pattern = Vec::with_capacity(elements.len());
+        for element in elements {
+            let element = match element {
+                PatternElement::NewLine {
+                    blank_lines,
+                    indent,
+                } => {
+                    let mut text = if pattern.is_empty() {
+                        String::new()
+                    } else {
+                        "\n".repeat(blank_lines + 1)
+                    };
+                    text.extend(std::iter::repeat_n(' ', indent - common_indent));
+                    PatternElement::Text(text)
+                }
+                element => element,
+            };
+            match (pattern.last_mut(), element) {
+                (Some(PatternElement::Text(last)), PatternElement::Text(text)) => {
+                    last.push_str(&text)
+                }
+                (_, element) => pattern.push(element),
+            }
+        }
+
+        if let Some(PatternElement::Text(last)) = pattern.last_mut() {
+            last.truncate(last.trim_end().len());
+            if last.is_empty() {
+                pattern.pop();
+            }
+        }
+
+        if pattern.is_empty() {
+            None
+        } else {
+            Some(pattern)
+        }
+    }
+
+    fn parse_placeable(&mut self) -> ParseResult<Expression> {
+        self.expect('{')?;
+        self.skip_blank();
+        let expression = self.parse_inline_expression()?;
+        self.skip_blank();
+        let expression = if self.eat_str("->") {
+            let variants = self.parse_variants()?;
+            Expression::Select {
+                selector: Box::new(expression),
+                variants,
+            }
+        } else {
+            expression
+        };
+        self.skip_blank();
+        self.expect('}')?;
+        Ok(expression)
+    }
+
+    fn parse_variants(&mut self) -> ParseResult<Vec<Variant>> {
+        let mut variants = Vec::new();
+        loop {
+            self.skip_blank();
+            let default = self.eat('*');
+            if !self.eat('[') {
+                if default {
+                    return self.error("expected '[' after '*'");
+                }
+                break;
+            }
+            self.skip_blank();
+            let key = match self.peek() {
+                Some(c) if c.is_ascii_digit() || c == '-' => self.parse_number_literal()?,
+                _ => self.parse_identifier()?,
+            };
+            self.skip_blank();
+            self.expect(']')?;
+            let value = self.parse_pattern()?.unwrap_or_default();
+            variants.push(Variant {
+                key,
+                default,
+                value,
+            });
+        }
+        match variants.iter().filter(|variant| variant.default).count() {
+            _ if variants.is_empty() => self.error("select expressions must have variants"),
+            1 => Ok(variants),
+            _ => self.error("select expressions must have exactly one default variant"),
+        }
+    }
+
+    fn parse_number_literal(&mut self) -> ParseResult<String> {
+        let start = self.pos;
+        self.eat('-');
+        if !matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            return self.error("expected a number");
+        }
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.bump();
+        }
+        if self.peek() == Some('.') && matches!(self.peek_nth(1), Some(c) if c.is_ascii_digit()) {
+            self.bump();
+            while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+                self.bump();
+            }
+        }
+        Ok(self.src[start..self.pos].to_string())
+    }
+
+    fn parse_string_literal(&mut self) -> ParseResult<String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n' | '\r') => return self.error("unterminated string literal"),
+                Some('"') => return Ok(s),
+                Some('\\') => match self.bump() {
+                    Some('\\') => s.push('\\'),
+                    Some('"') => s.push('"'),
+                    Some(u @ ('u' | 'U')) => {
+                        let len = if u == 'u' { 4 } else { 6 };
+                        let hex = self.rest().get(..len).unwrap_or_default();
+                        let Some(c) = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                        else {
+                            return self.error(format!("invalid unicode escape \"\\{u}{hex}\""));
+                        };
+                        self.pos += len;
+                        s.push(c);
+                    }
+                    _ => return self.error("unknown escape sequence"),
+                },
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    fn parse_attribute_accessor(&mut self) -> ParseResult<Option<String>> {
+        if self.eat('.') {
+            self.parse_identifier().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_call_arguments(&mut self) -> ParseResult<Option<CallArguments>> {
+        let start = self.pos;
+        self.skip_blank();
+        if !self.eat('(') {
+            self.pos = start;
+            return Ok(None);
+        }
+        let mut positional = Vec::new();
+        let mut named = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.eat(')') {
+                return Ok(Some((positional, named)));
+            }
+            let arg_start = self.pos;
+            let named_arg = match self.peek() {
+                Some(c) if c.is_ascii_alphabetic() => {
+                    let name = self.parse_identifier()?;
+                    self.skip_blank();
+                    if self.eat(':') {
+                        Some(name)
+                    } else {
+                        self.pos = arg_start;
+                        None
+                    }
+                }
+                _ => None,
+            };
+            match named_arg {
+                Some(name) => {
+                    self.skip_blank();
+                    let value = match self.peek() {
+                        Some('"') => Expression::StringLiteral(self.parse_string_literal()?),
+                        _ => Expression::NumberLiteral(self.parse_number_literal()?),
+                    };
+                    named.push((name, value));
+                }
+                None if !named.is_empty() => {
+                    return self.error("positional arguments must come before named arguments");
+                }
+                None => positional.push(self.parse_inline_expression()?),
+            }
+            self.skip_blank();
+            if !self.eat(',') {
+                self.expect(')')?;
+                return Ok(Some((positional, named)));
+            }
+        }
+    }
+
+    fn parse_inline_expression(&mut self) -> ParseResult<Expression> {
+        match self.peek() {
+            Some('"') => self.parse_string_literal().map(Expression::StringLiteral),
+            Some(c) if c.is_ascii_digit() => {
+                self.parse_number_literal().map(Expression::NumberLiteral)
+            }
+            Some('-') if matches!(self.peek_nth(1), Some(c) if c.is_ascii_digit()) => {
+                self.parse_number_literal().map(Expression::NumberLiteral)
+            }
+            Some('$') => {
+                self.bump();
+                self.parse_identifier().map(Expression::Variable)
+            }
+            Some('-') => {
+                self.bump();
+                let id = self.parse_identifier()?;
+                let attribute = self.parse_attribute_accessor()?;
+                let args = match self.parse_call_arguments()? {
+                    Some((positional, _)) if !positional.is_empty() => {
+                        return self.error("terms only accept named arguments");
+                    }
+                    Some((_, named)) => named,
+                    None => vec![],
+                };
+                Ok(Expression::Term {
+                    id,
+                    attribute,
+                    args,
+                })
+            }
+            Some('{') => self.parse_placeable(),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let id = self.parse_identifier()?;
+                if let Some((positional, named)) = self.parse_call_arguments()? {
+                    return Ok(Expression::Function {
+                        name: id,
+                        positional,
+                        named,
+                    });
+                }
+                let attribute = self.parse_attribute_accessor()?;
+                Ok(Expression::Message { id, attribute })
+            }
+            Some(c) => self.error(format!("unexpected character {c:?} in placeable")),
+            None => self.error("unexpected end of file in placeable"),
+        }
+    }
+}
+
+fn normalize_id(id: &str) -> String {
+    id.replace('-', "_")
+}
+
+fn entry_key(id: &str, is_term: bool, key_mapping: &KeyMapping) -> Result<Key> {
+    if is_term {
+        // terms are prefixed by an underscore, Fluent identifiers can't start with one so this can't collide with a message.
+        Key::try_with_mapping(&format!("_{}", normalize_id(id)), key_mapping)
+    } else {
+        Key::try_with_mapping(&normalize_id(id), key_mapping)
+    }
+}
+
+fn attribute_key(attribute: &str, key_mapping: &KeyMapping) -> Result<Key> {
+    Key::try_with_mapping(&normalize_id(attribute), key_mapping)
+}
+
+fn var_key(name: &str) -> Result<Key> {
+    Key::try_new(&format!("var_{}", normalize_id(name)))
+}
+
+struct Lowering<'a, 'b> {
+    seed: &'a LocaleSeed<'b>,
+    // entries with attributes are turned into subkeys, their value is moved to `FLUENT_VALUE_KEY`.
+    with_attributes: BTreeSet<(String, bool)>,
+}
+
+impl Lowering<'_, '_> {
+    fn ctx<'c>(&'c self, key_path: &'c KeyPath) -> ParseContext<'c> {
+        ParseContext {
+            loc: Loc {
+                key_path,
+                locale: &self.seed.top_locale_name,
+            },
+            foreign_keys_paths: self.seed.foreign_keys_paths,
+            formatters: self.seed.formatters,
+            diag: self.seed.diag,
+            parse_fns: self.seed.parse_fns,
+            key_mapping: self.seed.key_mapping,
+        }
+    }
+
+    fn location(&self, key_path: &KeyPath) -> Location {
+        Location::new(self.seed.top_locale_name.clone(), key_path.clone())
+    }
+
+    fn unsupported(&self, key_path: &KeyPath, construct: impl ToString) -> ParsedValue {
+        self.seed
+            .diag
+            .emit_error(Error::UnsupportedFluentConstruct {
+                loc: self.location(key_path),
+                construct: construct.to_string(),
+            });
+        ParsedValue::default()
+    }
+
+    fn lower_entries(&self, entries: Vec<Entry>) -> Result<BTreeMap<Key, ParsedValue>> {
+        let mut key_path = self.seed.key_path.clone();
+        let mut keys = BTreeMap::new();
+        for entry in entries {
+            let key = entry_key(&entry.id, entry.is_term, self.seed.key_mapping)?;
+            let mut pushed_key = key_path.push_key(key.clone());
+            let value = if entry.attributes.is_empty() {
+                let pattern = entry.value.unwrap_or_default();
+                self.lower_pattern(&pushed_key, &pattern)?
+            } else {
+                let mut sub_keys = BTreeMap::new();
+                let values = entry
+                    .value
+                    .map(|value| (FLUENT_VALUE_KEY.to_string(), value));
+                for (attribute, pattern) in values.into_iter().chain(entry.attributes) {
+                    let attribute_key = attribute_key(&attribute, self.seed.key_mapping)?;
+                    let pushed_key = pushed_key.push_key(attribute_key.clone());
+                    let value = self.lower_pattern(&pushed_key, &pattern)?;
+                    if sub_keys.insert(attribute_key, value).is_some() {
+                        return Err(Error::DuplicateFluentEntry {
+                            loc: self.location(&pushed_key),
+                        }
+                        .into());
+                    }
+                }
+                ParsedValue::Subkeys(Some(Locale {
+                    top_locale_name: self.seed.top_locale_name.clone(),
+                    name: key.clone(),
+                    keys: sub_keys,
+                    strings: vec![],
+                    top_locale_string_count: 0,
+                    metadata: BTreeMap::new(),
+                }))
+            };
+            if keys.insert(key, value).is_some() {
+                return Err(Error::DuplicateFluentEntry {
+                    loc: self.location(&pushed_key),
+                }
+                .into());
+            }
+        }
+        Ok(keys)
+    }
+
+    fn lower_pattern(&self, key_path: &KeyPath, pattern: &[PatternElement]) -> Result<ParsedValue> {
+        let mut values = pattern
+            .iter()
+            .map(|element| match element {
+                PatternElement::Text(text) => Ok(ParsedValue::Literal(Literal::String(
+                    text.clone(),
+                    usize::MAX,
+                ))),
+                PatternElement::Placeable(expression) => {
+                    self.lower_expression(key_path, expression)
+                }
+                PatternElement::NewLine { .. } => {
+                    unreachable!("new lines are removed when the pattern is finished")
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        match values.len() {
+            0 => Ok(ParsedValue::default()),
+            1 => Ok(values.remove(0)),
+            _ => Ok(ParsedValue::Bloc(values)),
+        }
+    }
+
+    fn foreign_key(
+        &self,
+        key_path: &KeyPath,
+        id: &str,
+        is_term: bool,
+        attribute: Option<&str>,
+        args: BTreeMap<String, ParsedValue>,
+    ) -> Result<ParsedValue> {
+        let mut path = vec![entry_key(id, is_term, self.seed.key_mapping)?];
+        match attribute {
+            Some(attribute) => path.push(attribute_key(attribute, self.seed.key_mapping)?),
+            None if self.with_attributes.contains(&(id.to_string(), is_term)) => {
+                path.push(attribute_key(FLUENT_VALUE_KEY, self.seed.key_mapping)?)
+            }
+            None => {}
+        }
+        let target = KeyPath::new_from_path(self.seed.key_path.namespace.clone(), path);
+        Ok(ParsedValue::ForeignKey(RefCell::new(ForeignKey::new(
+            self.location(key_path),
+            target,
+            args,
+            self.seed.foreign_keys_paths,
+        ))))
+    }
+
+    fn lower_expression(&self, key_path: &KeyPath, expression: &Expression) -> Result<ParsedValue> {
+        match expression {
+            Expression::StringLiteral(s) | Expression::NumberLiteral(s) => {
+                Ok(ParsedValue::Literal(Literal::String(s.clone(), usize::MAX)))
+            }
+            Expression::Variable(name) => Ok(ParsedValue::Variable {
+                key: var_key(name)?,
+                bounds: VarBounds::None,
+                default: None,
+            }),
+            Expression::Message { id, attribute } => {
+                self.foreign_key(key_path, id, false, attribute.as_deref(), BTreeMap::new())
+            }
+            Expression::Term {
+                id,
+                attribute: Some(attribute),
+                ..
+            } => Ok(self.unsupported(
+                key_path,
+                format!("term attribute \"-{id}.{attribute}\" can only be used as a selector"),
+            )),
+            Expression::Term {
+                id,
+                attribute: None,
+                args,
+            } => {
+                let args = args
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = self.lower_expression(key_path, value)?;
+                        Ok((format!("var_{}", normalize_id(name)), value))
+                    })
+                    .collect::<Result<_>>()?;
+                self.foreign_key(key_path, id, true, None, args)
+            }
+            Expression::Function {
+                name,
+                positional,
+                named,
+            } => {
+                let formatter_name = match name.as_str() {
+                    "NUMBER" => "number",
+                    "DATETIME" => "datetime",
+                    _ => return Ok(self.unsupported(key_path, format!("function {name:?}"))),
+                };
+                let [Expression::Variable(var_name)] = positional.as_slice() else {
+                    return Ok(self.unsupported(
+                        key_path,
+                        format!("function {name:?} only accepts a single variable as argument"),
+                    ));
+                };
+                if !named.is_empty() {
+                    self.seed
+                        .diag
+                        .emit_warning(Warning::IgnoredFluentConstruct {
+                            loc: self.location(key_path),
+                            construct: format!("options of function {name:?}"),
+                        });
+                }
+                let bounds = self
+                    .seed
+                    .formatters
+                    .parse(&self.ctx(key_path), formatter_name, &[]);
+                Ok(ParsedValue::Variable {
+                    key: var_key(var_name)?,
+                    bounds,
+                    default: None,
+                })
+            }
+            Expression::Select { selector, variants } => {
+                self.lower_select(key_path, selector, variants)
+            }
+        }
+    }
+
+    fn plural_selector(selector: &Expression) -> Option<(&str, PluralRuleType)> {
+        match selector {
+            Expression::Variable(name) => Some((name, PluralRuleType::Cardinal)),
+            Expression::Function {
+                name,
+                positional,
+                named,
+            } if name == "NUMBER" => {
+                let [Expression::Variable(var_name)] = positional.as_slice() else {
+                    return None;
+                };
+                let rule_type = match named.iter().find(|(arg, _)| arg == "type") {
+                    Some((_, Expression::StringLiteral(t))) if t == "ordinal" => {
+                        PluralRuleType::Ordinal
+                    }
+                    _ => PluralRuleType::Cardinal,
+                };
+                Some((var_name, rule_type))
+            }
+            _ => None,
+        }
+    }
+
+    // numeric variant keys such as `[0]` match that exact count.
+    fn variant_form(key: &str) -> Option<PluralForm> {
+        match key.parse() {
+            Ok(value) => Some(PluralForm::Exact(value)),
+            Err(_) => PluralForm::try_from_str(key).filter(|form| !form.is_exact()),
+        }
+    }
+
+    fn lower_select(
+        &self,
+        key_path: &KeyPath,
+        selector: &Expression,
+        variants: &[Variant],
+    ) -> Result<ParsedValue> {
+        let default = variants
+            .iter()
+            .find(|variant| variant.default)
+            .unwrap_or(&variants[0]);
+
+        let Some((count_var, rule_type)) = Self::plural_selector(selector) else {
+            self.unsupported(key_path, "selectors other than a variable or NUMBER($var)");
+            return self.lower_pattern(key_path, &default.value);
+        };
+
+        let is_plural = matches!(selector, Expression::Function { .. })
+            || variants
+                .iter()
+                .all(|variant| Self::variant_form(&variant.key).is_some());
+        if !is_plural {
+            return self.lower_variant_select(key_path, count_var, variants, default);
+        }
+
+        let mut forms = BTreeMap::new();
+        for variant in variants {
+            let Some(form) = Self::variant_form(&variant.key) else {
+                self.unsupported(
+                    key_path,
+                    format!(
+                        "variant key {:?} in a NUMBER selector, only plural categories and integers are supported",
+                        variant.key
+                    ),
+                );
+                return self.lower_pattern(key_path, &default.value);
+            };
+            forms.insert(form, self.lower_pattern(key_path, &variant.value)?);
+        }
+
+        if !cfg!(feature = "plurals") {
+            self.seed.diag.emit_error(Error::DisabledPlurals {
+                loc: self.location(key_path),
+            });
+            return self.lower_pattern(key_path, &default.value);
+        }
+
+        let other = match forms.remove(&PluralForm::Other) {
+            Some(other) => other,
+            None => {
+                let default_form = Self::variant_form(&default.key)
+                    .expect("variant keys have already been checked");
+                forms.get(&default_form).cloned().unwrap_or_default()
+            }
+        };
+
+        let plurals = Plurals {
+            rule_type,
+            count_key: var_key(count_var)?,
+            other: Box::new(other),
+            forms,
+            offset: 0,
+        };
+        plurals.check_forms(&self.ctx(key_path).loc, self.seed.diag)?;
+        Ok(ParsedValue::Plurals(plurals))
+    }
+
+    // selectors with variants that are not plural categories are selects, the default variant
+    // being also used as the `other` fallback when there is none.
+    fn lower_variant_select(
+        &self,
+        key_path: &KeyPath,
+        select_var: &str,
+        variants: &[Variant],
+        default: &Variant,
+    ) -> Result<ParsedValue> {
+        let mut select_variants = BTreeMap::new();
+        for variant in variants {
+            let Some(variant_key) = Key::with_mapping(&variant.key, self.seed.key_mapping) else {
+                self.unsupported(
+                    key_path,
+                    format!(
+                        "variant key {:?}, a select variant must be a valid identifier",
+                        variant.key
+                    ),
+                );
+                return self.lower_pattern(key_path, &default.value);
+            };
+            let value = self.lower_pattern(key_path, &variant.value)?;
+            select_variants.insert(variant_key, value);
+        }
+        if !select_variants.contains_key(SELECT_OTHER) {
+            let other = self.lower_pattern(key_path, &default.value)?;
+            select_variants.insert(Key::try_new(SELECT_OTHER)?, other);
+        }
+        let loc = self.ctx(key_path).loc;
+        Select::new(var_key(select_var)?, select_variants, &loc).map(ParsedValue::Select)
+    }
+}
+
+fn parse_resource(src: &str) -> Result<Vec<Entry>, FluentError> {
+    FluentParser::new(src).parse_resource()
+}
+
+pub fn deserialize(src: &str, seed: LocaleSeed) -> Result<Locale, SerdeError> {
+    let entries = parse_resource(src).map_err(SerdeError::Fluent)?;
+    let with_attributes = entries
+        .iter()
+        .filter(|entry| !entry.attributes.is_empty())
+        .map(|entry| (entry.id.clone(), entry.is_term))
+        .collect();
+    let lowering = Lowering {
+        seed: &seed,
+        with_attributes,
+    };
+    let keys = lowering
+        .lower_entries(entries)
+        .map_err(SerdeError::custom)?;
+    Ok(Locale {
+        top_locale_name: seed.top_locale_name,
+        name: seed.name,
+        keys,
+        strings: vec![],
+        top_locale_string_count: 0,
+        metadata: BTreeMap::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formatters::Formatters,
+        parse_locales::{ForeignKeysPaths, error::Diagnostics},
+    };
+
+    fn text(s: &str) -> PatternElement {
+        PatternElement::Text(s.to_string())
+    }
+
+    fn lit(s: &str) -> ParsedValue {
+        ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX))
+    }
+
+    fn var(name: &str) -> ParsedValue {
+        ParsedValue::Variable {
+            key: Key::new(&format!("var_{name}")).unwrap(),
+            bounds: VarBounds::None,
+            default: None,
+        }
+    }
+
+    fn parse_locale(src: &str) -> (Locale, Diagnostics) {
+        let foreign_keys_paths = ForeignKeysPaths::new();
+        let diag = Diagnostics::new();
+        let formatters = Formatters::new();
+        let locale = Key::new("en").unwrap();
+        let seed = LocaleSeed {
+            name: locale.clone(),
+            top_locale_name: locale,
+            key_path: KeyPath::new(None),
+            foreign_keys_paths: &foreign_keys_paths,
+            diag: &diag,
+            formatters: &formatters,
+            parse_fns: ParsedValue::DEFAULT_FNS,
+            key_mapping: &KeyMapping::new(),
+        };
+        let locale = deserialize(src, seed).unwrap();
+        (locale, diag)
+    }
+
+    fn get<'a>(locale: &'a Locale, key: &str) -> &'a ParsedValue {
+        locale.keys.get(&Key::new(key).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn parse_multiline_pattern() {
+        let entries = parse_resource(
+            "# comment\nmulti =\n    first line\n      indented\n\n    after blank\nnext = ok\n",
+        )
+        .unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].value,
+            Some(vec![text("first line\n  indented\n\nafter blank")])
+        );
+        assert_eq!(entries[1].value, Some(vec![text("ok")]));
+    }
+
+    #[test]
+    fn parse_syntax_error() {
+        let err = parse_resource("hello = { $name\nother = text").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn lower_variables() {
+        let (locale, diag) = parse_locale("hello = Hello { $user-name }!\n");
+
+        assert!(diag.errors().is_empty());
+        assert_eq!(
+            get(&locale, "hello"),
+            &ParsedValue::Bloc(vec![lit("Hello "), var("user_name"), lit("!")])
+        );
+    }
+
+    #[test]
+    fn lower_attributes_and_terms() {
+        let (locale, diag) =
+            parse_locale("-brand = Firefox\nlogin = Log in to { -brand }\n    .title = Login\n");
+
+        assert!(diag.errors().is_empty());
+        assert_eq!(get(&locale, "_brand"), &lit("Firefox"));
+        let ParsedValue::Subkeys(Some(login)) = get(&locale, "login") else {
+            panic!("expected subkeys");
+        };
+        assert_eq!(get(login, "title"), &lit("Login"));
+        let ParsedValue::Bloc(values) = get(login, FLUENT_VALUE_KEY) else {
+            panic!("expected bloc");
+        };
+        let ParsedValue::ForeignKey(foreign_key) = &values[1] else {
+            panic!("expected foreign key");
+        };
+        let ForeignKey::NotSet(target, _) = &*foreign_key.borrow() else {
+            panic!("expected unresolved foreign key");
+        };
+        assert_eq!(target.to_string(), "_brand");
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn lower_plurals() {
+        let (locale, diag) = parse_locale(
+            "emails = { $unread ->\n    [one] One email\n   *[other] { $unread } emails\n}\n",
+        );
+
+        assert!(diag.errors().is_empty());
+        let ParsedValue::Plurals(plurals) = get(&locale, "emails") else {
+            panic!("expected plurals");
+        };
+        assert_eq!(plurals.count_key, Key::new("var_unread").unwrap());
+        assert_eq!(plurals.forms.get(&PluralForm::One), Some(&lit("One email")));
+        assert_eq!(
+            *plurals.other,
+            ParsedValue::Bloc(vec![var("unread"), lit(" emails")])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn lower_exact_plurals() {
+        let (locale, diag) = parse_locale(
+            "emails = { $unread ->\n    [0] No email\n    [one] One email\n   *[other] Emails\n}\n",
+        );
+
+        assert!(diag.errors().is_empty());
+        let ParsedValue::Plurals(plurals) = get(&locale, "emails") else {
+            panic!("expected plurals");
+        };
+        assert_eq!(
+            plurals.forms.get(&PluralForm::Exact(0)),
+            Some(&lit("No email"))
+        );
+        assert_eq!(plurals.forms.get(&PluralForm::One), Some(&lit("One email")));
+        assert_eq!(*plurals.other, lit("Emails"));
+    }
+
+    #[test]
+    fn lower_select() {
+        let (locale, diag) = parse_locale(
+            "greet = { $gender ->\n    [male] Him\n    [female] Her\n   *[nonbinary] Them\n}\n",
+        );
+
+        assert!(diag.errors().is_empty());
+        let ParsedValue::Select(select) = get(&locale, "greet") else {
+            panic!("expected select");
+        };
+        assert_eq!(select.select_key, Key::new("var_gender").unwrap());
+        assert_eq!(select.get_variant("male"), &lit("Him"));
+        assert_eq!(select.get_variant("female"), &lit("Her"));
+        assert_eq!(select.get_variant("nonbinary"), &lit("Them"));
+        assert_eq!(*select.other, lit("Them"));
+    }
+
+    #[test]
+    fn unsupported_select() {
+        let (locale, diag) =
+            parse_locale("greet = { -brand.gender ->\n    [male] Him\n   *[other] Them\n}\n");
+
+        assert_eq!(diag.errors().len(), 1);
+        assert_eq!(get(&locale, "greet"), &lit("Them"));
+    }
+
+    #[test]
+    fn map_attribute_keys() {
+        let foreign_keys_paths = ForeignKeysPaths::new();
+        let diag = Diagnostics::new();
+        let formatters = Formatters::new();
+        let locale = Key::new("en").unwrap();
+        let key_mapping = KeyMapping::new().raw_keywords(true);
+        let seed = LocaleSeed {
+            name: locale.clone(),
+            top_locale_name: locale,
+            key_path: KeyPath::new(None),
+            foreign_keys_paths: &foreign_keys_paths,
+            diag: &diag,
+            formatters: &formatters,
+            parse_fns: ParsedValue::DEFAULT_FNS,
+            key_mapping: &key_mapping,
+        };
+        let locale = deserialize("login = Log in\n    .type = Login\n", seed).unwrap();
+
+        let ParsedValue::Subkeys(Some(login)) = get(&locale, "login") else {
+            panic!("expected subkeys");
+        };
+        let (type_key, value) = login
+            .keys
+            .iter()
+            .find(|(key, _)| &*key.name == "type")
+            .unwrap();
+        assert_eq!(type_key.ident.to_string(), "r#type");
+        assert_eq!(value, &lit("Login"));
+    }
+}
@@ -2,8 +2,8 @@ use serde::de::MapAccess;
 
 use crate::{
     formatters::{Formatters, VarBounds},
-    parse_locales::options::{Config, FileFormat, ParseOptions},
-    utils::{Key, KeyPath, Loc, Location, UnwrapAt},
+    parse_locales::options::{Config, FileFormat, KeyMapping, ParseOptions},
+    utils::{Key, KeyPath, Loc, Location, ParseContext, ParseFn, UnwrapAt},
 };
 use std::{
     collections::{BTreeMap, BTreeSet, HashSet, btree_map::Entry},
@@ -16,9 +16,12 @@ use std::{
 use super::{
     ForeignKeysPaths, StringIndexer,
     error::{Diagnostics, Error, Result, Warning},
+    metadata::{self, KeyMetadata, METADATA_PREFIX},
     parsed_value::{ParsedValue, ParsedValueSeed},
-    plurals::{PluralForm, PluralRuleType, Plurals},
+    plurals::{self, PluralForm, PluralRuleType, Plurals},
     ranges::RangeType,
+    select::{SELECT_OTHER, Select},
+    table::{self, TableContext},
 };
 // use super::warning::{Warning, Warnings};
 
@@ -29,6 +32,9 @@ pub enum SerdeError {
     Yaml(serde_yaml::Error),
     Toml(toml::de::Error),
     Json5(json5::Error),
+    Fluent(super::fluent::FluentError),
+    Po(super::po::PoError),
+    Table(super::table::TableError),
     Custom(String),
     Io(std::io::Error),
 }
@@ -40,6 +46,9 @@ impl std::fmt::Display for SerdeError {
             SerdeError::Yaml(error) => std::fmt::Display::fmt(error, f),
             SerdeError::Toml(error) => std::fmt::Display::fmt(error, f),
             SerdeError::Json5(error) => std::fmt::Display::fmt(error, f),
+            SerdeError::Fluent(error) => std::fmt::Display::fmt(error, f),
+            SerdeError::Po(error) => std::fmt::Display::fmt(error, f),
+            SerdeError::Table(error) => std::fmt::Display::fmt(error, f),
             SerdeError::Io(error) => std::fmt::Display::fmt(error, f),
             SerdeError::Custom(err) => std::fmt::Display::fmt(err, f),
         }
@@ -67,6 +76,7 @@ pub struct Locale {
     pub keys: BTreeMap<Key, ParsedValue>,
     pub strings: Vec<Rc<str>>,
     pub top_locale_string_count: usize,
+    pub metadata: BTreeMap<Key, KeyMetadata>,
 }
 
 #[derive(Debug)]
@@ -91,6 +101,38 @@ pub enum LocalesOrNamespaces {
 pub struct VarInfo {
     pub bounds: BTreeSet<VarBounds>,
     pub range_count: Option<RangeOrPlural>,
+    pub select_variants: Option<BTreeSet<Key>>,
+    /// If the variable is the count of plurals with an offset.
+    pub plural_offset: bool,
+    /// If the variable is the `bool` of a `{{#if var}}` condition.
+    pub condition: bool,
+    /// Value used when the variable is not given, the variable is then optional.
+    pub default: Option<String>,
+    /// If the variable was already found in the text of a value, with or without a default value.
+    used: bool,
+}
+
+impl VarInfo {
+    /// The count of plurals with an offset can only be declared with a primitive integer type.
+    fn check_plural_offset(&self, key_path: &KeyPath, key: &Key) -> Result<()> {
+        if !self.plural_offset {
+            return Ok(());
+        }
+        match self
+            .bounds
+            .iter()
+            .filter_map(VarBounds::var_type)
+            .find(|ty| !ty.can_be_offset())
+        {
+            Some(ty) => Err(Error::InvalidPluralOffsetType {
+                key_path: key_path.clone(),
+                key: key.clone(),
+                ty,
+            }
+            .into()),
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -107,6 +149,8 @@ pub enum LiteralType {
     Signed,
     Unsigned,
     Float,
+    /// A list of strings, with the number of items in the default locale.
+    List(usize),
 }
 
 #[derive(Debug)]
@@ -200,6 +244,8 @@ pub struct LocaleSeed<'a> {
     pub foreign_keys_paths: &'a ForeignKeysPaths,
     pub diag: &'a Diagnostics,
     pub formatters: &'a Formatters,
+    pub parse_fns: &'a [ParseFn],
+    pub key_mapping: &'a KeyMapping,
 }
 
 #[derive(Debug, Clone)]
@@ -216,22 +262,176 @@ impl DefaultTo {
     }
 }
 
-fn find_file(path: &mut PathBuf, file_format: &FileFormat) -> Result<File> {
+/// Open the file at `path` with one of the extensions of the enabled formats,
+/// erroring if none exist or if files with different extensions exist.
+fn find_file<'a>(path: &mut PathBuf, options: &'a ParseOptions) -> Result<(File, &'a FileFormat)> {
     let mut errs = vec![];
+    let mut found = vec![];
+
+    for file_format in options.file_formats() {
+        for ext in file_format.get_files_exts() {
+            path.set_extension(ext);
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            // see https://github.com/rust-lang/rust-clippy/issues/12856
+            match File::open(&path) {
+                Ok(file) => found.push((file, file_format, path.to_owned())),
+                Err(err) => {
+                    errs.push((path.to_owned(), err));
+                }
+            };
+        }
+    }
+
+    match found.len() {
+        0 => Err(Error::LocaleFileNotFound(errs).into()),
+        1 => {
+            let (file, file_format, found_path) = found.remove(0);
+            *path = found_path;
+            Ok((file, file_format))
+        }
+        _ => Err(
+            Error::MultipleFilesFormats(found.into_iter().map(|(_, _, path)| path).collect())
+                .into(),
+        ),
+    }
+}
+
+/// Collect the files in `dir` and its subdirectories having one of the extensions of the enabled formats, sorted by path.
+fn find_files_in_dir<'a>(
+    dir: &Path,
+    options: &'a ParseOptions,
+    files: &mut Vec<(PathBuf, &'a FileFormat)>,
+) -> Result<()> {
+    let mut entries = std::fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(Error::IoError)?;
+    entries.sort();
+    for path in entries {
+        let is_hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+        if path.is_dir() {
+            find_files_in_dir(&path, options, files)?;
+        } else if let Some(file_format) = options.file_format_of(&path) {
+            files.push((path, file_format));
+        }
+    }
+    Ok(())
+}
 
-    for ext in file_format.get_files_exts() {
-        path.set_extension(ext);
-        #[allow(clippy::needless_borrows_for_generic_args)]
-        // see https://github.com/rust-lang/rust-clippy/issues/12856
-        match File::open(&path) {
-            Ok(file) => return Ok(file),
-            Err(err) => {
-                errs.push((path.to_owned(), err));
+/// Open the file of the given locale and namespace, returning its path and format.
+fn open_locale_file<'a>(
+    locales_dir_path: &Path,
+    locale: &Key,
+    namespace: Option<&Key>,
+    options: &'a ParseOptions,
+) -> Result<(File, PathBuf, &'a FileFormat)> {
+    let namespace_name = namespace.map(|namespace| &*namespace.name);
+    let mut errs = vec![];
+    let mut found = vec![];
+    for file_format in options.file_formats() {
+        if let Some(file_path) = file_format.locale_file_path(&locale.name, namespace_name) {
+            let path = locales_dir_path.join(file_path);
+            match File::open(&path) {
+                Ok(file) => found.push((file, path, file_format)),
+                Err(err) => errs.push((path, err)),
             }
-        };
+        }
+    }
+    let mut path = locales_dir_path.join(&*locale.name);
+    if let Some(namespace) = namespace_name {
+        path.push(namespace);
+    }
+    if options.file_formats().any(|file_format| {
+        file_format
+            .locale_file_path(&locale.name, namespace_name)
+            .is_none()
+    }) {
+        match find_file(&mut path, options) {
+            Ok((file, file_format)) => found.push((file, path, file_format)),
+            Err(err) => match err.into_inner() {
+                Error::LocaleFileNotFound(find_errs) => errs.extend(find_errs),
+                err => return Err(err.into()),
+            },
+        }
+    }
+
+    match found.len() {
+        0 => Err(Error::LocaleFileNotFound(errs).into()),
+        1 => Ok(found.remove(0)),
+        _ => Err(
+            Error::MultipleFilesFormats(found.into_iter().map(|(_, path, _)| path).collect())
+                .into(),
+        ),
     }
+}
+
+/// Whether the locale is read from the files of the `{locale}/` directory when it has no file of its own.
+fn reads_locale_dir(
+    locales_dir_path: &Path,
+    locale: &Key,
+    namespace: Option<&Key>,
+    options: &ParseOptions,
+) -> bool {
+    namespace.is_none()
+        && options
+            .file_formats()
+            .all(|file_format| file_format.locale_file_path(&locale.name, None).is_none())
+        && locales_dir_path.join(&*locale.name).is_dir()
+}
+
+/// The subkeys the file at `path` in `locale_dir` is merged into, its relative path: `settings/profile.json` is `settings.profile`.
+fn dir_file_prefix(locale_dir: &Path, path: &Path, options: &ParseOptions) -> Result<Vec<Key>> {
+    let relative_path = path.strip_prefix(locale_dir).unwrap_at("dir_file_prefix_1");
+    let mut prefix = relative_path
+        .parent()
+        .into_iter()
+        .flat_map(Path::iter)
+        .map(|segment| Key::try_with_mapping(&segment.to_string_lossy(), &options.key_mapping))
+        .collect::<Result<Vec<_>>>()?;
+    let file_stem = relative_path.file_stem().unwrap_at("dir_file_prefix_2");
+    prefix.push(Key::try_with_mapping(
+        &file_stem.to_string_lossy(),
+        &options.key_mapping,
+    )?);
+    Ok(prefix)
+}
 
-    Err(Error::LocaleFileNotFound(errs).into())
+/// The files the translations of a locale are read from, with the subkeys each file is merged into:
+/// the file of the locale and namespace, or the files of the `{locale}/` directory, as `Locale::parse_at` reads them.
+pub fn locale_files<'a>(
+    locales_dir_path: &Path,
+    locale: &Key,
+    namespace: Option<&Key>,
+    options: &'a ParseOptions,
+) -> Result<Vec<(PathBuf, Vec<Key>, &'a FileFormat)>> {
+    match open_locale_file(locales_dir_path, locale, namespace, options) {
+        Ok((_, path, file_format)) => Ok(vec![(path, vec![], file_format)]),
+        Err(err) => match err.into_inner() {
+            Error::LocaleFileNotFound(_)
+                if reads_locale_dir(locales_dir_path, locale, namespace, options) =>
+            {
+                let locale_dir = locales_dir_path.join(&*locale.name);
+                let mut files = vec![];
+                find_files_in_dir(&locale_dir, options, &mut files)?;
+                files
+                    .into_iter()
+                    .map(|(path, file_format)| {
+                        let prefix = dir_file_prefix(&locale_dir, &path, options)?;
+                        Ok((path, prefix, file_format))
+                    })
+                    .collect()
+            }
+            err => Err(err.into()),
+        },
+    }
 }
 
 impl InterpolOrLit {
@@ -259,6 +459,53 @@ impl InterpolationKeys {
         var_infos.bounds.insert(bounds);
     }
 
+    /// Push a variable found in the text of a value, every use of the variable must declare the same default value.
+    pub fn push_var_with_default(
+        &mut self,
+        key_path: &KeyPath,
+        key: Key,
+        bounds: VarBounds,
+        default: Option<&str>,
+    ) -> Result<()> {
+        let var_infos = self.variables.entry(key.clone()).or_default();
+        if default.is_some()
+            && (var_infos.range_count.is_some()
+                || var_infos.select_variants.is_some()
+                || var_infos.condition)
+        {
+            return Err(Error::InvalidVarDefault {
+                key_path: key_path.clone(),
+                key,
+            }
+            .into());
+        }
+        if let Some(ty) = bounds.var_type()
+            && var_infos
+                .bounds
+                .iter()
+                .filter_map(VarBounds::var_type)
+                .any(|other| other != ty)
+        {
+            return Err(Error::VarTypeMismatch {
+                key_path: key_path.clone(),
+                key,
+            }
+            .into());
+        }
+        var_infos.bounds.insert(bounds);
+        var_infos.check_plural_offset(key_path, &key)?;
+        if !std::mem::replace(&mut var_infos.used, true) {
+            var_infos.default = default.map(str::to_owned);
+        } else if var_infos.default.as_deref() != default {
+            return Err(Error::VarDefaultMismatch {
+                key_path: key_path.clone(),
+                key,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
     pub fn push_comp_self_closed(&mut self, key: Key) {
         self.components_self_closed.insert(key);
     }
@@ -273,7 +520,28 @@ impl InterpolationKeys {
         ty: RangeOrPlural,
         count_key: Key,
     ) -> Result<()> {
-        let var_infos = self.variables.entry(count_key).or_default();
+        let var_infos = self.variables.entry(count_key.clone()).or_default();
+        if var_infos.default.is_some() {
+            return Err(Error::InvalidVarDefault {
+                key_path: key_path.clone(),
+                key: count_key,
+            }
+            .into());
+        }
+        if var_infos.select_variants.is_some() {
+            return Err(Error::SelectAndCountMix {
+                key_path: key_path.clone(),
+                key: count_key,
+            }
+            .into());
+        }
+        if var_infos.condition {
+            return Err(Error::ConditionAndSelectMix {
+                key_path: key_path.clone(),
+                key: count_key,
+            }
+            .into());
+        }
         match (var_infos.range_count.replace(ty), ty) {
             (None, _) | (Some(RangeOrPlural::Plural), RangeOrPlural::Plural) => Ok(()),
             (Some(RangeOrPlural::Range(old)), RangeOrPlural::Range(new)) if old == new => Ok(()),
@@ -295,6 +563,68 @@ impl InterpolationKeys {
         }
     }
 
+    /// Mark the count of plurals with an offset, the count then also needs to be offset.
+    pub fn push_plural_offset(&mut self, key_path: &KeyPath, count_key: Key) -> Result<()> {
+        let var_infos = self.variables.entry(count_key.clone()).or_default();
+        var_infos.plural_offset = true;
+        var_infos.check_plural_offset(key_path, &count_key)
+    }
+
+    pub fn push_select(
+        &mut self,
+        key_path: &mut KeyPath,
+        select_key: Key,
+        variants: impl IntoIterator<Item = Key>,
+    ) -> Result<()> {
+        let var_infos = self.variables.entry(select_key.clone()).or_default();
+        if var_infos.default.is_some() {
+            return Err(Error::InvalidVarDefault {
+                key_path: key_path.clone(),
+                key: select_key,
+            }
+            .into());
+        }
+        if var_infos.range_count.is_some() {
+            return Err(Error::SelectAndCountMix {
+                key_path: key_path.clone(),
+                key: select_key,
+            }
+            .into());
+        }
+        if var_infos.condition {
+            return Err(Error::ConditionAndSelectMix {
+                key_path: key_path.clone(),
+                key: select_key,
+            }
+            .into());
+        }
+        var_infos
+            .select_variants
+            .get_or_insert_default()
+            .extend(variants);
+        Ok(())
+    }
+
+    pub fn push_condition(&mut self, key_path: &mut KeyPath, condition_key: Key) -> Result<()> {
+        let var_infos = self.variables.entry(condition_key.clone()).or_default();
+        if var_infos.default.is_some() {
+            return Err(Error::InvalidVarDefault {
+                key_path: key_path.clone(),
+                key: condition_key,
+            }
+            .into());
+        }
+        if var_infos.range_count.is_some() || var_infos.select_variants.is_some() {
+            return Err(Error::ConditionAndSelectMix {
+                key_path: key_path.clone(),
+                key: condition_key,
+            }
+            .into());
+        }
+        var_infos.condition = true;
+        Ok(())
+    }
+
     pub fn iter_keys(&self) -> impl Iterator<Item = &Key> {
         self.components.iter().chain(self.variables.keys())
     }
@@ -329,7 +659,7 @@ impl BuildersKeysInner {
 
 impl Namespace {
     pub fn new(
-        locales_dir_path: &mut PathBuf,
+        locales_dir_path: &Path,
         key: Key,
         locale_keys: &[Key],
         foreign_keys_paths: &ForeignKeysPaths,
@@ -339,26 +669,16 @@ impl Namespace {
     ) -> Result<Self> {
         let mut locales = Vec::with_capacity(locale_keys.len());
         for locale in locale_keys.iter().cloned() {
-            let file_path: &Path = key.name.as_ref().as_ref();
-            locales_dir_path.push(&*locale.name);
-            locales_dir_path.push(file_path);
-
-            let locale_file = find_file(locales_dir_path, &options.file_format)?;
-
-            let locale = Locale::new(
-                locale_file,
+            let locale = Locale::parse_at(
                 locales_dir_path,
                 locale,
-                Some(key.clone()),
+                Some(&key),
                 foreign_keys_paths,
                 diag,
                 tracked_files,
                 options,
             )?;
-
             locales.push(locale);
-            locales_dir_path.pop();
-            locales_dir_path.pop();
         }
         Ok(Namespace { key, locales })
     }
@@ -372,6 +692,16 @@ impl LocalesOrNamespaces {
         tracked_files: &mut Vec<String>,
         cfg: &Config,
     ) -> Result<Self> {
+        if let Some(table_path) = &cfg.translations_table {
+            manifest_dir_path.push(table_path);
+            return Self::from_table(
+                manifest_dir_path,
+                foreign_keys_paths,
+                diag,
+                tracked_files,
+                cfg,
+            );
+        }
         manifest_dir_path.push(&cfg.locales_path);
         if !cfg.namespaces.is_empty() {
             let mut namespaces = Vec::with_capacity(cfg.namespaces.len());
@@ -390,10 +720,7 @@ impl LocalesOrNamespaces {
         } else {
             let mut locales = Vec::with_capacity(cfg.locales.len());
             for locale in cfg.locales.iter().cloned() {
-                manifest_dir_path.push(&*locale.name);
-                let locale_file = find_file(manifest_dir_path, &cfg.options.file_format)?;
-                let locale = Locale::new(
-                    locale_file,
+                let locale = Locale::parse_at(
                     manifest_dir_path,
                     locale,
                     None,
@@ -403,22 +730,48 @@ impl LocalesOrNamespaces {
                     &cfg.options,
                 )?;
                 locales.push(locale);
-                manifest_dir_path.pop();
             }
             Ok(LocalesOrNamespaces::Locales(locales))
         }
     }
 
+    fn from_table(
+        table_path: &mut PathBuf,
+        foreign_keys_paths: &ForeignKeysPaths,
+        diag: &Diagnostics,
+        tracked_files: &mut Vec<String>,
+        cfg: &Config,
+    ) -> Result<Self> {
+        let src = std::fs::read_to_string(&table_path)
+            .map_err(|err| Error::LocaleFileNotFound(vec![(table_path.clone(), err)]))?;
+        track_file(tracked_files, &cfg.default_locale, None, table_path, diag);
+
+        let ctx = TableContext {
+            cfg,
+            foreign_keys_paths,
+            diag,
+        };
+        ctx.parse(&src, table::delimiter_of(table_path))
+            .map_err(|err| {
+                Error::LocaleFileDeser {
+                    path: std::mem::take(table_path),
+                    err: SerdeError::Table(err),
+                }
+                .into()
+            })
+    }
+
     pub fn merge_plurals_inner(
         locales: &mut [Locale],
         namespace: Option<Key>,
         diag: &Diagnostics,
+        options: &ParseOptions,
     ) -> Result<()> {
         let mut key_path = KeyPath::new(namespace);
 
         for locale in locales {
             let top_locale = locale.name.clone();
-            locale.merge_plurals(&top_locale, &mut key_path, diag)?;
+            locale.merge_plurals(&top_locale, &mut key_path, diag, options)?;
         }
 
         Ok(())
@@ -426,7 +779,7 @@ impl LocalesOrNamespaces {
 
     // this step would be more optimized to be done during `check_locales` but plurals merging need to be done before foreign key resolution,
     // which also need to be done before `check_locales`.
-    pub fn merge_plurals(&mut self, diag: &Diagnostics) -> Result<()> {
+    pub fn merge_plurals(&mut self, diag: &Diagnostics, options: &ParseOptions) -> Result<()> {
         match self {
             LocalesOrNamespaces::NameSpaces(namespaces) => {
                 for namespace in namespaces {
@@ -434,12 +787,13 @@ impl LocalesOrNamespaces {
                         &mut namespace.locales,
                         Some(namespace.key.clone()),
                         diag,
+                        options,
                     )?;
                 }
                 Ok(())
             }
             LocalesOrNamespaces::Locales(locales) => {
-                Self::merge_plurals_inner(&mut *locales, None, diag)
+                Self::merge_plurals_inner(&mut *locales, None, diag, options)
             }
         }
     }
@@ -469,6 +823,7 @@ impl Locale {
     pub fn new(
         locale_file: File,
         path: &mut PathBuf,
+        file_format: &FileFormat,
         locale: Key,
         namespace: Option<Key>,
         foreign_keys_paths: &ForeignKeysPaths,
@@ -485,9 +840,141 @@ impl Locale {
             foreign_keys_paths,
             diag,
             formatters: &options.formatters,
+            parse_fns: &options.parse_fns(),
+            key_mapping: &options.key_mapping,
+        };
+
+        Self::de(locale_file, path, seed, file_format)
+    }
+
+    /// Parse the file of the locale in `locales_dir_path`, or when not using namespaces,
+    /// the files of the `{locale}/` directory if there is no `{locale}.{ext}` file.
+    pub fn parse_at(
+        locales_dir_path: &Path,
+        locale: Key,
+        namespace: Option<&Key>,
+        foreign_keys_paths: &ForeignKeysPaths,
+        diag: &Diagnostics,
+        tracked_files: &mut Vec<String>,
+        options: &ParseOptions,
+    ) -> Result<Self> {
+        match open_locale_file(locales_dir_path, &locale, namespace, options) {
+            Ok((locale_file, mut path, file_format)) => Locale::new(
+                locale_file,
+                &mut path,
+                file_format,
+                locale,
+                namespace.cloned(),
+                foreign_keys_paths,
+                diag,
+                tracked_files,
+                options,
+            ),
+            Err(err) => match err.into_inner() {
+                Error::LocaleFileNotFound(_)
+                    if reads_locale_dir(locales_dir_path, &locale, namespace, options) =>
+                {
+                    Locale::from_dir(
+                        &locales_dir_path.join(&*locale.name),
+                        locale,
+                        foreign_keys_paths,
+                        diag,
+                        tracked_files,
+                        options,
+                    )
+                }
+                err => Err(err.into()),
+            },
+        }
+    }
+
+    /// Merge every file in `locale_dir` and its subdirectories, the relative path of a file being used as subkeys:
+    /// `settings/profile.json` is `settings.profile`.
+    pub fn from_dir(
+        locale_dir: &Path,
+        locale: Key,
+        foreign_keys_paths: &ForeignKeysPaths,
+        diag: &Diagnostics,
+        tracked_files: &mut Vec<String>,
+        options: &ParseOptions,
+    ) -> Result<Self> {
+        let mut files = vec![];
+        find_files_in_dir(locale_dir, options, &mut files)?;
+        track_file(tracked_files, &locale, None, locale_dir, diag);
+
+        let mut this = Locale {
+            top_locale_name: locale.clone(),
+            name: locale.clone(),
+            keys: BTreeMap::new(),
+            strings: vec![],
+            top_locale_string_count: 0,
+            metadata: BTreeMap::new(),
         };
 
-        Self::de(locale_file, path, seed, &options.file_format)
+        for (mut path, file_format) in files {
+            let prefix = dir_file_prefix(locale_dir, &path, options)?;
+
+            track_file(tracked_files, &locale, None, &path, diag);
+            let locale_file = File::open(&path).map_err(Error::IoError)?;
+            let seed = LocaleSeed {
+                name: locale.clone(),
+                top_locale_name: locale.clone(),
+                key_path: KeyPath::new_from_path(None, prefix.clone()),
+                foreign_keys_paths,
+                diag,
+                formatters: &options.formatters,
+                parse_fns: &options.parse_fns(),
+                key_mapping: &options.key_mapping,
+            };
+            let file_locale = Self::de(locale_file, &mut path, seed, file_format)?;
+            this.insert_file_keys(&prefix, file_locale, &path)?;
+        }
+
+        Ok(this)
+    }
+
+    fn insert_file_keys(&mut self, prefix: &[Key], file_locale: Locale, path: &Path) -> Result<()> {
+        let top_locale_name = self.top_locale_name.clone();
+        let mut key_path = KeyPath::new(None);
+        let mut target = self;
+        for segment in prefix {
+            key_path.path.push(segment.clone());
+            let value = target.keys.entry(segment.clone()).or_insert_with(|| {
+                ParsedValue::Subkeys(Some(Locale {
+                    top_locale_name: top_locale_name.clone(),
+                    name: segment.clone(),
+                    keys: BTreeMap::new(),
+                    strings: vec![],
+                    top_locale_string_count: 0,
+                    metadata: BTreeMap::new(),
+                }))
+            });
+            let ParsedValue::Subkeys(Some(subkeys)) = value else {
+                return Err(Error::DuplicateKeyInLocaleDir {
+                    loc: Location::new(top_locale_name, key_path),
+                    path: path.to_owned(),
+                }
+                .into());
+            };
+            target = subkeys;
+        }
+        for (key, value) in file_locale.keys {
+            match target.keys.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(entry) => {
+                    key_path.path.push(entry.key().clone());
+                    return Err(Error::DuplicateKeyInLocaleDir {
+                        loc: Location::new(top_locale_name, key_path),
+                        path: path.to_owned(),
+                    }
+                    .into());
+                }
+            }
+        }
+        target.metadata.extend(file_locale.metadata);
+        Ok(())
     }
 
     fn de(
@@ -542,24 +1029,81 @@ impl Locale {
         PluralForm::try_from_str(suffix).map(|form| (base_key, rule_type, form))
     }
 
+    fn split_select_suffix<'a>(key: &'a Key, value: &ParsedValue) -> Option<(&'a str, &'a str)> {
+        if matches!(value, ParsedValue::Ranges(_) | ParsedValue::Subkeys(_)) {
+            return None;
+        }
+        key.name.rsplit_once('_')
+    }
+
+    /// Base keys of the suffixed keys forming a select: a `_other` key along with at least one suffix that is not a plural form.
+    fn find_select_bases(keys: &BTreeMap<Key, ParsedValue>) -> BTreeSet<String> {
+        let mut bases: BTreeMap<&str, (bool, bool)> = BTreeMap::new();
+        for (key, value) in keys {
+            let Some((base_key, suffix)) = Self::split_select_suffix(key, value) else {
+                continue;
+            };
+            let (has_other, has_variant) = bases.entry(base_key).or_default();
+            if suffix == SELECT_OTHER {
+                *has_other = true;
+            } else if PluralForm::try_from_str(suffix).is_none() {
+                *has_variant = true;
+            }
+        }
+        bases
+            .into_iter()
+            .filter(|(_, (has_other, has_variant))| *has_other && *has_variant)
+            .map(|(base_key, _)| base_key.to_owned())
+            .collect()
+    }
+
     pub fn merge_plurals(
         &mut self,
         locale: &Key,
         key_path: &mut KeyPath,
         diag: &Diagnostics,
+        options: &ParseOptions,
     ) -> Result<()> {
         let keys = std::mem::take(&mut self.keys);
+        let select_bases = match options.select_suffix_var {
+            Some(_) => Self::find_select_bases(&keys),
+            None => BTreeSet::new(),
+        };
         #[allow(clippy::type_complexity)]
         let mut possible_plurals: BTreeMap<
             String,
             BTreeMap<PluralForm, (Key, PluralRuleType, ParsedValue)>,
         > = BTreeMap::new();
+        let mut selects: BTreeMap<String, BTreeMap<Key, ParsedValue>> = BTreeMap::new();
+        #[allow(clippy::type_complexity)]
+        let mut dimensions_plurals: BTreeMap<
+            String,
+            Vec<(Vec<(String, PluralForm)>, ParsedValue)>,
+        > = BTreeMap::new();
         for (key, mut value) in keys {
             if let ParsedValue::Subkeys(Some(subkeys)) = &mut value {
                 let mut pushed_key = key_path.push_key(key.clone());
-                subkeys.merge_plurals(locale, &mut pushed_key, diag)?;
+                subkeys.merge_plurals(locale, &mut pushed_key, diag, options)?;
             }
-            if let Some((base_key, rule_type, plural_form)) = Self::is_possible_plural(&key, &value)
+            if let Some((base_key, dimensions)) = plurals::split_plural_dimensions(&key.name)
+                .filter(|_| !matches!(value, ParsedValue::Ranges(_) | ParsedValue::Subkeys(_)))
+            {
+                let dimensions = dimensions
+                    .into_iter()
+                    .map(|(var, form)| (var.to_owned(), form))
+                    .collect();
+                dimensions_plurals
+                    .entry(base_key.to_owned())
+                    .or_default()
+                    .push((dimensions, value));
+            } else if let Some((base_key, suffix)) = Self::split_select_suffix(&key, &value)
+                .filter(|(base_key, _)| select_bases.contains(*base_key))
+            {
+                let variant = Key::try_new(suffix)?;
+                let variants = selects.entry(base_key.to_owned()).or_default();
+                variants.insert(variant, value);
+            } else if let Some((base_key, rule_type, plural_form)) =
+                Self::is_possible_plural(&key, &value)
             {
                 let map = possible_plurals.entry(base_key.to_owned()).or_default();
                 map.insert(plural_form, (key, rule_type, value));
@@ -568,19 +1112,28 @@ impl Locale {
             }
         }
         for (base_key, mut plurals) in possible_plurals {
-            if plurals.len() == 1 {
-                for (_, (key, _, value)) in plurals {
-                    self.keys.insert(key, value);
-                }
-                continue;
-            }
-            let Some((_, rule_type, other)) = plurals.remove(&PluralForm::Other) else {
-                for (_, (key, _, value)) in plurals {
-                    self.keys.insert(key, value);
+            let other = match plurals.remove(&PluralForm::Other) {
+                Some(other) if !plurals.is_empty() => other,
+                other => {
+                    for (form, (key, _, value)) in plurals
+                        .into_iter()
+                        .chain(other.map(|other| (PluralForm::Other, other)))
+                    {
+                        // exact forms are only valid as part of plurals.
+                        if form.is_exact() {
+                            let pushed_key = key_path.push_key(key);
+                            return Err(Error::MissingPluralFallback {
+                                loc: Location::new(locale.clone(), pushed_key.clone()),
+                            }
+                            .into());
+                        }
+                        self.keys.insert(key, value);
+                    }
+                    continue;
                 }
-                continue;
             };
-            let key = Key::new(&base_key).unwrap_at("merge_plurals_1");
+            let (_, rule_type, other) = other;
+            let key = Key::try_with_mapping(&base_key, &options.key_mapping)?;
             let pushed_key = key_path.push_key(key);
             if !cfg!(feature = "plurals") {
                 return Err(Error::DisabledPlurals {
@@ -607,6 +1160,7 @@ impl Locale {
                 forms,
                 count_key: Key::count(),
                 other: Box::new(other),
+                offset: 0,
             };
             let loc = Loc {
                 locale,
@@ -623,7 +1177,63 @@ impl Locale {
                 .into());
             }
         }
+        for (base_key, values) in dimensions_plurals {
+            let key = Key::try_with_mapping(&base_key, &options.key_mapping)?;
+            let pushed_key = key_path.push_key(key);
+            let loc = Loc {
+                locale,
+                key_path: &pushed_key,
+            };
+            let value = Plurals::from_dimensions(values, &loc, diag)?;
+            let key = pushed_key.pop().unwrap_at("merge_plurals_4");
+            if self.keys.insert(key.clone(), value).is_some() {
+                let pushed_key = key_path.push_key(key);
+                return Err(Error::PluralsAtNormalKey {
+                    loc: Location::new(locale.clone(), pushed_key.clone()),
+                }
+                .into());
+            }
+        }
+        if let Some(select_key) = &options.select_suffix_var {
+            for (base_key, variants) in selects {
+                let key = Key::try_with_mapping(&base_key, &options.key_mapping)?;
+                let pushed_key = key_path.push_key(key);
+                let loc = Loc {
+                    locale,
+                    key_path: &pushed_key,
+                };
+                let select = Select::new(select_key.clone(), variants, &loc)?;
+                let key = pushed_key.pop().unwrap_at("merge_plurals_5");
+                if self
+                    .keys
+                    .insert(key.clone(), ParsedValue::Select(select))
+                    .is_some()
+                {
+                    let pushed_key = key_path.push_key(key);
+                    return Err(Error::SelectAtNormalKey {
+                        loc: Location::new(locale.clone(), pushed_key.clone()),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        self.check_idents_collisions(locale, key_path)
+    }
 
+    /// Different keys can map to the same identifier, such as `user-name` and `user_name`.
+    fn check_idents_collisions(&self, locale: &Key, key_path: &mut KeyPath) -> Result<()> {
+        let mut idents: BTreeMap<String, &Key> = BTreeMap::new();
+        for key in self.keys.keys() {
+            if let Some(other) = idents.insert(key.ident.to_string(), key) {
+                let pushed_key = key_path.push_key(key.clone());
+                return Err(Error::KeyIdentCollision {
+                    loc: Location::new(locale.clone(), pushed_key.clone()),
+                    other: other.clone(),
+                }
+                .into());
+            }
+        }
         Ok(())
     }
 
@@ -715,7 +1325,7 @@ impl<'de> serde::de::DeserializeSeed<'de> for LocaleSeed<'_> {
     where
         D: serde::Deserializer<'de>,
     {
-        let keys = deserializer.deserialize_map(self.clone())?;
+        let (keys, metadata) = deserializer.deserialize_map(self.clone())?;
         let Self {
             name,
             top_locale_name,
@@ -727,20 +1337,33 @@ impl<'de> serde::de::DeserializeSeed<'de> for LocaleSeed<'_> {
             top_locale_name,
             strings: vec![],
             top_locale_string_count: 0,
+            metadata,
         })
     }
 }
 
 impl<'de> serde::de::Visitor<'de> for LocaleSeed<'_> {
-    type Value = BTreeMap<Key, ParsedValue>;
+    type Value = (BTreeMap<Key, ParsedValue>, BTreeMap<Key, KeyMetadata>);
 
     fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
     where
         A: MapAccess<'de>,
     {
         let mut keys = BTreeMap::new();
-
-        while let Some(locale_key) = map.next_key::<Key>()? {
+        let mut keys_metadata = BTreeMap::new();
+
+        while let Some(locale_key) = map.next_key::<String>()? {
+            if let Some(target) = locale_key.strip_prefix(METADATA_PREFIX) {
+                if let Some(key_metadata) = metadata::next_metadata(&mut map, target)? {
+                    let target = Key::try_with_mapping(target, self.key_mapping)
+                        .map_err(serde::de::Error::custom)?;
+                    keys_metadata.insert(target, key_metadata);
+                }
+                continue;
+            }
+            let locale_key = Key::try_with_mapping(&locale_key, self.key_mapping)
+                .or_else(|err| plurals::plural_key(&locale_key, self.key_mapping).ok_or(err))
+                .map_err(serde::de::Error::custom)?;
             let pushed_key = self.key_path.push_key(locale_key.clone());
             let value = map.next_value_seed(ParsedValueSeed {
                 top_locale_name: &self.top_locale_name,
@@ -750,11 +1373,27 @@ impl<'de> serde::de::Visitor<'de> for LocaleSeed<'_> {
                 foreign_keys_paths: self.foreign_keys_paths,
                 diag: self.diag,
                 formatters: self.formatters,
+                parse_fns: self.parse_fns,
+                key_mapping: self.key_mapping,
             })?;
             keys.insert(locale_key, value);
         }
 
-        Ok(keys)
+        let ctx = ParseContext {
+            loc: Loc {
+                key_path: &self.key_path,
+                locale: &self.top_locale_name,
+            },
+            foreign_keys_paths: self.foreign_keys_paths,
+            diag: self.diag,
+            formatters: self.formatters,
+            parse_fns: self.parse_fns,
+            key_mapping: self.key_mapping,
+        };
+        metadata::insert_aliases(&mut keys, &keys_metadata, &ctx)
+            .map_err(serde::de::Error::custom)?;
+
+        Ok((keys, keys_metadata))
     }
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -782,3 +1421,784 @@ fn track_file(
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatters::VarBounds;
+    use crate::parse_locales::constraints;
+    use crate::parse_locales::parsed_value::{ForeignKey, Literal};
+
+    fn lit(s: &str) -> ParsedValue {
+        ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX))
+    }
+
+    fn merge_keys(keys: &[(&str, &str)], options: &ParseOptions) -> Result<Locale> {
+        let locale_key = Key::new("en").unwrap();
+        let mut locale = Locale {
+            top_locale_name: locale_key.clone(),
+            name: locale_key.clone(),
+            keys: keys
+                .iter()
+                .map(|(key, value)| (Key::new(key).unwrap(), lit(value)))
+                .collect(),
+            strings: vec![],
+            top_locale_string_count: 0,
+            metadata: BTreeMap::new(),
+        };
+        let diag = Diagnostics::new();
+        locale.merge_plurals(&locale_key, &mut KeyPath::new(None), &diag, options)?;
+        Ok(locale)
+    }
+
+    #[test]
+    fn merge_select_suffixes() {
+        let keys = [
+            ("invite_male", "He invited you"),
+            ("invite_female", "She invited you"),
+            ("invite_other", "They invited you"),
+            ("unrelated_other", "other"),
+        ];
+        let options = ParseOptions::default().select_suffix_var("gender").unwrap();
+        let locale = merge_keys(&keys, &options).unwrap();
+
+        let expected = ParsedValue::Select(Select {
+            select_key: Key::new("var_gender").unwrap(),
+            other: Box::new(lit("They invited you")),
+            variants: BTreeMap::from([
+                (Key::new("female").unwrap(), lit("She invited you")),
+                (Key::new("male").unwrap(), lit("He invited you")),
+            ]),
+        });
+        assert_eq!(locale.keys.get("invite"), Some(&expected));
+        assert_eq!(locale.keys.get("unrelated_other"), Some(&lit("other")));
+
+        // suffixed selects are opt-in
+        let locale = merge_keys(&keys, &ParseOptions::default()).unwrap();
+        assert_eq!(locale.keys.len(), keys.len());
+    }
+
+    fn parse_json(json: &str, options: &ParseOptions) -> Result<Locale> {
+        parse_json_with_foreign_keys(json, options, &ForeignKeysPaths::new())
+    }
+
+    fn parse_json_with_foreign_keys(
+        json: &str,
+        options: &ParseOptions,
+        foreign_keys_paths: &ForeignKeysPaths,
+    ) -> Result<Locale> {
+        let locale_key = Key::new("en").unwrap();
+        let diag = Diagnostics::new();
+        let seed = LocaleSeed {
+            name: locale_key.clone(),
+            top_locale_name: locale_key.clone(),
+            key_path: KeyPath::new(None),
+            foreign_keys_paths,
+            diag: &diag,
+            formatters: &options.formatters,
+            parse_fns: &options.parse_fns(),
+            key_mapping: &options.key_mapping,
+        };
+        let mut locale = serde::de::DeserializeSeed::deserialize(
+            seed,
+            &mut serde_json::Deserializer::from_str(json),
+        )
+        .map_err(|err| Error::LocaleFileDeser {
+            path: PathBuf::from("en.json"),
+            err: SerdeError::Json(err),
+        })?;
+        locale.merge_plurals(&locale_key, &mut KeyPath::new(None), &diag, options)?;
+        Ok(locale)
+    }
+
+    /// Parse a locale and resolve its foreign keys.
+    fn parse_resolved_json(json: &str) -> Result<Locale> {
+        let foreign_keys_paths = ForeignKeysPaths::new();
+        let locale =
+            parse_json_with_foreign_keys(json, &ParseOptions::default(), &foreign_keys_paths)?;
+        let locales = LocalesOrNamespaces::Locales(vec![locale]);
+        super::super::resolve_foreign_keys(
+            &locales,
+            &Key::new("en").unwrap(),
+            foreign_keys_paths.into_inner(),
+        )?;
+        let LocalesOrNamespaces::Locales(mut locales) = locales else {
+            unreachable!()
+        };
+        Ok(locales.remove(0))
+    }
+
+    #[test]
+    fn alias_subkeys() {
+        let json = r#"{
+            "common": {
+                "buttons": { "ok": "Ok", "cancel": "$t(common.cancel)" },
+                "cancel": "Cancel"
+            },
+            "dialog_buttons": "$t(common.buttons)"
+        }"#;
+        let mut locale = parse_resolved_json(json).unwrap();
+        let mut keys = locale
+            .make_builder_keys(&mut KeyPath::new(None), &mut StringIndexer::default())
+            .unwrap();
+        let Some(LocaleValue::Subkeys { keys, locales }) =
+            keys.0.remove(&Key::new("dialog_buttons").unwrap())
+        else {
+            panic!("expected the alias to be subkeys");
+        };
+        assert_eq!(
+            keys.0.keys().map(|key| &*key.name).collect::<Vec<_>>(),
+            ["cancel", "ok"]
+        );
+        // the strings are indexed by `make_builder_keys`
+        let get = |key: &str| match locales[0].get_value_at(&[Key::new(key).unwrap()]) {
+            Some(ParsedValue::Literal(lit)) => lit.is_string().map(str::to_owned),
+            _ => None,
+        };
+        assert_eq!(get("ok").as_deref(), Some("Ok"));
+        assert_eq!(get("cancel").as_deref(), Some("Cancel"));
+
+        let err = parse_resolved_json(r#"{ "a": { "b": "$t(a)" } }"#).unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::RecursiveForeignKey { .. }
+        ));
+
+        let json = r#"{ "a": { "b": "B" }, "c": "$t(a, { \"x\": \"y\" })" }"#;
+        let err = parse_resolved_json(json).unwrap_err();
+        assert!(matches!(err.into_inner(), Error::InvalidForeignKey { .. }));
+
+        let mut locale = parse_resolved_json(r#"{ "a": { "b": "B" }, "c": "see $t(a)" }"#).unwrap();
+        let err = locale
+            .make_builder_keys(&mut KeyPath::new(None), &mut StringIndexer::default())
+            .unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::SubkeysAliasInValue { .. }
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn foreign_key_plural_dimensions() {
+        let json = r#"{
+            "summary_files_one.folders_one": "{{ files }} file in {{ folders }} folder",
+            "summary_files_one.folders_other": "{{ files }} file in {{ folders }} folders",
+            "summary_files_other.folders_one": "{{ files }} files in {{ folders }} folder",
+            "summary_files_other.folders_other": "{{ files }} files in {{ folders }} folders",
+            "items_one": "{{ count }} item",
+            "items_other": "{{ count }} items",
+            "three_files": "$t(summary, { \"files\": 3, \"folders\": 1 })",
+            "one_item": "$t(items, { \"count\": 1 })"
+        }"#;
+        let locale = parse_resolved_json(json).unwrap();
+        fn flatten(value: &ParsedValue, acc: &mut String) {
+            match value {
+                ParsedValue::Literal(Literal::String(s, _)) => acc.push_str(s),
+                ParsedValue::Literal(Literal::Unsigned(n)) => acc.push_str(&n.to_string()),
+                ParsedValue::Bloc(values) => values.iter().for_each(|value| flatten(value, acc)),
+                value => panic!("unexpected value {value:?}"),
+            }
+        }
+        // each count of the foreign key arguments selects the form of its own plural
+        let render = |key: &str| {
+            let Some(ParsedValue::ForeignKey(foreign_key)) =
+                locale.keys.get(&Key::new(key).unwrap())
+            else {
+                panic!("expected a foreign key");
+            };
+            let ForeignKey::Set(value) = &*foreign_key.borrow() else {
+                panic!("expected the foreign key to be resolved");
+            };
+            let mut rendered = String::new();
+            flatten(value, &mut rendered);
+            rendered
+        };
+        assert_eq!(render("three_files"), "3 files in 1 folder");
+        assert_eq!(render("one_item"), "1 item");
+    }
+
+    #[test]
+    fn parse_lists() {
+        let options = ParseOptions::default();
+        let mut en = parse_json(r#"{ "features": ["Fast", "Cheap"] }"#, &options).unwrap();
+        let features = Key::new("features").unwrap();
+        assert_eq!(
+            en.keys.get(&features),
+            Some(&ParsedValue::List(vec![
+                Literal::String("Fast".to_string(), usize::MAX),
+                Literal::String("Cheap".to_string(), usize::MAX),
+            ]))
+        );
+
+        let mut strings = StringIndexer::default();
+        let mut keys = en
+            .make_builder_keys(&mut KeyPath::new(None), &mut strings)
+            .unwrap();
+        assert!(matches!(
+            keys.0.get(&features),
+            Some(LocaleValue::Value {
+                value: InterpolOrLit::Lit(LiteralType::List(2)),
+                ..
+            })
+        ));
+
+        // lengths can differ between locales
+        let mut merge = |json: &str, diag: &Diagnostics| {
+            let mut fr = parse_json(json, &options).unwrap();
+            fr.top_locale_name = Key::new("fr").unwrap();
+            fr.merge(
+                &mut keys,
+                Key::new("fr").unwrap(),
+                &DefaultTo::Explicit(Key::new("en").unwrap()),
+                &mut KeyPath::new(None),
+                &mut strings,
+                diag,
+                &options,
+            )
+        };
+        let diag = Diagnostics::new();
+        merge(
+            r#"{ "features": ["Rapide", "Bon marché", "Simple"] }"#,
+            &diag,
+        )
+        .unwrap();
+        let warnings = diag.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0],
+            Warning::ListLengthMismatch {
+                len: 3,
+                expected: 2,
+                ..
+            }
+        ));
+
+        let err = merge(r#"{ "features": "Rapide" }"#, &Diagnostics::new()).unwrap_err();
+        assert!(matches!(err.into_inner(), Error::ListMismatch { .. }));
+
+        let err = parse_json(r#"{ "a": ["Hello {{ name }}"] }"#, &options).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("list items can only be plain text")
+        );
+
+        let json = r#"{ "a": [[["b", "c"], 0], ["d", "_"]] }"#;
+        let err = parse_json(json, &options).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("lists are not allowed inside ranges")
+        );
+
+        // a first item looking like a numerical type is a mistyped range
+        let err = parse_json(r#"{ "a": ["u23", ["zero", 0]] }"#, &options).unwrap_err();
+        assert!(err.to_string().contains(r#"invalid range type "u23""#));
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn merge_plural_dimensions() {
+        let json = r#"{
+            "summary_files_one.folders_one": "a",
+            "summary_files_one.folders_other": "b",
+            "summary_files_other.folders_other": "c"
+        }"#;
+        let locale = parse_json(json, &ParseOptions::default()).unwrap();
+        let plurals = |count_key: &str, forms: Vec<(PluralForm, ParsedValue)>, other| {
+            ParsedValue::Plurals(Plurals {
+                rule_type: PluralRuleType::Cardinal,
+                count_key: Key::new(count_key).unwrap(),
+                other: Box::new(other),
+                forms: forms.into_iter().collect(),
+                offset: 0,
+            })
+        };
+        let expected = plurals(
+            "var_files",
+            vec![(
+                PluralForm::One,
+                plurals("var_folders", vec![(PluralForm::One, lit("a"))], lit("b")),
+            )],
+            plurals("var_folders", vec![], lit("c")),
+        );
+        assert_eq!(locale.keys.len(), 1);
+        assert_eq!(locale.keys.get("summary"), Some(&expected));
+
+        let json = r#"{
+            "summary_files_one.folders_other": "a",
+            "summary_folders_other.files_other": "b"
+        }"#;
+        let err = parse_json(json, &ParseOptions::default()).unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::PluralDimensionsMismatch { .. }
+        ));
+
+        let json = r#"{ "summary_files_one.folders_other": "a" }"#;
+        let err = parse_json(json, &ParseOptions::default()).unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::MissingPluralFallback { .. }
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn merge_exact_plural_forms() {
+        let json = r#"{
+            "items_=0": "no items",
+            "items_one": "one item",
+            "items_other": "some items",
+            "guests": {
+                "$plural": "count",
+                "$offset": 1,
+                "=0": "nobody",
+                "=1": "you",
+                "other": "you and others"
+            }
+        }"#;
+        let locale = parse_json(json, &ParseOptions::default()).unwrap();
+        let expected = ParsedValue::Plurals(Plurals {
+            rule_type: PluralRuleType::Cardinal,
+            count_key: Key::count(),
+            other: Box::new(lit("some items")),
+            forms: BTreeMap::from([
+                (PluralForm::Exact(0), lit("no items")),
+                (PluralForm::One, lit("one item")),
+            ]),
+            offset: 0,
+        });
+        assert_eq!(locale.keys.get("items"), Some(&expected));
+        let expected = ParsedValue::Plurals(Plurals {
+            rule_type: PluralRuleType::Cardinal,
+            count_key: Key::count(),
+            other: Box::new(lit("you and others")),
+            forms: BTreeMap::from([
+                (PluralForm::Exact(0), lit("nobody")),
+                (PluralForm::Exact(1), lit("you")),
+            ]),
+            offset: 1,
+        });
+        assert_eq!(locale.keys.get("guests"), Some(&expected));
+
+        // the count of plurals with an offset must be a primitive integer
+        let guests = |ty: &str| {
+            let json = format!(
+                r#"{{ "guests": {{ "$plural": "count", "$offset": 1, "other": "you and {{{{ count: {ty} }}}} others" }} }}"#
+            );
+            let locale = parse_json(&json, &ParseOptions::default()).unwrap();
+            locale.keys["guests"].get_keys(&mut KeyPath::new(None))
+        };
+        assert!(guests("u32").is_ok());
+        let err = guests("f64").unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::InvalidPluralOffsetType { ty, .. } if ty.name() == "f64"
+        ));
+
+        let err = parse_json(r#"{ "items_=0": "none" }"#, &ParseOptions::default()).unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::MissingPluralFallback { .. }
+        ));
+    }
+
+    #[test]
+    fn unreachable_plural_forms() {
+        let locale = Key::new("en").unwrap();
+        let key_path = KeyPath::new(None);
+        let loc = Loc {
+            locale: &locale,
+            key_path: &key_path,
+        };
+        let plurals = |forms: Vec<PluralForm>, offset| Plurals {
+            rule_type: PluralRuleType::Cardinal,
+            count_key: Key::count(),
+            other: Box::new(lit("other")),
+            forms: forms.into_iter().map(|form| (form, lit("form"))).collect(),
+            offset,
+        };
+        let check = |plurals: Plurals| {
+            let diag = Diagnostics::new();
+            plurals.check_forms(&loc, &diag).unwrap();
+            let (_, warnings) = diag.into_inner();
+            warnings
+                .into_iter()
+                .map(|warning| match warning {
+                    Warning::UnreachablePluralForm { form, .. } => form,
+                    warning => panic!("unexpected warning {warning}"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // "one" is only 1 in english
+        let forms = vec![PluralForm::Exact(1), PluralForm::One];
+        assert_eq!(check(plurals(forms.clone(), 0)), vec![PluralForm::One]);
+        // with an offset of 1 the category of 2 is "one"
+        let forms = vec![PluralForm::Exact(2), PluralForm::One];
+        assert_eq!(check(plurals(forms.clone(), 1)), vec![PluralForm::One]);
+        assert!(check(plurals(forms, 0)).is_empty());
+        let forms = vec![PluralForm::Exact(0), PluralForm::One];
+        assert!(check(plurals(forms, 0)).is_empty());
+    }
+
+    #[test]
+    fn map_keys_to_idents() {
+        let json = r#"{
+            "type": "Type",
+            "2fa_title": "Two-factor authentication",
+            "Save changes": "Save changes",
+            "user.name": "Name",
+            "userName": "Name",
+            "HTTPServer": { "maxRetries": "{{ count }} retries" }
+        }"#;
+
+        let err = parse_json(json, &ParseOptions::default()).unwrap_err();
+        assert!(err.to_string().contains(r#"invalid key "type""#));
+
+        let options = ParseOptions::default().key_mapping(KeyMapping::all().snake_case(false));
+        let locale = parse_json(json, &options).unwrap();
+        let idents = locale
+            .keys
+            .iter()
+            .map(|(key, value)| {
+                let subkeys = match value {
+                    ParsedValue::Subkeys(Some(subkeys)) => subkeys
+                        .keys
+                        .keys()
+                        .map(|key| key.ident.to_string())
+                        .collect(),
+                    _ => vec![],
+                };
+                (&*key.name, (key.ident.to_string(), subkeys))
+            })
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(idents["type"].0, "r#type");
+        assert_eq!(idents["2fa_title"].0, "_2fa_title");
+        assert_eq!(idents["Save changes"].0, "Save_changes");
+        assert_eq!(idents["user.name"].0, "user_name");
+        assert_eq!(idents["userName"].0, "userName");
+        assert_eq!(idents["HTTPServer"].1, ["maxRetries"]);
+
+        let options = ParseOptions::default().key_mapping(KeyMapping::all());
+        let err = parse_json(json, &options).unwrap_err();
+        let Error::KeyIdentCollision { loc, other } = err.into_inner() else {
+            panic!("expected a collision");
+        };
+        assert_eq!(loc.key_path.to_string(), "userName");
+        assert_eq!(&*other.name, "user.name");
+
+        let json = r#"{ "HTTPServer": { "maxRetries": "retries" }, "saveChanges": "Save" }"#;
+        let locale = parse_json(json, &options).unwrap();
+        let key = locale.keys.keys().next().unwrap();
+        assert_eq!(
+            (&*key.name, key.ident.to_string()),
+            ("HTTPServer", "http_server".into())
+        );
+        assert_eq!(
+            locale.keys.keys().nth(1).unwrap().ident.to_string(),
+            "save_changes"
+        );
+    }
+
+    #[test]
+    fn parse_keys_metadata() {
+        let json = r#"{
+            "@@locale": "en",
+            "checkout": "Checkout",
+            "@checkout": {
+                "description": "button label, shown on checkout",
+                "type": "text",
+                "maxLength": 20,
+                "screenshot": "https://example.com/checkout.png"
+            },
+            "cart": {
+                "items": "{{ count }} items",
+                "@items": { "placeholders": { "count": { "example": 3, "description": "number of items" } } }
+            }
+        }"#;
+        let locale = parse_json(json, &ParseOptions::default()).unwrap();
+        assert_eq!(locale.keys.len(), 2);
+        assert_eq!(
+            locale.metadata.get("checkout"),
+            Some(&KeyMetadata {
+                description: Some("button label, shown on checkout".into()),
+                screenshot: Some("https://example.com/checkout.png".into()),
+                max_length: Some(20),
+                ..Default::default()
+            })
+        );
+        let Some(ParsedValue::Subkeys(Some(cart))) = locale.keys.get("cart") else {
+            panic!("expected subkeys");
+        };
+        let placeholder = &cart.metadata["items"].placeholders["count"];
+        assert_eq!(placeholder.example.as_deref(), Some("3"));
+        assert_eq!(placeholder.description.as_deref(), Some("number of items"));
+
+        let mut docs = String::new();
+        locale.metadata["checkout"].write_docs(&mut docs).unwrap();
+        assert!(docs.starts_with("button label, shown on checkout\n"));
+        assert!(docs.contains("Max length: 20"));
+    }
+
+    #[test]
+    fn parse_deprecated_keys() {
+        let json = r#"{
+            "new_key": "New",
+            "old_key": "Old",
+            "@old_key": { "deprecated": "use new_key" },
+            "@renamed": { "deprecated": true, "alias": "new_key" }
+        }"#;
+        let locale = parse_json(json, &ParseOptions::default()).unwrap();
+        assert_eq!(
+            locale.metadata["old_key"].deprecated.as_deref(),
+            Some("use new_key")
+        );
+        assert_eq!(locale.metadata["renamed"].deprecated.as_deref(), Some(""));
+        let Some(ParsedValue::ForeignKey(foreign_key)) = locale.keys.get("renamed") else {
+            panic!("expected an alias");
+        };
+        let ForeignKey::NotSet(target, args) = &*foreign_key.borrow() else {
+            panic!("expected an unresolved foreign key");
+        };
+        assert_eq!(target.to_string(), "new_key");
+        assert!(args.is_empty());
+
+        let json = r#"{ "old_key": "Old", "@old_key": { "alias": "new_key" } }"#;
+        let err = parse_json(json, &ParseOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("alias but also has a value"));
+    }
+
+    fn find_single_brace_variable(ctx: &ParseContext, value: &str) -> Option<Result<ParsedValue>> {
+        let (before, rest) = value.split_once('{')?;
+        let (name, after) = rest.split_once('}')?;
+        let key = Key::new(&format!("var_{}", name.trim()))?;
+        let this = ParsedValue::Variable {
+            key,
+            bounds: VarBounds::None,
+            default: None,
+        };
+        let values = ParsedValue::new(ctx, before).and_then(|before| {
+            let after = ParsedValue::new(ctx, after)?;
+            Ok(vec![before, this, after])
+        });
+        Some(values.map(ParsedValue::Bloc))
+    }
+
+    #[test]
+    fn custom_parse_fns() {
+        let var = |name: &str| ParsedValue::Variable {
+            key: Key::new(&format!("var_{name}")).unwrap(),
+            bounds: VarBounds::None,
+            default: None,
+        };
+        let json = r#"{ "messages": "Hello {name}, you have {{ count }} messages" }"#;
+        let options = ParseOptions::default().add_parse_fn(find_single_brace_variable);
+        let mut locale = parse_json(json, &options).unwrap();
+        let value = locale.keys.get_mut("messages").unwrap();
+        value.reduce();
+        assert_eq!(
+            *value,
+            ParsedValue::Bloc(vec![
+                lit("Hello "),
+                var("name"),
+                lit(", you have "),
+                var("count"),
+                lit(" messages"),
+            ])
+        );
+
+        let options = ParseOptions::default().with_parse_fns(&[find_single_brace_variable]);
+        let json = r#"{ "messages": "<b>{name}</b>" }"#;
+        let mut locale = parse_json(json, &options).unwrap();
+        let value = locale.keys.get_mut("messages").unwrap();
+        value.reduce();
+        assert_eq!(
+            *value,
+            ParsedValue::Bloc(vec![lit("<b>"), var("name"), lit("</b>")])
+        );
+    }
+
+    #[test]
+    fn parse_fns_registration_order() {
+        fn first(_: &ParseContext, value: &str) -> Option<Result<ParsedValue>> {
+            value.starts_with('!').then(|| Ok(lit("first")))
+        }
+        fn second(_: &ParseContext, value: &str) -> Option<Result<ParsedValue>> {
+            value.starts_with('!').then(|| Ok(lit("second")))
+        }
+        let json = r#"{ "key": "!value" }"#;
+
+        let options = ParseOptions::default()
+            .add_parse_fn(first)
+            .add_parse_fn(second);
+        let locale = parse_json(json, &options).unwrap();
+        assert_eq!(locale.keys["key"], lit("first"));
+
+        let options = ParseOptions::default()
+            .with_parse_fns(&[second])
+            .add_parse_fn(first);
+        let locale = parse_json(json, &options).unwrap();
+        assert_eq!(locale.keys["key"], lit("second"));
+    }
+
+    #[test]
+    fn check_key_constraints() {
+        let json = r#"{
+            "short": "Checkout now",
+            "@short": { "maxLength": 10 },
+            "greeting": "Hello {{ name }}!",
+            "@greeting": { "maxLength": 11, "placeholders": { "name": { "maxLength": 4 } } },
+            "question": "Are you sure",
+            "@question": { "trailingPunctuation": "?", "forbiddenCharacters": "\"" },
+            "nested": {
+                "quote": "Say \"hi\"?",
+                "@quote": { "trailingPunctuation": "?", "forbiddenCharacters": "\"" }
+            }
+        }"#;
+        let options = ParseOptions::default().variable_length_allowance(20);
+        let locale = parse_json(json, &options).unwrap();
+        let diag = Diagnostics::new();
+        constraints::check_locales(&[&locale], &mut KeyPath::new(None), &diag, &options);
+        let warnings = diag
+            .warnings()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(warnings.len(), 3, "{warnings:?}");
+        assert!(warnings[0].contains("nested.quote") && warnings[0].contains("forbidden"));
+        assert!(warnings[1].contains("question") && warnings[1].contains("must end with"));
+        assert!(warnings[2].contains("short") && warnings[2].contains("12 characters long"));
+
+        let options = options.strict_constraints(true);
+        let diag = Diagnostics::new();
+        constraints::check_locales(&[&locale], &mut KeyPath::new(None), &diag, &options);
+        assert_eq!(diag.errors().len(), 3);
+        assert!(diag.warnings().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn check_missing_plural_forms() {
+        let json = r#"{
+            "items_one": "{{ count }} item",
+            "items_other": "{{ count }} items",
+            "place_ordinal_one": "{{ count }}st",
+            "place_ordinal_other": "{{ count }}th",
+            "nested": {
+                "guests": { "$plural": "count", "=1": "one guest", "other": "guests" }
+            }
+        }"#;
+        let options = ParseOptions::default();
+        let mut locale = parse_json(json, &options).unwrap();
+        let diag = Diagnostics::new();
+        plurals::check_locales(&[&locale], &mut KeyPath::new(None), &diag, &options).unwrap();
+        let warnings = diag
+            .warnings()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("place") && warnings[0].contains("\"_two\", \"_few\""));
+
+        // polish uses "few" and "many" for cardinal plurals
+        locale.top_locale_name = Key::new("pl").unwrap();
+        locale.keys.retain(|key, _| &*key.name == "items");
+        let options = options.strict_plural_forms(true);
+        let diag = Diagnostics::new();
+        plurals::check_locales(&[&locale], &mut KeyPath::new(None), &diag, &options).unwrap();
+        let errors = diag.errors();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            Error::MissingPluralForm { forms, .. } if *forms == [PluralForm::Few, PluralForm::Many]
+        ));
+        assert!(diag.warnings().is_empty());
+
+        // every category of the locale is checked, even the french "many" only used for millions
+        locale.top_locale_name = Key::new("fr").unwrap();
+        let diag = Diagnostics::new();
+        plurals::check_locales(&[&locale], &mut KeyPath::new(None), &diag, &options).unwrap();
+        let errors = diag.errors();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            Error::MissingPluralForm { forms, .. } if *forms == [PluralForm::Many]
+        ));
+    }
+
+    #[test]
+    fn locale_from_dir() {
+        let dir = std::env::temp_dir().join(format!("leptos_i18n_from_dir_{}", std::process::id()));
+        let locale_dir = dir.join("en");
+        std::fs::create_dir_all(locale_dir.join("settings")).unwrap();
+        std::fs::write(locale_dir.join("common.json"), r#"{ "hello": "Hello" }"#).unwrap();
+        std::fs::write(locale_dir.join("settings.json"), r#"{ "theme": "Theme" }"#).unwrap();
+        std::fs::write(
+            locale_dir.join("settings").join("profile.json"),
+            r#"{ "title": "Profile" }"#,
+        )
+        .unwrap();
+        std::fs::write(locale_dir.join("notes.txt"), "ignored").unwrap();
+
+        let parse = |tracked_files: &mut Vec<String>| {
+            Locale::from_dir(
+                &locale_dir,
+                Key::new("en").unwrap(),
+                &ForeignKeysPaths::new(),
+                &Diagnostics::new(),
+                tracked_files,
+                &ParseOptions::default(),
+            )
+        };
+        let mut tracked_files = vec![];
+        let locale = parse(&mut tracked_files).unwrap();
+        let get = |path: &str| {
+            let path = path
+                .split('.')
+                .map(|k| Key::new(k).unwrap())
+                .collect::<Vec<_>>();
+            locale.get_value_at(&path).cloned()
+        };
+        assert_eq!(get("common.hello"), Some(lit("Hello")));
+        assert_eq!(get("settings.theme"), Some(lit("Theme")));
+        assert_eq!(get("settings.profile.title"), Some(lit("Profile")));
+        assert_eq!(tracked_files.len(), 4);
+
+        std::fs::write(
+            locale_dir.join("settings.json"),
+            r#"{ "profile": "Profile" }"#,
+        )
+        .unwrap();
+        let err = parse(&mut vec![]).unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::DuplicateKeyInLocaleDir { .. }
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn find_file_mixed_formats() {
+        let dir =
+            std::env::temp_dir().join(format!("leptos_i18n_find_file_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("en.yaml"), "key: value").unwrap();
+        let options = ParseOptions::default().add_file_format(FileFormat::Yaml);
+
+        let mut path = dir.join("en");
+        let (_, file_format) = find_file(&mut path, &options).unwrap();
+        assert!(matches!(file_format, FileFormat::Yaml));
+        assert_eq!(path, dir.join("en.yaml"));
+
+        std::fs::write(dir.join("en.json"), r#"{ "key": "value" }"#).unwrap();
+        let err = find_file(&mut dir.join("en"), &options).unwrap_err();
+        assert!(matches!(
+            err.into_inner(),
+            Error::MultipleFilesFormats(paths) if paths.len() == 2
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,637 @@
+//! ICU MessageFormat support, enabled with `ParseOptions::icu_message_format`.
+//!
+//! Messages are rewritten to the `leptos_i18n` syntax: simple arguments such as `{name}` or `{n, number}`
+//! become variables, and the constructs that can't be expressed as text (plurals, selects and quoted literals)
+//! are replaced by placeholder variables, substituted back once the rewritten text is parsed.
+//! This way components can still wrap ICU arguments, e.g. `<b>{count, plural, one {# item} other {# items}}</b>`.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write},
+};
+
+use super::{
+    error::{Error, Result, Warning},
+    parsed_value::{Literal, ParsedValue},
+    plurals::{PluralForm, PluralRuleType, Plurals},
+    select::Select,
+};
+use crate::utils::{Key, ParseContext};
+
+/// Prefix of the placeholder variables, `{{ __icu_0 }}` is parsed as the key `var___icu_0`.
+const PLACEHOLDER_PREFIX: &str = "__icu_";
+
+/// A message rewritten to the `leptos_i18n` syntax, with the values that can't be expressed in it.
+#[derive(Default)]
+struct Message {
+    text: String,
+    substitutions: Vec<ParsedValue>,
+}
+
+impl Message {
+    fn push_substitution(&mut self, value: ParsedValue) {
+        let _ = write!(
+            self.text,
+            "{{{{ {PLACEHOLDER_PREFIX}{} }}}}",
+            self.substitutions.len()
+        );
+        self.substitutions.push(value);
+    }
+}
+
+struct MessageParser<'a> {
+    ctx: &'a ParseContext<'a>,
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> MessageParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c: char| !f(c)).unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn error<T>(&self, message: impl Display) -> Result<T> {
+        let column = self.src[..self.pos].chars().count() + 1;
+        Err(Error::InvalidIcuMessage {
+            loc: self.ctx.into(),
+            message: format!("{message} (column {column})"),
+        }
+        .into())
+    }
+
+    fn unsupported<T>(&self, construct: impl ToString) -> Result<T> {
+        Err(Error::UnsupportedIcuConstruct {
+            loc: self.ctx.into(),
+            construct: construct.to_string(),
+        }
+        .into())
+    }
+
+    /// Copy `leptos_i18n` constructs containing braces as is: `{{ var }}` and `$t(key, { "arg": "value" })`.
+    fn copy_through(&mut self, message: &mut Message) -> bool {
+        let rest = self.rest();
+        let len = if rest.starts_with("{{") {
+            rest.find("}}").map_or(rest.len(), |end| end + 2)
+        } else if rest.starts_with("$t(") {
+            let mut depth = 0usize;
+            rest.char_indices()
+                .find(|&(_, c)| match c {
+                    '{' => {
+                        depth += 1;
+                        false
+                    }
+                    '}' => {
+                        depth = depth.saturating_sub(1);
+                        false
+                    }
+                    ')' => depth == 0,
+                    _ => false,
+                })
+                .map_or(rest.len(), |(end, _)| end + 1)
+        } else {
+            return false;
+        };
+        message.text.push_str(&rest[..len]);
+        self.pos += len;
+        true
+    }
+
+    /// Handle an apostrophe: `''` is a literal apostrophe, and an apostrophe before a special character starts quoted text.
+    fn quoted(&mut self, message: &mut Message, in_plural: bool) {
+        self.pos += 1;
+        match self.peek() {
+            Some('\'') => {
+                self.pos += 1;
+                message.text.push('\'');
+                return;
+            }
+            Some('{' | '}' | '<') => {}
+            Some('#') if in_plural => {}
+            _ => {
+                message.text.push('\'');
+                return;
+            }
+        }
+        let mut literal = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+            if c == '\'' {
+                if !self.eat('\'') {
+                    break;
+                }
+                literal.push('\'');
+            } else {
+                literal.push(c);
+            }
+        }
+        message.push_substitution(ParsedValue::Literal(Literal::String(literal, usize::MAX)));
+    }
+
+    /// Parse a message until the end of the input or, when `nested`, until the closing brace of the enclosing argument.
+    fn message(&mut self, plural_arg: Option<&str>, nested: bool) -> Result<ParsedValue> {
+        let mut message = Message::default();
+        while let Some(c) = self.peek() {
+            match (c, plural_arg) {
+                ('\'', _) => self.quoted(&mut message, plural_arg.is_some()),
+                ('{' | '$', _) if self.copy_through(&mut message) => {}
+                ('{', _) => {
+                    self.pos += 1;
+                    self.argument(&mut message)?;
+                }
+                ('}', _) if nested => break,
+                ('#', Some(arg)) => {
+                    self.pos += 1;
+                    let _ = write!(message.text, "{{{{ {arg} }}}}");
+                }
+                (c, _) => {
+                    self.pos += c.len_utf8();
+                    message.text.push(c);
+                }
+            }
+        }
+        self.finish(message)
+    }
+
+    fn finish(&self, message: Message) -> Result<ParsedValue> {
+        let ctx = ParseContext {
+            parse_fns: ParsedValue::DEFAULT_FNS,
+            ..*self.ctx
+        };
+        let mut value = ParsedValue::new(&ctx, &message.text)?;
+        if message.substitutions.is_empty() {
+            return Ok(value);
+        }
+        let mut substitutions: Vec<_> = message.substitutions.into_iter().map(Some).collect();
+        substitute(&mut value, &mut substitutions);
+        if substitutions.iter().any(Option::is_some) {
+            return Err(Error::InvalidIcuMessage {
+                loc: self.ctx.into(),
+                message: "plurals and quoted text can't be used inside component attributes"
+                    .to_string(),
+            }
+            .into());
+        }
+        Ok(value)
+    }
+
+    /// Parse an argument, the opening brace being already consumed.
+    fn argument(&mut self, message: &mut Message) -> Result<()> {
+        self.skip_whitespace();
+        let name = self.take_while(|c| !c.is_whitespace() && !matches!(c, ',' | '{' | '}'));
+        if Key::new(&format!("var_{name}")).is_none() {
+            return self.error(format!("invalid argument name {name:?}"));
+        }
+        self.skip_whitespace();
+        if self.eat('}') {
+            let _ = write!(message.text, "{{{{ {name} }}}}");
+            return Ok(());
+        }
+        if !self.eat(',') {
+            return self.error("expected \",\" or \"}\" after the argument name");
+        }
+        self.skip_whitespace();
+        let arg_type = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        self.skip_whitespace();
+        match arg_type {
+            "number" | "date" | "time" => {
+                let style = if self.eat(',') {
+                    self.take_while(|c| c != '}').trim()
+                } else {
+                    ""
+                };
+                if !self.eat('}') {
+                    return self.error("unclosed argument");
+                }
+                let formatter = self.formatter(arg_type, style);
+                let _ = write!(message.text, "{{{{ {name}, {formatter} }}}}");
+                Ok(())
+            }
+            "plural" | "selectordinal" => {
+                if !self.eat(',') {
+                    return self.error(format!("expected \",\" after \"{arg_type}\""));
+                }
+                self.skip_whitespace();
+                let offset = if self.rest().starts_with("offset:") {
+                    self.pos += "offset:".len();
+                    self.skip_whitespace();
+                    let offset = self.take_while(|c| c.is_ascii_digit());
+                    let Ok(offset) = offset.parse() else {
+                        return self.error(format!("invalid plural offset {offset:?}"));
+                    };
+                    offset
+                } else {
+                    0
+                };
+                let rule_type = if arg_type == "plural" {
+                    PluralRuleType::Cardinal
+                } else {
+                    PluralRuleType::Ordinal
+                };
+                let plurals = self.plural(name, rule_type, offset)?;
+                message.push_substitution(plurals);
+                Ok(())
+            }
+            "select" => {
+                if !self.eat(',') {
+                    return self.error("expected \",\" after \"select\"");
+                }
+                self.skip_whitespace();
+                let select = self.select(name)?;
+                message.push_substitution(select);
+                Ok(())
+            }
+            "" => self.error("expected an argument type"),
+            other => self.unsupported(format!("argument type {other:?}")),
+        }
+    }
+
+    /// Translate a number, date or time argument style to formatter arguments.
+    fn formatter(&self, arg_type: &str, style: &str) -> String {
+        match (arg_type, style) {
+            (_, "") => arg_type.to_string(),
+            ("date" | "time", "short" | "medium" | "long") => {
+                format!("{arg_type}(length: {style})")
+            }
+            ("date" | "time", "full") => format!("{arg_type}(length: long)"),
+            _ => {
+                self.ctx.diag.emit_warning(Warning::IgnoredIcuConstruct {
+                    loc: self.ctx.into(),
+                    construct: format!("{arg_type} style {style:?}"),
+                });
+                arg_type.to_string()
+            }
+        }
+    }
+
+    /// Parse the `selector {message}` branches of a plural or select argument, up to and including its closing brace.
+    fn branches(&mut self, plural_arg: Option<&str>) -> Result<Vec<(&'a str, ParsedValue)>> {
+        let mut branches = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.eat('}') {
+                return Ok(branches);
+            }
+            if self.peek().is_none() {
+                return self.error("unclosed argument");
+            }
+            let selector = self.take_while(|c| !c.is_whitespace() && !matches!(c, '{' | '}'));
+            if selector.is_empty() {
+                return self.error("expected a selector");
+            }
+            self.skip_whitespace();
+            if !self.eat('{') {
+                return self.error(format!("expected a message after selector {selector:?}"));
+            }
+            let value = self.message(plural_arg, true)?;
+            if !self.eat('}') {
+                return self.error(format!("unclosed message for selector {selector:?}"));
+            }
+            if branches.iter().any(|(s, _)| *s == selector) {
+                return self.error(format!("duplicate selector {selector:?}"));
+            }
+            branches.push((selector, value));
+        }
+    }
+
+    fn plural(
+        &mut self,
+        name: &str,
+        rule_type: PluralRuleType,
+        offset: u64,
+    ) -> Result<ParsedValue> {
+        let branches = self.branches(Some(name))?;
+        let mut forms = BTreeMap::new();
+        for (selector, value) in branches {
+            let Some(form) = PluralForm::try_from_str(selector) else {
+                return self.error(format!("unknown plural category {selector:?}"));
+            };
+            forms.insert(form, value);
+        }
+        let Some(other) = forms.remove(&PluralForm::Other) else {
+            return self.error("missing \"other\" selector");
+        };
+
+        if !cfg!(feature = "plurals") {
+            return Err(Error::DisabledPlurals {
+                loc: self.ctx.into(),
+            }
+            .into());
+        }
+
+        let plurals = Plurals {
+            rule_type,
+            count_key: Key::new(&format!("var_{name}"))
+                .expect("argument names have already been checked"),
+            other: Box::new(other),
+            forms,
+            offset,
+        };
+        plurals.check_forms(&self.ctx.loc, self.ctx.diag)?;
+        Ok(ParsedValue::Plurals(plurals))
+    }
+
+    fn select(&mut self, name: &str) -> Result<ParsedValue> {
+        let branches = self.branches(None)?;
+        let mut variants = BTreeMap::new();
+        for (selector, value) in branches {
+            let Some(variant) = Key::new(selector) else {
+                return self.error(format!("invalid select variant {selector:?}"));
+            };
+            variants.insert(variant, value);
+        }
+        let select_key =
+            Key::new(&format!("var_{name}")).expect("argument names have already been checked");
+        Select::new(select_key, variants, &self.ctx.loc).map(ParsedValue::Select)
+    }
+}
+
+/// Replace the placeholder variables with their values.
+fn substitute(value: &mut ParsedValue, substitutions: &mut [Option<ParsedValue>]) {
+    match value {
+        ParsedValue::Variable { key, .. } => {
+            let index = key
+                .name
+                .strip_prefix("var_")
+                .and_then(|name| name.strip_prefix(PLACEHOLDER_PREFIX))
+                .and_then(|index| index.parse::<usize>().ok());
+            if let Some(substitution) = index
+                .and_then(|index| substitutions.get_mut(index))
+                .and_then(Option::take)
+            {
+                *value = substitution;
+            }
+        }
+        ParsedValue::Bloc(values) => {
+            for value in values {
+                substitute(value, substitutions);
+            }
+        }
+        ParsedValue::Component {
+            inner: Some(inner), ..
+        } => substitute(inner, substitutions),
+        _ => {}
+    }
+}
+
+/// Parse a string value as an ICU MessageFormat message.
+pub fn parse(ctx: &ParseContext, value: &str) -> Result<ParsedValue> {
+    let mut parser = MessageParser {
+        ctx,
+        src: value,
+        pos: 0,
+    };
+    parser.message(None, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formatters::{Formatters, VarBounds},
+        parse_locales::{ForeignKeysPaths, error::Diagnostics, options::KeyMapping},
+        utils::{KeyPath, Loc},
+    };
+
+    fn parse_message(value: &str) -> (Result<ParsedValue>, Diagnostics) {
+        let key_path = KeyPath::new(None);
+        let locale = Key::new("en").unwrap();
+        let foreign_keys_paths = ForeignKeysPaths::new();
+        let diag = Diagnostics::new();
+        let formatters = Formatters::new();
+        let ctx = ParseContext {
+            loc: Loc {
+                key_path: &key_path,
+                locale: &locale,
+            },
+            foreign_keys_paths: &foreign_keys_paths,
+            diag: &diag,
+            formatters: &formatters,
+            parse_fns: ParsedValue::ICU_FNS,
+            key_mapping: &KeyMapping::new(),
+        };
+        let value = ParsedValue::new(&ctx, value).map(|mut value| {
+            value.reduce();
+            value
+        });
+        (value, diag)
+    }
+
+    fn parse_ok(value: &str) -> ParsedValue {
+        let (value, diag) = parse_message(value);
+        assert!(diag.errors().is_empty());
+        assert!(diag.warnings().is_empty());
+        value.unwrap()
+    }
+
+    fn parse_err(value: &str) -> Error {
+        parse_message(value).0.unwrap_err().into_inner()
+    }
+
+    fn lit(s: &str) -> ParsedValue {
+        ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX))
+    }
+
+    fn var(name: &str) -> ParsedValue {
+        ParsedValue::Variable {
+            key: Key::new(&format!("var_{name}")).unwrap(),
+            bounds: VarBounds::None,
+            default: None,
+        }
+    }
+
+    #[test]
+    fn parse_arguments() {
+        let value = parse_ok("Hello {name}, {{ other }} and {{ name }}");
+        assert_eq!(
+            value,
+            ParsedValue::Bloc(vec![
+                lit("Hello "),
+                var("name"),
+                lit(", "),
+                var("other"),
+                lit(" and "),
+                var("name"),
+            ])
+        );
+
+        #[cfg(feature = "format_nums")]
+        {
+            let ParsedValue::Bloc(values) = parse_ok("{n, number} points") else {
+                panic!("expected a bloc");
+            };
+            assert!(matches!(
+                &values[0],
+                ParsedValue::Variable {
+                    bounds: VarBounds::Formatted { formatter_name, .. },
+                    ..
+                } if *formatter_name == "number"
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_quotes() {
+        assert_eq!(
+            parse_ok("It''s '{name}' and 'quoted'"),
+            lit("It's {name} and 'quoted'")
+        );
+        assert_eq!(parse_ok("'<b>'"), lit("<b>"));
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn parse_plural() {
+        let value = parse_ok("{count, plural, one {# item} other {# items}}");
+        let expected = ParsedValue::Plurals(Plurals {
+            rule_type: PluralRuleType::Cardinal,
+            count_key: Key::new("var_count").unwrap(),
+            other: Box::new(ParsedValue::Bloc(vec![var("count"), lit(" items")])),
+            forms: BTreeMap::from([(
+                PluralForm::One,
+                ParsedValue::Bloc(vec![var("count"), lit(" item")]),
+            )]),
+            offset: 0,
+        });
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn parse_plural_exact_and_offset() {
+        let value =
+            parse_ok("{count, plural, offset:1 =0 {nobody} =1 {you} other {you and # others}}");
+        let expected = ParsedValue::Plurals(Plurals {
+            rule_type: PluralRuleType::Cardinal,
+            count_key: Key::new("var_count").unwrap(),
+            other: Box::new(ParsedValue::Bloc(vec![
+                lit("you and "),
+                var("count"),
+                lit(" others"),
+            ])),
+            forms: BTreeMap::from([
+                (PluralForm::Exact(0), lit("nobody")),
+                (PluralForm::Exact(1), lit("you")),
+            ]),
+            offset: 1,
+        });
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn parse_selectordinal_in_component() {
+        use crate::parse_locales::parsed_value::Attributes;
+
+        let value =
+            parse_ok("<b>{n, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}</b>!");
+        let ParsedValue::Bloc(values) = value else {
+            panic!("expected a bloc");
+        };
+        let [
+            ParsedValue::Component {
+                key,
+                inner: Some(inner),
+                attributes,
+            },
+            rest,
+        ] = values.as_slice()
+        else {
+            panic!("expected a component");
+        };
+        assert_eq!(key, &Key::new("comp_b").unwrap());
+        assert_eq!(attributes, &Attributes::default());
+        assert_eq!(rest, &lit("!"));
+        let ParsedValue::Plurals(plurals) = &**inner else {
+            panic!("expected plurals");
+        };
+        assert_eq!(plurals.rule_type, PluralRuleType::Ordinal);
+        assert_eq!(plurals.count_key, Key::new("var_n").unwrap());
+        assert_eq!(plurals.forms.len(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn parse_nested_plural() {
+        let value =
+            parse_ok("{a, plural, one {{b, plural, one {# b} other {# bs}} and # a} other {# as}}");
+        let ParsedValue::Plurals(outer) = value else {
+            panic!("expected plurals");
+        };
+        let ParsedValue::Bloc(one) = &outer.forms[&PluralForm::One] else {
+            panic!("expected a bloc");
+        };
+        let ParsedValue::Plurals(inner) = &one[0] else {
+            panic!("expected plurals");
+        };
+        assert_eq!(inner.count_key, Key::new("var_b").unwrap());
+        assert_eq!(one[1..], [lit(" and "), var("a"), lit(" a")]);
+    }
+
+    #[test]
+    fn parse_select() {
+        let value =
+            parse_ok("{gender, select, male {He} female {She} other {They}} invited {name}");
+        let expected = ParsedValue::Bloc(vec![
+            ParsedValue::Select(Select {
+                select_key: Key::new("var_gender").unwrap(),
+                other: Box::new(lit("They")),
+                variants: BTreeMap::from([
+                    (Key::new("female").unwrap(), lit("She")),
+                    (Key::new("male").unwrap(), lit("He")),
+                ]),
+            }),
+            lit(" invited "),
+            var("name"),
+        ]);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            parse_err("{count, plural, one {# item}}"),
+            Error::InvalidIcuMessage { .. }
+        ));
+        assert!(matches!(
+            parse_err("{count, plural, one {# item} other {# items}"),
+            Error::InvalidIcuMessage { .. }
+        ));
+        assert!(matches!(
+            parse_err("{count, plural, offset:x =0 {none} other {# items}}"),
+            Error::InvalidIcuMessage { .. }
+        ));
+        assert!(matches!(
+            parse_err("{count, spellout}"),
+            Error::UnsupportedIcuConstruct { .. }
+        ));
+        assert!(matches!(
+            parse_err("{gender, select, male {He} female {She}}"),
+            Error::MissingSelectFallback { .. }
+        ));
+    }
+}
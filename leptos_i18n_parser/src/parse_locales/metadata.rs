@@ -0,0 +1,338 @@
+use std::collections::BTreeMap;
+
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+
+use super::{
+    error::{Error, Result},
+    locale::Locale,
+    parsed_value::ParsedValue,
+};
+use crate::utils::{Key, Loc, ParseContext};
+
+/// Prefix of the entries holding the metadata of a key, ARB style:
+/// `{ "checkout": "Checkout", "@checkout": { "description": "button label", "maxLength": 20 } }`
+pub const METADATA_PREFIX: char = '@';
+
+/// Context given to translators about a key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyMetadata {
+    pub description: Option<String>,
+    pub screenshot: Option<String>,
+    pub max_length: Option<u64>,
+    pub placeholders: BTreeMap<String, PlaceholderMetadata>,
+    /// Deprecation note of the key, empty for a deprecated key without a note.
+    pub deprecated: Option<String>,
+    /// Path of the key this key is an alias of.
+    pub alias: Option<String>,
+    /// Characters the value must not contain.
+    pub forbidden_characters: Option<String>,
+    /// The value must end with one of these characters.
+    pub trailing_punctuation: Option<String>,
+}
+
+/// Context given to translators about a variable or a component of a key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlaceholderMetadata {
+    pub description: Option<String>,
+    pub example: Option<String>,
+    /// Length counted for the placeholder when checking the max length of the key.
+    pub max_length: Option<u64>,
+}
+
+impl KeyMetadata {
+    /// The metadata of a key, from the first locale giving one, starting with the default locale.
+    pub fn find<'a>(locales: &'a [Locale], key: &Key) -> Option<&'a KeyMetadata> {
+        locales.iter().find_map(|locale| locale.metadata.get(key))
+    }
+
+    /// Write the metadata as markdown, used for the generated docs.
+    pub fn write_docs(&self, docs: &mut String) -> core::fmt::Result {
+        use core::fmt::Write;
+        if let Some(description) = &self.description {
+            writeln!(docs, "{description}\n")?;
+        }
+        if let Some(alias) = &self.alias {
+            writeln!(docs, "Alias of `{alias}`\n")?;
+        }
+        if let Some(max_length) = self.max_length {
+            writeln!(docs, "Max length: {max_length}\n")?;
+        }
+        if let Some(screenshot) = &self.screenshot {
+            writeln!(docs, "Screenshot: <{screenshot}>\n")?;
+        }
+        if self.placeholders.is_empty() {
+            return Ok(());
+        }
+        writeln!(docs, "## Placeholders :")?;
+        for (name, placeholder) in &self.placeholders {
+            write!(docs, "- `{name}`")?;
+            if let Some(description) = &placeholder.description {
+                write!(docs, ": {description}")?;
+            }
+            if let Some(example) = &placeholder.example {
+                write!(docs, " (e.g. `{example}`)")?;
+            }
+            writeln!(docs)?;
+        }
+        writeln!(docs)
+    }
+}
+
+/// Read the value of an `@key` entry, `None` for ARB global attributes such as `@@locale`.
+pub fn next_metadata<'de, A>(map: &mut A, target: &str) -> Result<Option<KeyMetadata>, A::Error>
+where
+    A: MapAccess<'de>,
+{
+    if target.starts_with(METADATA_PREFIX) {
+        map.next_value::<IgnoredAny>()?;
+        Ok(None)
+    } else {
+        map.next_value().map(Some)
+    }
+}
+
+/// Declare the keys that are an alias of another key, `{ "@old_key": { "alias": "new_key" } }`,
+/// the `key_path` of `ctx` being the path of `keys`.
+pub fn insert_aliases(
+    keys: &mut BTreeMap<Key, ParsedValue>,
+    keys_metadata: &BTreeMap<Key, KeyMetadata>,
+    ctx: &ParseContext,
+) -> Result<()> {
+    for (key, metadata) in keys_metadata {
+        let Some(alias) = &metadata.alias else {
+            continue;
+        };
+        let mut key_path = ctx.loc.key_path.clone();
+        key_path.path.push(key.clone());
+        let ctx = ParseContext {
+            loc: Loc {
+                key_path: &key_path,
+                locale: ctx.loc.locale,
+            },
+            ..*ctx
+        };
+        if keys.contains_key(key) {
+            return Err(Error::AliasWithValue {
+                loc: ctx.loc.into(),
+            }
+            .into());
+        }
+        keys.insert(key.clone(), ParsedValue::new_alias(&ctx, alias)?);
+    }
+    Ok(())
+}
+
+fn deser_field<'de, A, T>(
+    option: &mut Option<T>,
+    map: &mut A,
+    field_name: &'static str,
+) -> Result<(), A::Error>
+where
+    A: MapAccess<'de>,
+    T: serde::Deserialize<'de>,
+{
+    if option.replace(map.next_value()?).is_some() {
+        Err(serde::de::Error::duplicate_field(field_name))
+    } else {
+        Ok(())
+    }
+}
+
+struct KeyMetadataVisitor;
+
+impl<'de> serde::Deserialize<'de> for KeyMetadata {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(KeyMetadataVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for KeyMetadataVisitor {
+    type Value = KeyMetadata;
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut metadata = KeyMetadata::default();
+        let mut placeholders = None;
+        while let Some(field) = map.next_key::<String>()? {
+            match field.as_str() {
+                "description" => deser_field(&mut metadata.description, &mut map, "description")?,
+                "screenshot" => deser_field(&mut metadata.screenshot, &mut map, "screenshot")?,
+                "maxLength" | "max_length" => {
+                    deser_field(&mut metadata.max_length, &mut map, "maxLength")?
+                }
+                "placeholders" => deser_field(&mut placeholders, &mut map, "placeholders")?,
+                "deprecated" => {
+                    let mut deprecated: Option<Deprecated> = None;
+                    deser_field(&mut deprecated, &mut map, "deprecated")?;
+                    metadata.deprecated = deprecated.and_then(|Deprecated(note)| note);
+                }
+                "alias" => deser_field(&mut metadata.alias, &mut map, "alias")?,
+                "forbiddenCharacters" | "forbidden_characters" => deser_field(
+                    &mut metadata.forbidden_characters,
+                    &mut map,
+                    "forbiddenCharacters",
+                )?,
+                "trailingPunctuation" | "trailing_punctuation" => deser_field(
+                    &mut metadata.trailing_punctuation,
+                    &mut map,
+                    "trailingPunctuation",
+                )?,
+                // other ARB attributes such as "type" or "context"
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        metadata.placeholders = placeholders.unwrap_or_default();
+        Ok(metadata)
+    }
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "a map with the metadata of a key (\"description\", \"screenshot\", \"maxLength\", \"placeholders\", \"deprecated\", \"alias\", \"forbiddenCharacters\", \"trailingPunctuation\")"
+        )
+    }
+}
+
+struct PlaceholderMetadataVisitor;
+
+impl<'de> serde::Deserialize<'de> for PlaceholderMetadata {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(PlaceholderMetadataVisitor)
+    }
+}
+
+impl<'de> Visitor<'de> for PlaceholderMetadataVisitor {
+    type Value = PlaceholderMetadata;
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut metadata = PlaceholderMetadata::default();
+        let mut example: Option<Scalar> = None;
+        while let Some(field) = map.next_key::<String>()? {
+            match field.as_str() {
+                "description" => deser_field(&mut metadata.description, &mut map, "description")?,
+                "example" => deser_field(&mut example, &mut map, "example")?,
+                "maxLength" | "max_length" => {
+                    deser_field(&mut metadata.max_length, &mut map, "maxLength")?
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        metadata.example = example.map(|Scalar(example)| example);
+        Ok(metadata)
+    }
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "a map with the metadata of a placeholder (\"description\", \"example\", \"maxLength\")"
+        )
+    }
+}
+
+/// A deprecation note, or a boolean for a deprecated key without a note.
+struct Deprecated(Option<String>);
+
+struct DeprecatedVisitor;
+
+impl<'de> serde::Deserialize<'de> for Deprecated {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DeprecatedVisitor)
+    }
+}
+
+impl Visitor<'_> for DeprecatedVisitor {
+    type Value = Deprecated;
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Deprecated(Some(v.to_owned())))
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Deprecated(v.then(String::new)))
+    }
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a deprecation note or a boolean")
+    }
+}
+
+/// Examples can be given as strings, numbers or booleans.
+struct Scalar(String);
+
+struct ScalarVisitor;
+
+impl<'de> serde::Deserialize<'de> for Scalar {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ScalarVisitor)
+    }
+}
+
+impl Visitor<'_> for ScalarVisitor {
+    type Value = Scalar;
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Scalar(v.to_owned()))
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Scalar(v.to_string()))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Scalar(v.to_string()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Scalar(v.to_string()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Scalar(v.to_string()))
+    }
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "a string, a number or a boolean")
+    }
+}
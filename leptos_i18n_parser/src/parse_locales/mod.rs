@@ -9,15 +9,25 @@ use icu_locale::LanguageIdentifier;
 use locale::{BuildersKeys, BuildersKeysInner, DefaultTo, Locale, LocalesOrNamespaces};
 
 pub mod cfg_file;
+pub mod condition;
+pub mod constraints;
 pub mod error;
+pub mod fluent;
 pub mod locale;
+pub mod message_format;
+pub mod metadata;
 pub mod options;
+pub mod overlay;
 pub mod parsed_value;
 pub mod plurals;
+pub mod po;
 pub mod ranges;
+pub mod select;
+pub mod table;
 // pub mod warning;
 
 use error::{Diagnostics, Error, Result};
+use overlay::OverlayChecks;
 // use warning::Warnings;
 
 use crate::{
@@ -48,13 +58,14 @@ pub struct RawParsedLocales {
     pub foreign_keys_paths: ForeignKeysPaths,
     pub diag: Diagnostics,
     pub tracked_files: Vec<String>,
+    pub overlay_checks: OverlayChecks,
 }
 
 pub fn parse_locales_raw(
     cargo_manifest_dir: Option<PathBuf>,
     cfg: Config,
 ) -> Result<RawParsedLocales> {
-    let mut cargo_manifest_dir = unwrap_manifest_dir(cargo_manifest_dir)?;
+    let cargo_manifest_dir = unwrap_manifest_dir(cargo_manifest_dir)?;
 
     let foreign_keys_paths = ForeignKeysPaths::new();
 
@@ -64,8 +75,17 @@ pub fn parse_locales_raw(
 
     let mut tracked_files = Vec::with_capacity(cfg.locales.len() * cfg.namespaces.len().max(1));
 
-    let locales = LocalesOrNamespaces::new(
-        &mut cargo_manifest_dir,
+    let mut locales = LocalesOrNamespaces::new(
+        &mut cargo_manifest_dir.clone(),
+        &foreign_keys_paths,
+        &diag,
+        &mut tracked_files,
+        &cfg,
+    )?;
+
+    let overlay_checks = overlay::apply_overlays(
+        &mut locales,
+        &cargo_manifest_dir,
         &foreign_keys_paths,
         &diag,
         &mut tracked_files,
@@ -78,6 +98,7 @@ pub fn parse_locales_raw(
         foreign_keys_paths,
         diag,
         tracked_files,
+        overlay_checks,
     };
 
     Ok(raw_parsed_locales)
@@ -87,17 +108,24 @@ pub fn make_builder_keys(
     mut locales: LocalesOrNamespaces,
     cfg: &Config,
     foreign_keys_paths: ForeignKeysPaths,
+    overlay_checks: OverlayChecks,
     diag: &Diagnostics,
 ) -> Result<BuildersKeys> {
-    locales.merge_plurals(diag)?;
+    let builder_keys = || {
+        locales.merge_plurals(diag, &cfg.options)?;
 
-    resolve_foreign_keys(
-        &locales,
-        &cfg.default_locale,
-        foreign_keys_paths.into_inner(),
-    )?;
+        resolve_foreign_keys(
+            &locales,
+            &cfg.default_locale,
+            foreign_keys_paths.into_inner(),
+        )?;
+
+        overlay_checks.check(&locales, &cfg.default_locale)?;
 
-    check_locales(locales, &cfg.extensions, diag, &cfg.options)
+        check_locales(locales, &cfg.extensions, diag, &cfg.options)
+    };
+    // errors on overridden values name the overlay they come from.
+    builder_keys().map_err(|err| diag.with_overlay_origin(err.into_inner()).into())
 }
 
 pub struct ParsedLocales {
@@ -114,9 +142,10 @@ pub fn parse_locales(cargo_manifest_dir: Option<PathBuf>, cfg: Config) -> Result
         foreign_keys_paths,
         tracked_files,
         diag,
+        overlay_checks,
     } = parse_locales_raw(cargo_manifest_dir, cfg)?;
 
-    let builder_keys = make_builder_keys(locales, &cfg, foreign_keys_paths, &diag)?;
+    let builder_keys = make_builder_keys(locales, &cfg, foreign_keys_paths, overlay_checks, &diag)?;
 
     Ok(ParsedLocales {
         cfg,
@@ -267,9 +296,14 @@ fn check_locales_inner(
     options: &ParseOptions,
 ) -> Result<BuildersKeysInner> {
     let icu_locales = locales_to_icu(locales)?;
+    let mut key_path = KeyPath::new(namespace);
+
+    let locales_refs = locales.iter().collect::<Vec<_>>();
+    constraints::check_locales(&locales_refs, &mut key_path, diag, options);
+    plurals::check_locales(&locales_refs, &mut key_path, diag, options)?;
+
     let (default_locale, other_locales) =
         locales.split_first_mut().unwrap_at("check_locales_inner_1");
-    let mut key_path = KeyPath::new(namespace);
 
     let mut string_indexer = StringIndexer::default();
     let mut default_keys = default_locale.make_builder_keys(&mut key_path, &mut string_indexer)?;
@@ -4,9 +4,12 @@ use crate::{
     parse_locales::{
         cfg_file::DEFAULT_LOCALES_PATH,
         error::Result,
+        fluent,
         locale::{Locale, LocaleSeed, SerdeError},
+        parsed_value::ParsedValue,
+        po,
     },
-    utils::Key,
+    utils::{Key, ParseFn},
 };
 use parser::Parser;
 use std::{
@@ -25,6 +28,8 @@ pub struct Config {
     pub default_locale: Key,
     pub locales: Vec<Key>,
     pub locales_path: Cow<'static, Path>,
+    pub translations_table: Option<Cow<'static, Path>>,
+    pub overlay_paths: Vec<Cow<'static, Path>>,
     pub namespaces: Vec<Key>,
     pub translations_uri: Option<Cow<'static, str>>,
     pub extensions: BTreeMap<Key, Key>,
@@ -35,12 +40,118 @@ pub struct Config {
 #[non_exhaustive]
 pub struct ParseOptions {
     pub file_format: FileFormat,
+    pub additional_file_formats: Vec<FileFormat>,
     pub suppress_key_warnings: bool,
     pub interpolate_display: bool,
     pub show_keys_only: bool,
+    pub icu_message_format: bool,
+    pub select_suffix_var: Option<Key>,
+    pub key_mapping: KeyMapping,
+    pub strict_constraints: bool,
+    pub strict_plural_forms: bool,
+    pub variable_length_allowance: usize,
+    pub custom_parse_fns: Vec<ParseFn>,
+    pub default_parse_fns: bool,
     pub formatters: Formatters,
 }
 
+/// How translation keys that are not valid rust identifiers are turned into one.
+///
+/// `-` is always replaced by `_`, the other conversions are opt-in.
+/// Diagnostics keep using the original key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KeyMapping {
+    pub raw_keywords: bool,
+    pub snake_case: bool,
+    pub sanitize: bool,
+}
+
+impl KeyMapping {
+    pub const fn new() -> Self {
+        KeyMapping {
+            raw_keywords: false,
+            snake_case: false,
+            sanitize: false,
+        }
+    }
+
+    /// Enable every conversion.
+    pub const fn all() -> Self {
+        KeyMapping {
+            raw_keywords: true,
+            snake_case: true,
+            sanitize: true,
+        }
+    }
+
+    /// Use raw identifiers for keywords: `type` becomes `r#type`.
+    pub fn raw_keywords(self, raw_keywords: bool) -> Self {
+        Self {
+            raw_keywords,
+            ..self
+        }
+    }
+
+    /// Convert camelCase keys to snake_case: `userName` becomes `user_name`.
+    pub fn snake_case(self, snake_case: bool) -> Self {
+        Self { snake_case, ..self }
+    }
+
+    /// Replace the characters that can't be used in an identifier by `_`,
+    /// and prefix keys starting with a digit with `_`: `user.name` becomes `user_name` and `2fa_title` becomes `_2fa_title`.
+    pub fn sanitize(self, sanitize: bool) -> Self {
+        Self { sanitize, ..self }
+    }
+
+    /// The identifier for the given key.
+    pub fn map(&self, name: &str) -> String {
+        let mut ident = name.replace('-', "_");
+        if self.sanitize {
+            ident = ident
+                .chars()
+                .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                .collect();
+            if ident.starts_with(|c: char| c.is_ascii_digit()) {
+                ident.insert(0, '_');
+            }
+        }
+        if self.snake_case {
+            ident = to_snake_case(&ident);
+        }
+        if self.raw_keywords
+            && syn::parse_str::<syn::Ident>(&ident).is_err()
+            && syn::parse_str::<syn::Ident>(&format!("r#{ident}")).is_ok()
+        {
+            ident.insert_str(0, "r#");
+        }
+        ident
+    }
+}
+
+fn to_snake_case(s: &str) -> String {
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(s.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let boundary = match prev {
+                Some(prev) if prev.is_lowercase() || prev.is_ascii_digit() => true,
+                Some(prev) if prev.is_uppercase() => next.is_some_and(|c| c.is_lowercase()),
+                _ => false,
+            };
+            if boundary && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 #[derive(Clone, Default)]
 #[non_exhaustive]
 pub enum FileFormat {
@@ -49,6 +160,8 @@ pub enum FileFormat {
     Json5,
     Yaml,
     Toml,
+    Fluent,
+    Po,
     Custom(Arc<dyn Parser>),
 }
 
@@ -59,6 +172,8 @@ impl Debug for FileFormat {
             FileFormat::Json5 => f.write_str("Json5"),
             FileFormat::Yaml => f.write_str("Yaml"),
             FileFormat::Toml => f.write_str("Toml"),
+            FileFormat::Fluent => f.write_str("Fluent"),
+            FileFormat::Po => f.write_str("Po"),
             FileFormat::Custom(..) => f.debug_tuple("Custom").finish(),
         }
     }
@@ -74,9 +189,18 @@ impl ParseOptions {
     pub fn new() -> Self {
         ParseOptions {
             file_format: FileFormat::Json,
+            additional_file_formats: vec![],
             suppress_key_warnings: false,
             interpolate_display: false,
             show_keys_only: false,
+            icu_message_format: false,
+            select_suffix_var: None,
+            key_mapping: KeyMapping::new(),
+            strict_constraints: false,
+            strict_plural_forms: false,
+            variable_length_allowance: 0,
+            custom_parse_fns: vec![],
+            default_parse_fns: true,
             formatters: Formatters::new(),
         }
     }
@@ -88,6 +212,29 @@ impl ParseOptions {
         }
     }
 
+    /// Also read files of the given format, the format of each file being picked from its extension.
+    pub fn add_file_format(mut self, file_format: FileFormat) -> Self {
+        self.additional_file_formats.push(file_format);
+        self
+    }
+
+    /// Also read files with the extensions of the given parser.
+    pub fn add_custom_parser<P: Parser>(self, parser: P) -> Self {
+        self.add_file_format(FileFormat::Custom(Arc::new(parser)))
+    }
+
+    /// The enabled formats, `file_format` being the first one.
+    pub fn file_formats(&self) -> impl Iterator<Item = &FileFormat> {
+        std::iter::once(&self.file_format).chain(&self.additional_file_formats)
+    }
+
+    /// The first enabled format handling the extension of `path`.
+    pub fn file_format_of(&self, path: &Path) -> Option<&FileFormat> {
+        let ext = path.extension()?;
+        self.file_formats()
+            .find(|file_format| file_format.get_files_exts().iter().any(|e| ext == *e))
+    }
+
     pub fn suppress_key_warnings(self, suppress_key_warnings: bool) -> Self {
         Self {
             suppress_key_warnings,
@@ -109,6 +256,98 @@ impl ParseOptions {
         }
     }
 
+    /// Parse values as ICU MessageFormat, e.g. `{count, plural, one {# item} other {# items}}`.
+    pub fn icu_message_format(self, icu_message_format: bool) -> Self {
+        Self {
+            icu_message_format,
+            ..self
+        }
+    }
+
+    /// Merge suffixed keys such as `invite_male`, `invite_female` and `invite_other` into a select on the given variable.
+    ///
+    /// Errors if the variable name is not a valid identifier.
+    pub fn select_suffix_var(self, var_name: &str) -> Result<Self> {
+        let key = Key::try_new(&format!("var_{var_name}"))?;
+        Ok(Self {
+            select_suffix_var: Some(key),
+            ..self
+        })
+    }
+
+    /// How keys that are not valid rust identifiers are mapped to one, see [`KeyMapping`].
+    pub fn key_mapping(self, key_mapping: KeyMapping) -> Self {
+        Self {
+            key_mapping,
+            ..self
+        }
+    }
+
+    /// Report values violating the constraints of their metadata (max length, forbidden characters, trailing punctuation) as errors instead of warnings.
+    pub fn strict_constraints(self, strict_constraints: bool) -> Self {
+        Self {
+            strict_constraints,
+            ..self
+        }
+    }
+
+    /// Report plurals missing forms used by their locale as errors instead of warnings.
+    pub fn strict_plural_forms(self, strict_plural_forms: bool) -> Self {
+        Self {
+            strict_plural_forms,
+            ..self
+        }
+    }
+
+    /// Length counted for each variable when checking the max length of an interpolated value,
+    /// overridden by the `maxLength` of the placeholder metadata.
+    pub fn variable_length_allowance(self, variable_length_allowance: usize) -> Self {
+        Self {
+            variable_length_allowance,
+            ..self
+        }
+    }
+
+    /// Register a function parsing string values, see [`ParseFn`] for what it should return.
+    ///
+    /// Registered functions are tried in the order they were registered, all before the default ones.
+    /// After [`with_parse_fns`](Self::with_parse_fns) they are tried after the given functions.
+    pub fn add_parse_fn(mut self, parse_fn: ParseFn) -> Self {
+        self.custom_parse_fns.push(parse_fn);
+        self
+    }
+
+    /// Replace the functions parsing string values, tried in the given order.
+    /// Functions registered before with [`add_parse_fn`](Self::add_parse_fn) are dropped.
+    ///
+    /// The default ones ([`ParsedValue::DEFAULT_FNS`]) can be reused, for example to only replace the variable syntax:
+    /// `with_parse_fns(&[ParsedValue::find_component, ParsedValue::find_foreign_key, find_single_brace_variable])`
+    pub fn with_parse_fns(self, parse_fns: &[ParseFn]) -> Self {
+        Self {
+            custom_parse_fns: parse_fns.to_vec(),
+            default_parse_fns: false,
+            ..self
+        }
+    }
+
+    /// The functions used to parse string values.
+    pub fn parse_fns(&self) -> Cow<'_, [ParseFn]> {
+        let default_parse_fns = if !self.default_parse_fns {
+            &[]
+        } else if self.icu_message_format {
+            ParsedValue::ICU_FNS
+        } else {
+            ParsedValue::DEFAULT_FNS
+        };
+        if self.custom_parse_fns.is_empty() {
+            Cow::Borrowed(default_parse_fns)
+        } else if default_parse_fns.is_empty() {
+            Cow::Borrowed(&self.custom_parse_fns)
+        } else {
+            Cow::Owned([&self.custom_parse_fns, default_parse_fns].concat())
+        }
+    }
+
     pub fn with_custom_parser<P: Parser>(self, parser: P) -> Self {
         Self {
             file_format: FileFormat::Custom(Arc::new(parser)),
@@ -131,6 +370,8 @@ impl Config {
             locales: vec![default_locale.clone()],
             default_locale,
             locales_path: Cow::Borrowed(DEFAULT_LOCALES_PATH.as_ref()),
+            translations_table: None,
+            overlay_paths: vec![],
             namespaces: vec![],
             translations_uri: None,
             extensions: BTreeMap::new(),
@@ -175,6 +416,26 @@ impl Config {
         }
     }
 
+    /// Read every locale and namespace from a single CSV file, or TSV file with the `.tsv` extension,
+    /// instead of one file per locale in `locales_path`.
+    ///
+    /// The table has a `key` column with the dot separated key paths, prefixed by the namespace if any,
+    /// and one column per locale named after it.
+    pub fn translations_table(self, path: impl ToPathCow<'static>) -> Self {
+        Self {
+            translations_table: Some(path.into_cow()),
+            ..self
+        }
+    }
+
+    /// Layer a directory of locales over `locales_path`, its files overriding the base translations key by key.
+    ///
+    /// Overlays are applied in the order they are added.
+    pub fn add_overlay_path(mut self, path: impl ToPathCow<'static>) -> Self {
+        self.overlay_paths.push(path.into_cow());
+        self
+    }
+
     pub fn add_namespace(mut self, namespace: &str) -> Result<Self> {
         self.namespaces.push(Key::try_new(namespace)?);
         Ok(self)
@@ -250,10 +511,19 @@ impl FileFormat {
             FileFormat::Json5 => &["json5"],
             FileFormat::Yaml => &["yaml", "yml"],
             FileFormat::Toml => &["toml"],
+            FileFormat::Fluent => &["ftl"],
+            FileFormat::Po => &["po"],
             FileFormat::Custom(parser) => parser.file_extensions(),
         }
     }
 
+    pub fn locale_file_path(&self, locale: &str, namespace: Option<&str>) -> Option<PathBuf> {
+        match self {
+            FileFormat::Custom(parser) => parser.locale_file_path(locale, namespace),
+            _ => None,
+        }
+    }
+
     pub fn deserialize<R: Read>(
         &self,
         locale_file: R,
@@ -265,6 +535,8 @@ impl FileFormat {
             FileFormat::Json5 => de_json5(locale_file, seed),
             FileFormat::Yaml => de_yaml(locale_file, seed),
             FileFormat::Toml => de_toml(locale_file, seed),
+            FileFormat::Fluent => de_fluent(locale_file, seed),
+            FileFormat::Po => de_po(locale_file, seed),
             FileFormat::Custom(parser) => parser::de_custom(&**parser, locale_file, path, seed),
         }
     }
@@ -296,14 +568,45 @@ fn de_toml<R: Read>(mut locale_file: R, seed: LocaleSeed) -> Result<Locale, Serd
     serde::de::DeserializeSeed::deserialize(seed, deserializer).map_err(SerdeError::Toml)
 }
 
+fn de_fluent<R: Read>(mut locale_file: R, seed: LocaleSeed) -> Result<Locale, SerdeError> {
+    let mut buf = String::new();
+    locale_file
+        .read_to_string(&mut buf)
+        .map_err(SerdeError::Io)?;
+    fluent::deserialize(&buf, seed)
+}
+
+fn de_po<R: Read>(mut locale_file: R, seed: LocaleSeed) -> Result<Locale, SerdeError> {
+    let mut buf = String::new();
+    locale_file
+        .read_to_string(&mut buf)
+        .map_err(SerdeError::Io)?;
+    po::deserialize(&buf, seed)
+}
+
 pub mod parser {
     use crate::parse_locales::locale::{Locale, LocaleSeed};
-    use std::{io::Read, path::Path};
+    use std::{
+        io::Read,
+        path::{Path, PathBuf},
+    };
 
     pub use crate::parse_locales::locale::SerdeError;
 
     pub struct Seed<'a>(LocaleSeed<'a>);
 
+    impl Seed<'_> {
+        /// The name of the locale being parsed.
+        pub fn locale(&self) -> &str {
+            &self.0.name.name
+        }
+
+        /// The namespace being parsed, if any.
+        pub fn namespace(&self) -> Option<&str> {
+            self.0.key_path.namespace.as_ref().map(|ns| &*ns.name)
+        }
+    }
+
     impl<'de> serde::de::DeserializeSeed<'de> for Seed<'_> {
         type Value = Value;
         fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
@@ -325,6 +628,14 @@ pub mod parser {
         ) -> Result<Value, SerdeError>;
 
         fn file_extensions(&self) -> &'static [&'static str];
+
+        /// Path of the file to parse for a locale and namespace, relative to the locales directory.
+        ///
+        /// Return `None` to use the default layout, `{locale}.{ext}` or `{locale}/{namespace}.{ext}`.
+        fn locale_file_path(&self, locale: &str, namespace: Option<&str>) -> Option<PathBuf> {
+            let _ = (locale, namespace);
+            None
+        }
     }
 
     pub(crate) fn de_custom<R: Read>(
@@ -0,0 +1,660 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::Infallible,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    ForeignKeysPaths,
+    error::{Diagnostics, Error, Result, Warning},
+    locale::{Locale, LocalesOrNamespaces},
+    parsed_value::{AttributeValue, Dummy, ForeignKey, ParsedValue},
+    plurals::Plurals,
+    select::Select,
+};
+use crate::{
+    parse_locales::options::Config,
+    utils::{Key, KeyPath, Loc, Location, UnwrapAt},
+};
+
+/// The variables and components a value needs, foreign keys must be resolved to be part of it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Signature {
+    variables: BTreeSet<Key>,
+    components: BTreeSet<Key>,
+}
+
+impl Signature {
+    fn of(value: &ParsedValue) -> Option<Self> {
+        let mut signature = Signature::default();
+        match value {
+            ParsedValue::Subkeys(_) => return None,
+            value => signature.push_value(value),
+        }
+        Some(signature)
+    }
+
+    fn push_value(&mut self, value: &ParsedValue) {
+        match value {
+            ParsedValue::Default
+            | ParsedValue::Literal(_)
+            | ParsedValue::List(_)
+            | ParsedValue::Subkeys(_) => {}
+            ParsedValue::ForeignKey(foreign_key) => {
+                if let ForeignKey::Set(value) = &*foreign_key.borrow() {
+                    self.push_value(value);
+                }
+            }
+            ParsedValue::Variable { key, .. } => {
+                self.variables.insert(key.clone());
+            }
+            ParsedValue::Component {
+                key,
+                inner,
+                attributes,
+            } => {
+                self.components.insert(key.clone());
+                if let Some(inner) = inner {
+                    self.push_value(inner);
+                }
+                for attribute in &attributes.0 {
+                    if let Some(AttributeValue::Variable(key)) = &attribute.value {
+                        self.variables.insert(key.clone());
+                    }
+                }
+            }
+            ParsedValue::Bloc(values) => values.iter().for_each(|value| self.push_value(value)),
+            ParsedValue::Ranges(ranges) => {
+                self.variables.insert(ranges.count_key.clone());
+                let _ = ranges.try_for_each_value(|value| {
+                    self.push_value(value);
+                    Ok::<_, Infallible>(())
+                });
+            }
+            ParsedValue::Plurals(Plurals {
+                count_key,
+                forms,
+                other,
+                ..
+            }) => {
+                self.variables.insert(count_key.clone());
+                forms.values().for_each(|value| self.push_value(value));
+                self.push_value(other);
+            }
+            ParsedValue::Select(Select {
+                select_key,
+                other,
+                variants,
+            }) => {
+                self.variables.insert(select_key.clone());
+                variants.values().for_each(|value| self.push_value(value));
+                self.push_value(other);
+            }
+            ParsedValue::Condition(condition) => {
+                self.variables.insert(condition.condition_key.clone());
+                condition
+                    .branches()
+                    .into_iter()
+                    .for_each(|value| self.push_value(value));
+            }
+            ParsedValue::Dummy(dummies) => {
+                for dummy in dummies {
+                    match dummy {
+                        Dummy::Variable(key) => self.variables.insert(key.clone()),
+                        Dummy::Component(key) => self.components.insert(key.clone()),
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn names<'a>(keys: &'a BTreeSet<Key>, prefix: &'a str) -> Vec<&'a str> {
+            keys.iter()
+                .map(|key| key.name.strip_prefix(prefix).unwrap_or(&key.name))
+                .collect()
+        }
+        write!(
+            f,
+            "variables {:?} and components {:?}",
+            names(&self.variables, "var_"),
+            names(&self.components, "comp_")
+        )
+    }
+}
+
+fn describe(signature: Option<Signature>) -> String {
+    match signature {
+        Some(signature) => signature.to_string(),
+        None => "subkeys".to_string(),
+    }
+}
+
+/// A value overridden by an overlay, with the value it replaces.
+///
+/// Foreign keys are resolved after the plurals are merged, so their signatures are compared once all overlays are applied.
+#[derive(Debug)]
+struct OverriddenValue {
+    loc: Location,
+    overlay: PathBuf,
+    expected: ParsedValue,
+    found: ParsedValue,
+}
+
+/// The values overridden by the overlays, to check once foreign keys can be resolved.
+#[derive(Debug, Default)]
+pub struct OverlayChecks(Vec<OverriddenValue>);
+
+impl OverlayChecks {
+    /// Check that every overridden value needs the same variables and components as the value it replaces.
+    pub fn check(self, locales: &LocalesOrNamespaces, default_locale: &Key) -> Result<()> {
+        for value in self.0 {
+            let loc = Loc {
+                locale: &value.loc.locale,
+                key_path: &value.loc.key_path,
+            };
+            value
+                .found
+                .resolve_foreign_key(locales, &loc, default_locale)?;
+            // the replaced value is no longer part of the translations, it is not checked if its foreign keys don't resolve.
+            if value
+                .expected
+                .resolve_foreign_key(locales, &loc, default_locale)
+                .is_err()
+            {
+                continue;
+            }
+            let expected = Signature::of(&value.expected);
+            let found = Signature::of(&value.found);
+            if expected != found {
+                return Err(Error::OverlaySignatureMismatch {
+                    loc: value.loc,
+                    overlay: value.overlay,
+                    expected: describe(expected),
+                    found: describe(found),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Overlay<'a> {
+    path: &'a Path,
+    default_locale: &'a Key,
+    diag: &'a Diagnostics,
+}
+
+impl Overlay<'_> {
+    fn unknown_key(&self, locale: &Key, key_path: &KeyPath) -> Error {
+        Error::OverlayUnknownKey {
+            loc: Location::new(locale.clone(), key_path.clone()),
+            overlay: self.path.to_owned(),
+        }
+    }
+
+    fn signature_mismatch(
+        &self,
+        locale: &Key,
+        key_path: &KeyPath,
+        expected: Option<Signature>,
+        found: Option<Signature>,
+    ) -> Error {
+        Error::OverlaySignatureMismatch {
+            loc: Location::new(locale.clone(), key_path.clone()),
+            overlay: self.path.to_owned(),
+            expected: describe(expected),
+            found: describe(found),
+        }
+    }
+
+    fn record_origin(&self, locale: &Key, key_path: &KeyPath) {
+        self.diag.set_overlay_origin(
+            Location::new(locale.clone(), key_path.clone()),
+            self.path.to_owned(),
+        );
+    }
+
+    /// Override the keys of `base`, `default` being the keys of the default locale to check keys missing in `base`.
+    fn override_keys(
+        &self,
+        base: &mut BTreeMap<Key, ParsedValue>,
+        default: Option<&BTreeMap<Key, ParsedValue>>,
+        overlay: BTreeMap<Key, ParsedValue>,
+        locale: &Key,
+        key_path: &mut KeyPath,
+        checks: &mut OverlayChecks,
+    ) -> Result<()> {
+        for (key, value) in overlay {
+            let mut key_path = key_path.push_key(key.clone());
+            let default_value = default.and_then(|default| default.get(&key));
+            let Some(expected) = base.get(&key).or(default_value) else {
+                return Err(self.unknown_key(locale, &key_path).into());
+            };
+
+            match (expected, value) {
+                (ParsedValue::Subkeys(_), ParsedValue::Subkeys(overlay_subkeys)) => {
+                    let default_subkeys = match default_value {
+                        Some(ParsedValue::Subkeys(Some(default_subkeys))) => {
+                            Some(&default_subkeys.keys)
+                        }
+                        _ => None,
+                    };
+                    let base_subkeys = base.entry(key.clone()).or_insert_with(|| {
+                        self.record_origin(locale, &key_path);
+                        ParsedValue::Subkeys(Some(Locale {
+                            top_locale_name: locale.clone(),
+                            name: key.clone(),
+                            keys: BTreeMap::new(),
+                            strings: vec![],
+                            top_locale_string_count: 0,
+                            metadata: BTreeMap::new(),
+                        }))
+                    });
+                    let (ParsedValue::Subkeys(Some(base_subkeys)), Some(overlay_subkeys)) =
+                        (base_subkeys, overlay_subkeys)
+                    else {
+                        continue;
+                    };
+                    self.override_keys(
+                        &mut base_subkeys.keys,
+                        default_subkeys,
+                        overlay_subkeys.keys,
+                        locale,
+                        &mut key_path,
+                        checks,
+                    )?;
+                }
+                (expected, value)
+                    if matches!(expected, ParsedValue::Subkeys(_))
+                        || matches!(value, ParsedValue::Subkeys(_)) =>
+                {
+                    let expected = Signature::of(expected);
+                    let found = Signature::of(&value);
+                    return Err(self
+                        .signature_mismatch(locale, &key_path, expected, found)
+                        .into());
+                }
+                (_, value) => {
+                    self.record_origin(locale, &key_path);
+                    let expected = match base.insert(key, value.clone()) {
+                        Some(replaced) => replaced,
+                        None => default_value.cloned().unwrap_at("override_keys_1"),
+                    };
+                    checks.0.push(OverriddenValue {
+                        loc: Location::new(locale.clone(), key_path.clone()),
+                        overlay: self.path.to_owned(),
+                        expected,
+                        found: value,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(
+        &self,
+        locales: &mut [Locale],
+        namespace: Option<&Key>,
+        parse_locale: &mut dyn FnMut(&Key) -> Result<Option<Locale>>,
+        checks: &mut OverlayChecks,
+    ) -> Result<()> {
+        let default_index = locales
+            .iter()
+            .position(|locale| &locale.name == self.default_locale)
+            .unwrap_or_default();
+        for index in 0..locales.len() {
+            let Some(overlay_locale) = parse_locale(&locales[index].name)? else {
+                continue;
+            };
+            let (base, default) = base_and_default(locales, index, default_index);
+            let mut key_path = KeyPath::new(namespace.cloned());
+            self.override_keys(
+                &mut base.keys,
+                default.map(|default| &default.keys),
+                overlay_locale.keys,
+                &base.name,
+                &mut key_path,
+                checks,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The locale at `index` and the default locale, if they are not the same.
+fn base_and_default(
+    locales: &mut [Locale],
+    index: usize,
+    default_index: usize,
+) -> (&mut Locale, Option<&Locale>) {
+    if index < default_index {
+        let (before, after) = locales.split_at_mut(default_index);
+        (&mut before[index], Some(&after[0]))
+    } else if index > default_index {
+        let (before, after) = locales.split_at_mut(index);
+        (&mut after[0], Some(&before[default_index]))
+    } else {
+        (&mut locales[index], None)
+    }
+}
+
+/// Forward the diagnostics emitted while parsing an overlay, noting the overlay they come from.
+fn forward_diagnostics(overlay_diag: Diagnostics, diag: &Diagnostics, overlay: &Path) {
+    if overlay_diag.has_ranges() {
+        diag.set_has_ranges();
+    }
+    let (errors, warnings) = overlay_diag.into_inner();
+    for err in errors {
+        diag.emit_error(Error::InOverlay {
+            overlay: overlay.to_owned(),
+            err: Box::new(err),
+        });
+    }
+    for warning in warnings {
+        diag.emit_warning(Warning::InOverlay {
+            overlay: overlay.to_owned(),
+            warning: Box::new(warning),
+        });
+    }
+}
+
+/// Layer the overlays of the config over the base translations, in order.
+pub fn apply_overlays(
+    locales: &mut LocalesOrNamespaces,
+    manifest_dir_path: &Path,
+    foreign_keys_paths: &ForeignKeysPaths,
+    diag: &Diagnostics,
+    tracked_files: &mut Vec<String>,
+    cfg: &Config,
+) -> Result<OverlayChecks> {
+    let mut checks = OverlayChecks::default();
+    for overlay_path in &cfg.overlay_paths {
+        let overlay_dir: PathBuf = manifest_dir_path.join(overlay_path);
+        let overlay = Overlay {
+            path: &overlay_dir,
+            default_locale: &cfg.default_locale,
+            diag,
+        };
+        let overlay_diag = Diagnostics::new();
+        let mut parse_locale = |namespace: Option<&Key>, locale: &Key| {
+            let parsed = Locale::parse_at(
+                &overlay_dir,
+                locale.clone(),
+                namespace,
+                foreign_keys_paths,
+                &overlay_diag,
+                tracked_files,
+                &cfg.options,
+            );
+            match parsed {
+                Ok(locale) => Ok(Some(locale)),
+                // overlays only need to provide the locales they override.
+                Err(err) => match err.into_inner() {
+                    Error::LocaleFileNotFound(_) => Ok(None),
+                    err => Err(err.into()),
+                },
+            }
+        };
+        let result = match locales {
+            LocalesOrNamespaces::NameSpaces(namespaces) => {
+                namespaces.iter_mut().try_for_each(|namespace| {
+                    overlay.apply(
+                        &mut namespace.locales,
+                        Some(&namespace.key),
+                        &mut |locale| parse_locale(Some(&namespace.key), locale),
+                        &mut checks,
+                    )
+                })
+            }
+            LocalesOrNamespaces::Locales(locales) => overlay.apply(
+                locales,
+                None,
+                &mut |locale| parse_locale(None, locale),
+                &mut checks,
+            ),
+        };
+        forward_diagnostics(overlay_diag, diag, &overlay_dir);
+        result?;
+    }
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formatters::VarBounds,
+        parse_locales::parsed_value::{Attributes, Literal},
+    };
+
+    fn lit(s: &str) -> ParsedValue {
+        ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX))
+    }
+
+    fn var(name: &str) -> ParsedValue {
+        ParsedValue::Variable {
+            key: Key::new(&format!("var_{name}")).unwrap(),
+            bounds: VarBounds::None,
+            default: None,
+        }
+    }
+
+    fn keys(values: &[(&str, ParsedValue)]) -> BTreeMap<Key, ParsedValue> {
+        values
+            .iter()
+            .map(|(key, value)| (Key::new(key).unwrap(), value.clone()))
+            .collect()
+    }
+
+    fn subkeys(name: &str, values: &[(&str, ParsedValue)]) -> ParsedValue {
+        ParsedValue::Subkeys(Some(Locale {
+            top_locale_name: Key::new("en").unwrap(),
+            name: Key::new(name).unwrap(),
+            keys: keys(values),
+            strings: vec![],
+            top_locale_string_count: 0,
+            metadata: BTreeMap::new(),
+        }))
+    }
+
+    fn override_keys(
+        base: &mut BTreeMap<Key, ParsedValue>,
+        default: Option<&BTreeMap<Key, ParsedValue>>,
+        overlay: BTreeMap<Key, ParsedValue>,
+    ) -> Result<()> {
+        let default_locale = Key::new("en").unwrap();
+        let diag = Diagnostics::new();
+        let overlay_layer = Overlay {
+            path: Path::new("brands/acme"),
+            default_locale: &default_locale,
+            diag: &diag,
+        };
+        let mut checks = OverlayChecks::default();
+        overlay_layer.override_keys(
+            base,
+            default,
+            overlay,
+            &default_locale,
+            &mut KeyPath::new(None),
+            &mut checks,
+        )?;
+        checks.check(&LocalesOrNamespaces::Locales(vec![]), &default_locale)
+    }
+
+    #[test]
+    fn override_existing_keys() {
+        let greeting = ParsedValue::Bloc(vec![lit("Hello "), var("name")]);
+        let mut base = keys(&[
+            ("title", lit("App")),
+            ("greeting", greeting),
+            ("sub", subkeys("sub", &[("a", lit("A")), ("b", lit("B"))])),
+        ]);
+        let acme_greeting = ParsedValue::Bloc(vec![lit("Welcome to Acme, "), var("name")]);
+        let overlay = keys(&[
+            ("title", lit("Acme")),
+            ("greeting", acme_greeting.clone()),
+            ("sub", subkeys("sub", &[("b", lit("Acme B"))])),
+        ]);
+        override_keys(&mut base, None, overlay).unwrap();
+
+        let expected = keys(&[
+            ("title", lit("Acme")),
+            ("greeting", acme_greeting),
+            (
+                "sub",
+                subkeys("sub", &[("a", lit("A")), ("b", lit("Acme B"))]),
+            ),
+        ]);
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn override_key_missing_in_locale() {
+        let default = keys(&[("title", lit("App"))]);
+        let mut base = keys(&[]);
+        override_keys(&mut base, Some(&default), keys(&[("title", lit("Appli"))])).unwrap();
+        assert_eq!(base, keys(&[("title", lit("Appli"))]));
+    }
+
+    #[test]
+    fn override_errors() {
+        let base = keys(&[
+            ("title", lit("App")),
+            (
+                "greeting",
+                ParsedValue::Bloc(vec![lit("Hello "), var("name")]),
+            ),
+            ("sub", subkeys("sub", &[("a", lit("A"))])),
+        ]);
+        let err = |overlay: &[(&str, ParsedValue)]| {
+            override_keys(&mut base.clone(), None, keys(overlay))
+                .unwrap_err()
+                .into_inner()
+        };
+
+        assert!(matches!(
+            err(&[("unknown", lit("x"))]),
+            Error::OverlayUnknownKey { .. }
+        ));
+        assert!(matches!(
+            err(&[("sub", subkeys("sub", &[("b", lit("x"))]))]),
+            Error::OverlayUnknownKey { .. }
+        ));
+        assert!(matches!(
+            err(&[("greeting", lit("Hello"))]),
+            Error::OverlaySignatureMismatch { .. }
+        ));
+        assert!(matches!(
+            err(&[("title", var("name"))]),
+            Error::OverlaySignatureMismatch { .. }
+        ));
+        assert!(matches!(
+            err(&[("sub", lit("x"))]),
+            Error::OverlaySignatureMismatch { .. }
+        ));
+        let component = ParsedValue::Component {
+            key: Key::new("comp_b").unwrap(),
+            inner: Some(Box::new(lit("App"))),
+            attributes: Attributes::default(),
+        };
+        assert!(matches!(
+            err(&[("title", component)]),
+            Error::OverlaySignatureMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn post_merge_diagnostics_name_overlay() {
+        let dir = std::env::temp_dir().join(format!("leptos_i18n_overlay_{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "locales/en.json",
+            r#"{ "title": "App", "sub": { "a": "A", "b": "B" } }"#,
+        );
+        write("locales/fr.json", r#"{ "title": "Appli" }"#);
+        write("brands/acme/fr.json", r#"{ "sub": { "a": "Acme A" } }"#);
+        write("brands/broken/en.json", r#"{ "title": "$t(missing)" }"#);
+        let cfg = || {
+            Config::new("en")
+                .unwrap()
+                .add_locale("fr")
+                .unwrap()
+                .add_overlay_path("brands/acme")
+        };
+
+        let parsed = super::super::parse_locales(Some(dir.clone()), cfg()).unwrap();
+        let warnings = parsed
+            .diag
+            .warnings()
+            .iter()
+            .map(|warning| match warning {
+                Warning::InOverlay { overlay, warning } => {
+                    (warning.to_string(), overlay.ends_with("brands/acme"))
+                }
+                warning => (warning.to_string(), false),
+            })
+            .collect::<Vec<_>>();
+        // "sub" is created in "fr" by the overlay, which only provides "sub.a"
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].0.contains("sub.b") && warnings[0].1);
+
+        let err =
+            super::super::parse_locales(Some(dir.clone()), cfg().add_overlay_path("brands/broken"))
+                .err()
+                .unwrap()
+                .into_inner();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let Error::InOverlay { overlay, err } = err else {
+            panic!("expected the error to name the overlay, got {err:?}");
+        };
+        assert!(overlay.ends_with("brands/broken"));
+        assert!(matches!(*err, Error::MissingForeignKey { .. }), "{err:?}");
+    }
+
+    #[test]
+    fn compare_resolved_foreign_keys() {
+        let dir = std::env::temp_dir().join(format!(
+            "leptos_i18n_overlay_foreign_keys_{}",
+            std::process::id()
+        ));
+        let write = |path: &str, content: &str| {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "locales/en.json",
+            r#"{ "hello": "Hello {{ name }}", "greeting": "Hello {{ name }}", "title": "App", "welcome": "$t(title)" }"#,
+        );
+        write("brands/acme/en.json", r#"{ "greeting": "$t(hello)" }"#);
+        write(
+            "brands/broken/en.json",
+            r#"{ "welcome": "$t(hello, {\"name\": \"{{ user }}\"})" }"#,
+        );
+        let cfg = || Config::new("en").unwrap().add_overlay_path("brands/acme");
+
+        assert!(super::super::parse_locales(Some(dir.clone()), cfg()).is_ok());
+
+        let err =
+            super::super::parse_locales(Some(dir.clone()), cfg().add_overlay_path("brands/broken"))
+                .err()
+                .unwrap()
+                .into_inner();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let Error::OverlaySignatureMismatch { overlay, found, .. } = err else {
+            panic!("expected a signature mismatch, got {err:?}");
+        };
+        assert!(overlay.ends_with("brands/broken"));
+        assert!(found.contains("user"), "{found}");
+    }
+}
@@ -1,25 +1,29 @@
-use std::{cell::RefCell, collections::BTreeMap, fmt::Display, mem};
+use std::{borrow::Cow, cell::RefCell, collections::BTreeMap, fmt::Display, mem};
 
-use serde::{
-    Deserialize,
-    de::{DeserializeSeed, Visitor, value::MapAccessDeserializer},
-};
+use serde::{Deserialize, de::Visitor};
 
 use crate::{
-    formatters::{Formatters, VarBounds},
-    parse_locales::{error::Warning, options::ParseOptions},
+    formatters::{Formatters, VarBounds, VarType},
+    parse_locales::{
+        error::Warning,
+        options::{KeyMapping, ParseOptions},
+    },
     utils::{Key, KeyPath, Loc, Location, ParseContext, ParseFn, UnwrapAt},
 };
 
 use super::{
     ForeignKeysPaths, StringIndexer,
+    condition::{Condition, IF_CLOSE, IF_ELSE, IF_OPEN},
     error::{Diagnostics, Error, Result},
     locale::{
         DefaultTo, DefaultedLocales, InterpolOrLit, InterpolationKeys, LiteralType, Locale,
-        LocaleSeed, LocaleValue, LocalesOrNamespaces, RangeOrPlural,
+        LocaleValue, LocalesOrNamespaces, RangeOrPlural,
     },
-    plurals::Plurals,
-    ranges::Ranges,
+    message_format,
+    metadata::{self, METADATA_PREFIX},
+    plurals::{self, OFFSET_MARKER, ORDINAL_MARKER, PLURAL_MARKER, PluralRuleType, Plurals},
+    ranges::{Ranges, RangesOrList},
+    select::{SELECT_MARKER, Select},
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -53,6 +57,8 @@ pub enum ParsedValue {
     Variable {
         key: Key,
         bounds: VarBounds,
+        /// Value used when the variable is not given, `{{ name = "friend" }}`.
+        default: Option<String>,
     },
     Component {
         key: Key,
@@ -62,6 +68,10 @@ pub enum ParsedValue {
     Bloc(Vec<Self>),
     Subkeys(Option<Locale>),
     Plurals(Plurals),
+    Select(Select),
+    Condition(Condition),
+    /// A list of strings, declared as an array.
+    List(Vec<Literal>),
     Dummy(Vec<Dummy>),
 }
 
@@ -154,19 +164,132 @@ impl Display for Literal {
     }
 }
 
+/// Character escaping the delimiters of the default parse functions: `\{{`, `\<` and `\$t(` are output as `{{`, `<` and `$t(`,
+/// and `\\` right before one of them as `\`. Other escape characters are kept as is.
+pub const ESCAPE_CHAR: char = '\\';
+
+/// Length of the delimiter `value` starts with: `{{`, `$t(` or a `<` starting a tag.
+fn delimiter_len(value: &str) -> Option<usize> {
+    if value.starts_with("{{") {
+        return Some(2);
+    }
+    if value.starts_with("$t(") {
+        return Some(3);
+    }
+    let tag = value.strip_prefix('<')?;
+    let tag = tag.strip_prefix('/').unwrap_or(tag).trim_start();
+    tag.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        .then_some(1)
+}
+
+fn is_escaped(value: &str, index: usize) -> bool {
+    let escapes = value[..index]
+        .chars()
+        .rev()
+        .take_while(|c| *c == ESCAPE_CHAR)
+        .count();
+    escapes % 2 == 1
+}
+
+/// Index of the first occurrence of `pat` not preceded by an escape character.
+pub fn find_unescaped(value: &str, pat: &str) -> Option<usize> {
+    value
+        .match_indices(pat)
+        .map(|(index, _)| index)
+        .find(|index| !is_escaped(value, *index))
+}
+
+/// Split on the first occurrence of `pat` not preceded by an escape character.
+///
+/// The text before is cut right before a delimiter, see [`before_delimiter`].
+pub fn split_once_unescaped<'a>(value: &'a str, pat: &str) -> Option<(&'a str, &'a str)> {
+    let index = find_unescaped(value, pat)?;
+    Some((&value[..index], &value[index + pat.len()..]))
+}
+
+/// Unescape the escape characters at the end of a text cut right before a delimiter,
+/// as [`unescape`] can't see the delimiter they precede anymore: `\\` is output as `\`.
+pub fn before_delimiter(value: &str) -> Cow<'_, str> {
+    let escapes = value
+        .chars()
+        .rev()
+        .take_while(|c| *c == ESCAPE_CHAR)
+        .count();
+    if escapes == 0 {
+        return Cow::Borrowed(value);
+    }
+    let mut unescaped = value[..value.len() - escapes].to_string();
+    unescaped.extend(std::iter::repeat_n(ESCAPE_CHAR, escapes / 2));
+    Cow::Owned(unescaped)
+}
+
+/// Remove the escape characters before delimiters, other escape characters are kept as is.
+pub fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        if c != ESCAPE_CHAR {
+            unescaped.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let escapes = rest.chars().take_while(|c| *c == ESCAPE_CHAR).count();
+        let after = &rest[escapes..];
+        match delimiter_len(after) {
+            Some(len) => {
+                unescaped.extend(std::iter::repeat_n(ESCAPE_CHAR, escapes / 2));
+                // an odd number of escape characters escapes the delimiter.
+                let len = if escapes % 2 == 1 { len } else { 0 };
+                unescaped.push_str(&after[..len]);
+                rest = &after[len..];
+            }
+            None => {
+                unescaped.push_str(&rest[..escapes]);
+                rest = after;
+            }
+        }
+    }
+    unescaped
+}
+
+/// Escape the delimiters of the default parse functions in a plain text, the opposite of [`unescape`].
+pub fn escape(value: &str, out: &mut String) {
+    let mut escapes = 0;
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        if let Some(len) = delimiter_len(rest) {
+            // the escape characters before the delimiter escape each other.
+            out.extend(std::iter::repeat_n(ESCAPE_CHAR, escapes + 1));
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            escapes = 0;
+            continue;
+        }
+        escapes = if c == ESCAPE_CHAR { escapes + 1 } else { 0 };
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
 impl ParsedValue {
+    /// The functions used to parse string values by default.
     pub const DEFAULT_FNS: &[ParseFn] = &[
+        ParsedValue::find_condition,
         ParsedValue::find_component,
         ParsedValue::find_foreign_key,
         ParsedValue::find_variable,
     ];
 
+    /// The functions used to parse string values as ICU MessageFormat.
+    pub const ICU_FNS: &[ParseFn] = &[ParsedValue::find_icu_message];
+
+    /// Parse a string value with the parse functions of `ctx`, a literal if none of them match.
     pub fn new(ctx: &ParseContext, value: &str) -> Result<Self> {
         let parsed_value = ctx.parse_fns.iter().find_map(|f| f(ctx, value));
         match parsed_value {
             Some(Ok(value)) => Ok(value),
             None => Ok(ParsedValue::Literal(Literal::String(
-                value.to_string(),
+                unescape(value),
                 usize::MAX,
             ))),
             Some(Err(err)) => Err(err),
@@ -221,7 +344,7 @@ impl ParsedValue {
         ctx.formatters.parse(ctx, name, &args)
     }
 
-    fn parse_key_path(path: &str) -> Option<KeyPath> {
+    fn parse_key_path(ctx: &ParseContext, path: &str) -> Option<KeyPath> {
         let (ns, path) = if let Some((namespace, rest)) = path.split_once(':') {
             let namespace = Key::new(namespace)?;
 
@@ -231,7 +354,7 @@ impl ParsedValue {
         };
         let mut key_path = Vec::new();
         for key in path.split('.') {
-            let key = Key::new(key)?;
+            let key = Key::with_mapping(key, ctx.key_mapping)?;
             key_path.push(key);
         }
 
@@ -311,13 +434,14 @@ impl ParsedValue {
         Ok((args, after))
     }
 
-    fn find_foreign_key(ctx: &ParseContext, value: &str) -> Option<Result<Self>> {
-        let (before, rest) = value.split_once("$t(")?;
+    /// Parse the first `$t(key_path)` or `$t(key_path, { "arg": "value" })` foreign key.
+    pub fn find_foreign_key(ctx: &ParseContext, value: &str) -> Option<Result<Self>> {
+        let (before, rest) = split_once_unescaped(value, "$t(")?;
         let next_split = rest.find([',', ')'])?;
         let keypath = rest.get(..next_split)?;
         let sep = rest[next_split..].chars().next()?;
         let after = rest.get(next_split + sep.len_utf8()..)?;
-        let target_key_path = Self::parse_key_path(keypath)?;
+        let target_key_path = Self::parse_key_path(ctx, keypath)?;
 
         let (args, after) = if sep == ',' {
             nested_result_try!(Self::parse_foreign_key_args(after, ctx))
@@ -332,21 +456,45 @@ impl ParsedValue {
             ctx.foreign_keys_paths,
         )));
 
-        let before = nested_result_try!(Self::new(ctx, before));
+        // a value that is only a foreign key can alias subkeys, keep it unwrapped
+        if before.is_empty() && after.is_empty() {
+            return Some(Ok(this));
+        }
+
+        let before = nested_result_try!(Self::new(ctx, &before_delimiter(before)));
         let after = nested_result_try!(Self::new(ctx, after));
 
         Some(Ok(ParsedValue::Bloc(vec![before, this, after])))
     }
 
+    /// A foreign key to `key_path`, for a key declared as an alias.
+    pub fn new_alias(ctx: &ParseContext, key_path: &str) -> Result<Self> {
+        let Some(target_key_path) = Self::parse_key_path(ctx, key_path.trim()) else {
+            return Err(Error::InvalidAlias {
+                loc: ctx.loc.into(),
+                alias: key_path.to_owned(),
+            }
+            .into());
+        };
+        Ok(ParsedValue::ForeignKey(RefCell::new(ForeignKey::new(
+            ctx.loc.into(),
+            target_key_path,
+            BTreeMap::new(),
+            ctx.foreign_keys_paths,
+        ))))
+    }
+
+    /// Parse the whole value as an ICU MessageFormat message.
+    pub fn find_icu_message(ctx: &ParseContext, value: &str) -> Option<Result<Self>> {
+        Some(message_format::parse(ctx, value))
+    }
+
     fn find_dummy_var(value: &str, dummies: &mut Vec<Dummy>) -> Option<()> {
-        let (before, rest) = value.split_once("{{")?;
+        let (before, rest) = split_once_unescaped(value, "{{")?;
         let (ident, after) = rest.split_once("}}")?;
 
-        let ident = if let Some((ident, _)) = ident.split_once(',') {
-            ident.trim()
-        } else {
-            ident.trim()
-        };
+        let ident = ident.split([',', '=']).next().unwrap_or(ident).trim();
+        let ident = ident.strip_prefix("#if").map_or(ident, str::trim_start);
         let key = Key::new(&format!("var_{ident}"))?;
 
         dummies.push(Dummy::Variable(key));
@@ -361,30 +509,105 @@ impl ParsedValue {
         value: &'a str,
         ctx: &ParseContext,
     ) -> Option<Result<(&'a str, Self, &'a str)>> {
-        let (before, rest) = value.split_once("{{")?;
+        let (before, rest) = split_once_unescaped(value, "{{")?;
         let (ident, after) = rest.split_once("}}")?;
 
         let ident = ident.trim();
 
-        let this = if let Some((ident, s)) = ident.split_once(',') {
+        let this = if let Some((ident, default)) = ident.split_once('=') {
+            let ident = ident.trim();
+            let name = ident.split([',', ':']).next().unwrap_or(ident);
+            let key = Key::new(&format!("var_{}", name.trim()))?;
+            let default = nested_result_try!(Self::parse_var_default(ctx, ident, default));
+            ParsedValue::Variable {
+                key,
+                bounds: VarBounds::None,
+                default: Some(default),
+            }
+        } else if let Some((ident, s)) = ident.split_once(',') {
+            let name = ident.split_once(':').map_or(ident, |(name, _)| name);
+            let key = Key::new(&format!("var_{}", name.trim()))?;
+            if ident.contains(':') {
+                return Some(Err(Error::UnexpectedToken {
+                    loc: ctx.into(),
+                    message: format!(
+                        "variable {:?} can't have both a type and a formatter",
+                        ident.trim()
+                    ),
+                }
+                .into()));
+            }
             let bounds = Self::parse_formatter(ctx, s);
+            ParsedValue::Variable {
+                key,
+                bounds,
+                default: None,
+            }
+        } else if let Some((ident, ty)) = ident.split_once(':') {
             let key = Key::new(&format!("var_{}", ident.trim()))?;
-            ParsedValue::Variable { key, bounds }
+            let bounds = nested_result_try!(Self::parse_var_type(ctx, ty));
+            ParsedValue::Variable {
+                key,
+                bounds,
+                default: None,
+            }
         } else {
             let key = Key::new(&format!("var_{ident}"))?;
             ParsedValue::Variable {
                 key,
                 bounds: VarBounds::None,
+                default: None,
             }
         };
 
         Some(Ok((before, this, after)))
     }
 
-    fn find_variable(ctx: &ParseContext, value: &str) -> Option<Result<Self>> {
+    /// The type of `{{ count: u32 }}`, `date`, `time` and `datetime` are the formatters of the same name.
+    fn parse_var_type(ctx: &ParseContext, ty: &str) -> Result<VarBounds> {
+        let ty = ty.trim();
+        if matches!(ty, "date" | "time" | "datetime") {
+            Ok(ctx.formatters.parse(ctx, ty, &[]))
+        } else if let Some(ty) = VarType::new(ty) {
+            Ok(VarBounds::Typed(ty))
+        } else {
+            Err(Error::UnknownVarType {
+                name: ty.to_owned(),
+                loc: ctx.into(),
+            }
+            .into())
+        }
+    }
+
+    /// The default value of `{{ name = "friend" }}`, a quoted string.
+    fn parse_var_default(ctx: &ParseContext, ident: &str, default: &str) -> Result<String> {
+        let message = if ident.contains(',') {
+            format!("variable {ident:?} can't have both a formatter and a default value")
+        } else if ident.contains(':') {
+            format!("variable {ident:?} can't have both a type and a default value")
+        } else if let Some(default) = default
+            .trim()
+            .strip_prefix('"')
+            .and_then(|default| default.strip_suffix('"'))
+        {
+            return Ok(default.to_owned());
+        } else {
+            format!(
+                "invalid default value for variable {ident:?}, expected a quoted string such as {{{{ {ident} = \"default\" }}}}"
+            )
+        };
+        Err(Error::UnexpectedToken {
+            loc: ctx.into(),
+            message,
+        }
+        .into())
+    }
+
+    /// Parse the first `{{ var }}`, `{{ var: type }}` or `{{ var, formatter }}` variable.
+    pub fn find_variable(ctx: &ParseContext, value: &str) -> Option<Result<Self>> {
         let (before, this, after) = nested_result_try!(Self::find_valid_variable(value, ctx)?);
 
-        let before = nested_result_try!(Self::new(ctx, before));
+        let before = nested_result_try!(Self::new(ctx, &before_delimiter(before)));
         let after = nested_result_try!(Self::new(ctx, after));
 
         Some(Ok(ParsedValue::Bloc(vec![before, this, after])))
@@ -600,7 +823,76 @@ impl ParsedValue {
         }
     }
 
-    fn find_component(ctx: &ParseContext, value: &str) -> Option<Result<Self>> {
+    /// Split the content following `{{#if var}}` into the two branches and the text after `{{/if}}`.
+    fn split_condition_branches(value: &str) -> Option<(&str, Option<&str>, &str)> {
+        let mut depth = 0usize;
+        let mut else_idx = None;
+        let mut search_start = 0;
+
+        loop {
+            let (rel_idx, marker) = [IF_OPEN, IF_ELSE, IF_CLOSE]
+                .into_iter()
+                .filter_map(|marker| {
+                    find_unescaped(&value[search_start..], marker).map(|idx| (idx, marker))
+                })
+                .min_by_key(|(idx, _)| *idx)?;
+            let idx = search_start + rel_idx;
+            search_start = idx + marker.len();
+
+            match marker {
+                IF_OPEN => depth += 1,
+                IF_ELSE if depth == 0 && else_idx.is_none() => else_idx = Some(idx),
+                IF_CLOSE if depth == 0 => {
+                    let after = &value[search_start..];
+                    return Some(match else_idx {
+                        Some(else_idx) => (
+                            &value[..else_idx],
+                            Some(&value[else_idx + IF_ELSE.len()..idx]),
+                            after,
+                        ),
+                        None => (&value[..idx], None, after),
+                    });
+                }
+                IF_CLOSE => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Parse the first `{{#if var}}...{{/if}}` or `{{#if var}}...{{else}}...{{/if}}` condition.
+    ///
+    /// Components starting before the condition are left to `find_component`, the condition is then parsed in their inner value or after them.
+    pub fn find_condition(ctx: &ParseContext, value: &str) -> Option<Result<Self>> {
+        let (before, rest) = split_once_unescaped(value, IF_OPEN)?;
+        if let Some((_, comp_before, ..)) = Self::find_valid_component(value)
+            && comp_before.map_or(0, str::len) < before.len()
+        {
+            return None;
+        }
+        let (ident, rest) = rest.split_once("}}")?;
+        let condition_key = Key::new(&format!("var_{}", ident.trim()))?;
+
+        let Some((then, otherwise, after)) = Self::split_condition_branches(rest) else {
+            return Some(Err(Error::UnclosedCondition { loc: ctx.into() }.into()));
+        };
+
+        let this = ParsedValue::Condition(Condition {
+            condition_key,
+            then: Box::new(nested_result_try!(Self::new(ctx, &before_delimiter(then)))),
+            otherwise: Box::new(nested_result_try!(Self::new(
+                ctx,
+                &before_delimiter(otherwise.unwrap_or(""))
+            ))),
+        });
+
+        let before = nested_result_try!(Self::new(ctx, &before_delimiter(before)));
+        let after = nested_result_try!(Self::new(ctx, after));
+
+        Some(Ok(ParsedValue::Bloc(vec![before, this, after])))
+    }
+
+    /// Parse the first `<comp>inner</comp>` or `<comp />` component.
+    pub fn find_component(ctx: &ParseContext, value: &str) -> Option<Result<Self>> {
         let (key, before, between, after, attrs) = Self::find_valid_component(value)?;
 
         let mut values = Vec::new();
@@ -610,13 +902,16 @@ impl ParsedValue {
             && !before.is_empty()
         {
             values.push(ParsedValue::Literal(Literal::String(
-                before.to_string(),
+                unescape(&before_delimiter(before)),
                 usize::MAX,
             )));
         }
 
         let inner = match between {
-            Some(between) => Some(Box::new(nested_result_try!(ParsedValue::new(ctx, between)))),
+            Some(between) => Some(Box::new(nested_result_try!(ParsedValue::new(
+                ctx,
+                &before_delimiter(between)
+            )))),
             None => None,
         };
 
@@ -647,7 +942,7 @@ impl ParsedValue {
         let mut depth = 0usize;
         let mut search_start = 0;
 
-        while let Some(rel_open) = value[search_start..].find('<') {
+        while let Some(rel_open) = find_unescaped(&value[search_start..], "<") {
             let open_idx = search_start + rel_open;
             let Some(rel_close) = value[open_idx..].find('>') else {
                 break;
@@ -681,7 +976,7 @@ impl ParsedValue {
     }
 
     fn find_opening_tag(value: &str) -> Option<(&str, &str, &str, usize, bool)> {
-        let open_idx = value.find('<')?;
+        let open_idx = find_unescaped(value, "<")?;
         let close_idx = value[open_idx..].find('>')? + open_idx;
 
         let before = &value[..open_idx];
@@ -767,9 +1062,23 @@ impl ParsedValue {
         match self {
             ParsedValue::Variable { .. }
             | ParsedValue::Literal(_)
+            | ParsedValue::List(_)
             | ParsedValue::Default
             | ParsedValue::Dummy(_) => Ok(()),
-            ParsedValue::Subkeys(_) => Ok(()), // unreachable ?
+            ParsedValue::Subkeys(None) => Ok(()),
+            // the target of a foreign key aliasing subkeys, its own foreign keys are resolved before it is copied
+            ParsedValue::Subkeys(Some(subkeys)) => {
+                let mut key_path = loc.key_path.clone();
+                for (key, value) in &subkeys.keys {
+                    let key_path = key_path.push_key(key.clone());
+                    let loc = Loc {
+                        locale: loc.locale,
+                        key_path: &key_path,
+                    };
+                    value.resolve_foreign_key(values, &loc, default_locale)?;
+                }
+                Ok(())
+            }
             ParsedValue::Ranges(inner) => inner.resolve_foreign_keys(values, loc, default_locale),
             ParsedValue::Component {
                 inner, attributes, ..
@@ -798,6 +1107,20 @@ impl ParsedValue {
                 }
                 other.resolve_foreign_key(values, loc, default_locale)
             }
+            ParsedValue::Select(Select {
+                variants, other, ..
+            }) => {
+                for value in variants.values() {
+                    value.resolve_foreign_key(values, loc, default_locale)?;
+                }
+                other.resolve_foreign_key(values, loc, default_locale)
+            }
+            ParsedValue::Condition(condition) => {
+                for value in condition.branches() {
+                    value.resolve_foreign_key(values, loc, default_locale)?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -811,13 +1134,11 @@ impl ParsedValue {
             ParsedValue::Default
             | ParsedValue::ForeignKey(_)
             | ParsedValue::Literal(_)
+            | ParsedValue::List(_)
             | ParsedValue::Dummy(_) => Ok(self.clone()),
-            ParsedValue::Variable { key, bounds } => match args.get(&*key.name) {
+            ParsedValue::Variable { key, .. } => match args.get(&*key.name) {
                 Some(value) => Ok(value.clone()),
-                None => Ok(ParsedValue::Variable {
-                    key: key.clone(),
-                    bounds: bounds.clone(),
-                }),
+                None => Ok(self.clone()),
             },
             ParsedValue::Component {
                 key,
@@ -842,6 +1163,9 @@ impl ParsedValue {
                 .map(ParsedValue::Bloc),
             ParsedValue::Ranges(ranges) => ranges.populate(args, foreign_key, loc),
             ParsedValue::Plurals(plurals) => plurals.populate(args, foreign_key, loc),
+            ParsedValue::Select(select) => select.populate(args, foreign_key, loc),
+            ParsedValue::Condition(condition) => condition.populate(args, foreign_key, loc),
+            ParsedValue::Subkeys(_) if args.is_empty() => Ok(self.clone()),
             ParsedValue::Subkeys(_) => Err(Error::InvalidForeignKey {
                 foreign_key: foreign_key.to_owned(),
                 loc: loc.into(),
@@ -876,6 +1200,7 @@ impl ParsedValue {
                     keys: dummy_keys,
                     strings: vec![],
                     top_locale_string_count: 0,
+                    metadata: BTreeMap::new(),
                 };
                 *this = ParsedValue::Subkeys(None);
 
@@ -902,6 +1227,37 @@ impl ParsedValue {
                 locales.push(loc);
                 Ok(())
             }
+            (
+                ParsedValue::List(items),
+                LocaleValue::Value {
+                    value: InterpolOrLit::Lit(LiteralType::List(expected)),
+                    ..
+                },
+            ) => {
+                for item in items.iter_mut() {
+                    item.index_strings(strings);
+                }
+                if items.len() != *expected {
+                    diag.emit_warning(Warning::ListLengthMismatch {
+                        loc: Location::new(top_locale, key_path.clone()),
+                        len: items.len(),
+                        expected: *expected,
+                    });
+                }
+                Ok(())
+            }
+            (ParsedValue::List(_), LocaleValue::Value { .. })
+            | (
+                ParsedValue::Literal(_),
+                LocaleValue::Value {
+                    value: InterpolOrLit::Lit(LiteralType::List(_)),
+                    ..
+                },
+            ) => Err(Error::ListMismatch {
+                locale: top_locale,
+                key_path: key_path.clone(),
+            }
+            .into()),
             (
                 ParsedValue::Literal(lit),
                 LocaleValue::Value {
@@ -928,6 +1284,8 @@ impl ParsedValue {
                 | ParsedValue::Ranges(_)
                 | ParsedValue::Variable { .. }
                 | ParsedValue::Plurals(_)
+                | ParsedValue::Select(_)
+                | ParsedValue::Condition(_)
                 | ParsedValue::ForeignKey(_)
                 | ParsedValue::Dummy(_),
                 LocaleValue::Value {
@@ -955,6 +1313,7 @@ impl ParsedValue {
             }
             ParsedValue::Variable { .. }
             | ParsedValue::Literal(_)
+            | ParsedValue::List(_)
             | ParsedValue::Default
             | ParsedValue::Dummy(_) => {}
             ParsedValue::ForeignKey(foreign_key) => {
@@ -1005,15 +1364,33 @@ impl ParsedValue {
                     value.reduce();
                 }
             }
+            ParsedValue::Select(Select {
+                variants, other, ..
+            }) => {
+                for value in variants.values_mut().chain(Some(&mut **other)) {
+                    value.reduce();
+                }
+            }
+            ParsedValue::Condition(condition) => {
+                for value in condition.branches_mut() {
+                    value.reduce();
+                }
+            }
         }
     }
 
     pub fn reduce_into(self, bloc: &mut Vec<Self>) {
         match self {
-            ParsedValue::Default => {}    // default in a bloc ? skip
-            ParsedValue::Subkeys(_) => {} // same for subkeys
-            ParsedValue::Dummy(_) => {}   // Dummies are already reduced
-            mut plurals_like @ (ParsedValue::Ranges(_) | ParsedValue::Plurals(_)) => {
+            ParsedValue::Default => {} // default in a bloc ? skip
+            // a foreign key to subkeys mixed with other values, reported by `get_keys_inner`
+            subkeys @ ParsedValue::Subkeys(_) => bloc.push(subkeys),
+            // same for lists
+            list @ ParsedValue::List(_) => bloc.push(list),
+            ParsedValue::Dummy(_) => {} // Dummies are already reduced
+            mut plurals_like @ (ParsedValue::Ranges(_)
+            | ParsedValue::Plurals(_)
+            | ParsedValue::Select(_)
+            | ParsedValue::Condition(_)) => {
                 plurals_like.reduce();
                 bloc.push(plurals_like);
             }
@@ -1033,9 +1410,7 @@ impl ParsedValue {
                     bloc.push(ParsedValue::Literal(s));
                 }
             }
-            ParsedValue::Variable { key, bounds } => {
-                bloc.push(ParsedValue::Variable { key, bounds })
-            }
+            variable @ ParsedValue::Variable { .. } => bloc.push(variable),
             ParsedValue::Component {
                 key,
                 mut inner,
@@ -1099,10 +1474,34 @@ impl ParsedValue {
             ParsedValue::Literal(lit_type) if is_top => {
                 *keys = InterpolOrLit::Lit(lit_type.get_type());
             }
-            ParsedValue::Literal(_) | ParsedValue::Subkeys(_) | ParsedValue::Default => {}
-            ParsedValue::Variable { key, bounds } => {
-                keys.get_interpol_keys_mut()
-                    .push_var(key.clone(), bounds.clone());
+            ParsedValue::List(items) if is_top => {
+                *keys = InterpolOrLit::Lit(LiteralType::List(items.len()));
+            }
+            ParsedValue::List(_) => {
+                return Err(Error::ListInValue {
+                    key_path: key_path.clone(),
+                }
+                .into());
+            }
+            ParsedValue::Literal(_) | ParsedValue::Default => {}
+            // only a whole value can alias subkeys
+            ParsedValue::Subkeys(_) => {
+                return Err(Error::SubkeysAliasInValue {
+                    key_path: key_path.clone(),
+                }
+                .into());
+            }
+            ParsedValue::Variable {
+                key,
+                bounds,
+                default,
+            } => {
+                keys.get_interpol_keys_mut().push_var_with_default(
+                    key_path,
+                    key.clone(),
+                    bounds.clone(),
+                    default.as_deref(),
+                )?;
             }
             ParsedValue::Component {
                 key,
@@ -1155,6 +1554,7 @@ impl ParsedValue {
                 forms,
                 other,
                 count_key,
+                offset,
                 ..
             }) => {
                 keys.get_interpol_keys_mut().push_count(
@@ -1162,11 +1562,37 @@ impl ParsedValue {
                     RangeOrPlural::Plural,
                     count_key.clone(),
                 )?;
+                if *offset != 0 {
+                    keys.get_interpol_keys_mut()
+                        .push_plural_offset(key_path, count_key.clone())?;
+                }
                 for value in forms.values() {
                     value.get_keys_inner(key_path, keys, false)?;
                 }
                 other.get_keys_inner(key_path, keys, false)?;
             }
+            ParsedValue::Select(Select {
+                select_key,
+                other,
+                variants,
+            }) => {
+                keys.get_interpol_keys_mut().push_select(
+                    key_path,
+                    select_key.clone(),
+                    variants.keys().cloned(),
+                )?;
+                for value in variants.values() {
+                    value.get_keys_inner(key_path, keys, false)?;
+                }
+                other.get_keys_inner(key_path, keys, false)?;
+            }
+            ParsedValue::Condition(condition) => {
+                keys.get_interpol_keys_mut()
+                    .push_condition(key_path, condition.condition_key.clone())?;
+                for value in condition.branches() {
+                    value.get_keys_inner(key_path, keys, false)?;
+                }
+            }
         }
         Ok(())
     }
@@ -1183,6 +1609,11 @@ impl ParsedValue {
             ParsedValue::Literal(lit) => {
                 lit.index_strings(strings);
             }
+            ParsedValue::List(items) => {
+                for item in items {
+                    item.index_strings(strings);
+                }
+            }
             ParsedValue::Ranges(ranges) => ranges.index_strings(strings),
             ParsedValue::Component {
                 inner, attributes, ..
@@ -1193,6 +1624,8 @@ impl ParsedValue {
                 attributes.index_strings(strings);
             }
             ParsedValue::Plurals(plurals) => plurals.index_strings(strings),
+            ParsedValue::Select(select) => select.index_strings(strings),
+            ParsedValue::Condition(condition) => condition.index_strings(strings),
             ParsedValue::Bloc(vec) => {
                 for value in vec {
                     value.index_strings(strings);
@@ -1420,6 +1853,8 @@ pub struct ParsedValueSeed<'a> {
     pub foreign_keys_paths: &'a ForeignKeysPaths,
     pub diag: &'a Diagnostics,
     pub formatters: &'a Formatters,
+    pub parse_fns: &'a [ParseFn],
+    pub key_mapping: &'a KeyMapping,
 }
 
 impl<'de> serde::de::DeserializeSeed<'de> for ParsedValueSeed<'_> {
@@ -1448,7 +1883,8 @@ impl<'de> serde::de::Visitor<'de> for ParsedValueSeed<'_> {
             foreign_keys_paths: self.foreign_keys_paths,
             diag: self.diag,
             formatters: self.formatters,
-            parse_fns: ParsedValue::DEFAULT_FNS,
+            parse_fns: self.parse_fns,
+            key_mapping: self.key_mapping,
         };
         let pv = ParsedValue::new(&ctx, v);
 
@@ -1491,7 +1927,7 @@ impl<'de> serde::de::Visitor<'de> for ParsedValueSeed<'_> {
         Ok(ParsedValue::Literal(Literal::Unsigned(v)))
     }
 
-    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
     where
         A: serde::de::MapAccess<'de>,
     {
@@ -1499,18 +1935,120 @@ impl<'de> serde::de::Visitor<'de> for ParsedValueSeed<'_> {
             return Err(serde::de::Error::custom(Error::RangeSubkeys));
         }
 
-        let map_de = MapAccessDeserializer::new(map);
+        let mut key_path = self.key_path.to_owned();
+        let mut keys = BTreeMap::new();
+        let mut keys_metadata = BTreeMap::new();
+        let mut marker = None;
+        let mut offset = None;
+
+        while let Some(locale_key) = map.next_key::<String>()? {
+            if let Some(target) = locale_key.strip_prefix(METADATA_PREFIX) {
+                if let Some(key_metadata) = metadata::next_metadata(&mut map, target)? {
+                    let target = Key::try_with_mapping(target, self.key_mapping)
+                        .map_err(serde::de::Error::custom)?;
+                    keys_metadata.insert(target, key_metadata);
+                }
+                continue;
+            }
+            if locale_key == OFFSET_MARKER {
+                offset = Some(map.next_value::<u64>()?);
+                continue;
+            }
+            let marker_kind = match locale_key.as_str() {
+                SELECT_MARKER => Some(None),
+                PLURAL_MARKER => Some(Some(PluralRuleType::Cardinal)),
+                ORDINAL_MARKER => Some(Some(PluralRuleType::Ordinal)),
+                _ => None,
+            };
+            if let Some(marker_kind) = marker_kind {
+                let var_name = map.next_value::<String>()?;
+                let key = Key::try_new(&format!("var_{}", var_name.trim()))
+                    .map_err(serde::de::Error::custom)?;
+                if marker.replace((marker_kind, key)).is_some() {
+                    let loc = Loc {
+                        locale: self.top_locale_name,
+                        key_path: self.key_path,
+                    };
+                    return Err(serde::de::Error::custom(Error::ConflictingMarkers {
+                        loc: loc.into(),
+                    }));
+                }
+                continue;
+            }
+            let locale_key = Key::try_with_mapping(&locale_key, self.key_mapping)
+                .or_else(|err| plurals::plural_key(&locale_key, self.key_mapping).ok_or(err))
+                .map_err(serde::de::Error::custom)?;
+            let pushed_key = key_path.push_key(locale_key.clone());
+            let value = map.next_value_seed(ParsedValueSeed {
+                key: &locale_key,
+                key_path: &pushed_key,
+                ..self
+            })?;
+            keys.insert(locale_key, value);
+        }
 
-        let seed = LocaleSeed {
-            name: self.key.clone(),
-            top_locale_name: self.top_locale_name.clone(),
-            key_path: self.key_path.to_owned(),
+        let loc = Loc {
+            locale: self.top_locale_name,
+            key_path: self.key_path,
+        };
+        match (marker, offset) {
+            (Some((Some(rule_type), var_key)), offset) => {
+                return Plurals::new(
+                    rule_type,
+                    var_key,
+                    keys,
+                    offset.unwrap_or_default(),
+                    &loc,
+                    self.diag,
+                )
+                .map(ParsedValue::Plurals)
+                .map_err(serde::de::Error::custom);
+            }
+            (_, Some(_)) => {
+                return Err(serde::de::Error::custom(Error::OffsetWithoutPlurals {
+                    loc: loc.into(),
+                }));
+            }
+            (Some((None, var_key)), None) => {
+                return Select::new(var_key, keys, &loc)
+                    .map(ParsedValue::Select)
+                    .map_err(serde::de::Error::custom);
+            }
+            (None, None) => {}
+        }
+
+        // exact forms such as `=0` only have a valid key inside plural maps.
+        if let Some(key) = keys
+            .keys()
+            .find(|key| key.name.starts_with(plurals::EXACT_FORM_PREFIX))
+        {
+            return Err(serde::de::Error::custom(Error::InvalidKey(
+                key.name.to_string(),
+            )));
+        }
+
+        let ctx = ParseContext {
+            loc: Loc {
+                key_path: self.key_path,
+                locale: self.top_locale_name,
+            },
             foreign_keys_paths: self.foreign_keys_paths,
             diag: self.diag,
             formatters: self.formatters,
+            parse_fns: self.parse_fns,
+            key_mapping: self.key_mapping,
         };
+        metadata::insert_aliases(&mut keys, &keys_metadata, &ctx)
+            .map_err(serde::de::Error::custom)?;
 
-        seed.deserialize(map_de).map(Some).map(ParsedValue::Subkeys)
+        Ok(ParsedValue::Subkeys(Some(Locale {
+            name: self.key.clone(),
+            keys,
+            top_locale_name: self.top_locale_name.clone(),
+            strings: vec![],
+            top_locale_string_count: 0,
+            metadata: keys_metadata,
+        })))
     }
 
     fn visit_unit<E>(self) -> std::result::Result<Self::Value, E>
@@ -1524,13 +2062,31 @@ impl<'de> serde::de::Visitor<'de> for ParsedValueSeed<'_> {
     where
         A: serde::de::SeqAccess<'de>,
     {
+        let in_range = std::mem::replace(&mut self.in_range, true);
+        let ranges = match Ranges::from_serde_seq(map, self)? {
+            RangesOrList::Ranges(ranges) => ranges,
+            RangesOrList::List(_) if in_range => {
+                return Err(serde::de::Error::custom(Error::ListInRange));
+            }
+            RangesOrList::List(items) => {
+                return items
+                    .into_iter()
+                    .map(|item| match self.visit_str(&item)? {
+                        ParsedValue::Literal(lit @ Literal::String(..)) => Ok(lit),
+                        // the parse error is already reported
+                        ParsedValue::Dummy(_) => Ok(Literal::String(item, usize::MAX)),
+                        _ => Err(serde::de::Error::custom(Error::InvalidListItem(item))),
+                    })
+                    .collect::<Result<_, _>>()
+                    .map(ParsedValue::List);
+            }
+        };
         self.diag.set_has_ranges();
         // nested ranges are not allowed, the code technically supports it,
         // but it's pointless and probably nobody will ever needs it.
-        if std::mem::replace(&mut self.in_range, true) {
+        if in_range {
             return Err(serde::de::Error::custom(Error::NestedRanges));
         }
-        let ranges = Ranges::from_serde_seq(map, self)?;
 
         let (invalid_fallback, fallback_count, should_have_fallback) =
             ranges.check_deserialization();
@@ -1551,7 +2107,7 @@ impl<'de> serde::de::Visitor<'de> for ParsedValueSeed<'_> {
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             formatter,
-            "either a string, a sequence of ranges or a map of subkeys"
+            "either a string, a sequence of ranges, a list of strings or a map of subkeys"
         )
     }
 }
@@ -1625,6 +2181,10 @@ mod tests {
     use super::*;
 
     fn new_parsed_value(value: &str) -> ParsedValue {
+        try_new_parsed_value(value).unwrap()
+    }
+
+    fn try_new_parsed_value(value: &str) -> Result<ParsedValue> {
         let key_path = KeyPath::new(None);
         let locale = new_key("locale_key");
         let foreign_keys_paths = ForeignKeysPaths::new();
@@ -1640,22 +2200,49 @@ mod tests {
             diag: &diag,
             formatters: &formatters,
             parse_fns: ParsedValue::DEFAULT_FNS,
+            key_mapping: &KeyMapping::new(),
         };
 
-        let p = ParsedValue::new(&ctx, value).unwrap();
+        let p = ParsedValue::new(&ctx, value)?;
         if let Some(err) = diag.errors().first() {
             panic!("{err}");
         }
         if let Some(warning) = diag.warnings().first() {
             panic!("{warning}");
         }
-        p
+        Ok(p)
     }
 
     fn new_key(key: &str) -> Key {
         Key::new(key).unwrap()
     }
 
+    fn deserialize_value(json: &str) -> Result<ParsedValue, serde_json::Error> {
+        deserialize_value_for("locale_key", json)
+    }
+
+    fn deserialize_value_for(locale: &str, json: &str) -> Result<ParsedValue, serde_json::Error> {
+        let key_path = KeyPath::new(None);
+        let locale = new_key(locale);
+        let key = new_key("key");
+        let foreign_keys_paths = ForeignKeysPaths::new();
+        let diag = Diagnostics::new();
+        let formatters = Formatters::new();
+
+        let seed = ParsedValueSeed {
+            top_locale_name: &locale,
+            in_range: false,
+            key_path: &key_path,
+            key: &key,
+            foreign_keys_paths: &foreign_keys_paths,
+            diag: &diag,
+            formatters: &formatters,
+            parse_fns: ParsedValue::DEFAULT_FNS,
+            key_mapping: &KeyMapping::new(),
+        };
+        serde::de::DeserializeSeed::deserialize(seed, &mut serde_json::Deserializer::from_str(json))
+    }
+
     #[test]
     fn parse_normal_string() {
         let value = new_parsed_value("test");
@@ -1676,13 +2263,262 @@ mod tests {
                 ParsedValue::Literal(Literal::String("before ".to_string(), usize::MAX)),
                 ParsedValue::Variable {
                     key: new_key("var_var"),
-                    bounds: VarBounds::None
+                    bounds: VarBounds::None,
+                    default: None,
                 },
                 ParsedValue::Literal(Literal::String(" after".to_string(), usize::MAX))
             ])
         )
     }
 
+    #[test]
+    fn parse_escaped_delimiters() {
+        let lit = |s: &str| ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX));
+
+        let value = new_parsed_value(r"\{{ not_a_var }} and {{ var }}");
+        assert_eq!(
+            value,
+            ParsedValue::Bloc(vec![
+                lit("{{ not_a_var }} and "),
+                ParsedValue::Variable {
+                    key: new_key("var_var"),
+                    bounds: VarBounds::None,
+                    default: None,
+                },
+                lit("")
+            ])
+        );
+
+        let value = new_parsed_value(r"\<b>bold\</b> \$t(key) \\\{{ C:\dir");
+        assert_eq!(value, lit(r"<b>bold</b> $t(key) \{{ C:\dir"));
+
+        // only the escape characters before a delimiter are removed
+        for value in [r"\$5", r"\}", r"\>", r"C:\\dir", r"\< 3", r"a\\"] {
+            assert_eq!(new_parsed_value(value), lit(value));
+        }
+
+        let value = new_parsed_value(r"<b>a \< b</b>");
+        assert_eq!(
+            value,
+            ParsedValue::Bloc(vec![ParsedValue::Component {
+                key: new_key("comp_b"),
+                inner: Some(Box::new(lit("a < b"))),
+                attributes: Attributes::default(),
+            }])
+        );
+
+        let value = new_parsed_value(r"\\{{ var }}");
+        assert_eq!(
+            value,
+            ParsedValue::Bloc(vec![
+                lit("\\"),
+                ParsedValue::Variable {
+                    key: new_key("var_var"),
+                    bounds: VarBounds::None,
+                    default: None,
+                },
+                lit("")
+            ])
+        );
+
+        let value = new_parsed_value(r"<b>a\\</b>\\{{ var }}");
+        assert_eq!(
+            value,
+            ParsedValue::Bloc(vec![
+                ParsedValue::Component {
+                    key: new_key("comp_b"),
+                    inner: Some(Box::new(lit("a\\"))),
+                    attributes: Attributes::default(),
+                },
+                lit("\\"),
+                ParsedValue::Variable {
+                    key: new_key("var_var"),
+                    bounds: VarBounds::None,
+                    default: None,
+                },
+                lit("")
+            ])
+        );
+
+        for value in [r"{{ a }} <b> $t( \", r"\{{ \\<b> $5 \} C:\\dir"] {
+            let mut escaped = String::new();
+            escape(value, &mut escaped);
+            assert_eq!(unescape(&escaped), value);
+        }
+        assert_eq!(
+            ParsedValue::new_dummy(r"\{{ var }}"),
+            ParsedValue::Dummy(vec![])
+        );
+    }
+
+    #[test]
+    fn parse_variable_default() {
+        let value = new_parsed_value(r#"Hi {{ name = "my friend" }}!"#);
+        assert_eq!(
+            value,
+            ParsedValue::Bloc(vec![
+                ParsedValue::Literal(Literal::String("Hi ".to_string(), usize::MAX)),
+                ParsedValue::Variable {
+                    key: new_key("var_name"),
+                    bounds: VarBounds::None,
+                    default: Some("my friend".to_string()),
+                },
+                ParsedValue::Literal(Literal::String("!".to_string(), usize::MAX))
+            ])
+        );
+
+        let mut key_path = KeyPath::new(None);
+        let keys = value.get_keys(&mut key_path).unwrap();
+        let (_, var) = keys.is_interpol().unwrap().iter_vars().next().unwrap();
+        assert_eq!(var.default.as_deref(), Some("my friend"));
+
+        // every use must agree on the default
+        let mut keys = InterpolOrLit::Lit(LiteralType::String);
+        value
+            .get_keys_inner(&mut key_path, &mut keys, false)
+            .unwrap();
+        let other_locale = new_parsed_value("Salut {{ name }} !");
+        let err = other_locale
+            .get_keys_inner(&mut key_path, &mut keys, false)
+            .unwrap_err();
+        assert!(matches!(err.into_inner(), Error::VarDefaultMismatch { .. }));
+
+        let err = try_new_parsed_value(r#"{{ name, number = "0" }}"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("both a formatter and a default value")
+        );
+        let err = try_new_parsed_value("{{ name = friend }}").unwrap_err();
+        assert!(err.to_string().contains("expected a quoted string"));
+    }
+
+    #[test]
+    fn parse_variable_type() {
+        let value = new_parsed_value("{{ count: u32 }} new {{ name : str }}");
+        assert_eq!(
+            value,
+            ParsedValue::Bloc(vec![
+                ParsedValue::Literal(Literal::String("".to_string(), usize::MAX)),
+                ParsedValue::Variable {
+                    key: new_key("var_count"),
+                    bounds: VarBounds::Typed(VarType::new("u32").unwrap()),
+                    default: None,
+                },
+                ParsedValue::Bloc(vec![
+                    ParsedValue::Literal(Literal::String(" new ".to_string(), usize::MAX)),
+                    ParsedValue::Variable {
+                        key: new_key("var_name"),
+                        bounds: VarBounds::Typed(VarType::new("str").unwrap()),
+                        default: None,
+                    },
+                    ParsedValue::Literal(Literal::String("".to_string(), usize::MAX))
+                ])
+            ])
+        );
+
+        // `date`, `time` and `datetime` are their formatter with the default options
+        #[cfg(feature = "format_datetime")]
+        {
+            let value = new_parsed_value("{{ when: date }}");
+            let ParsedValue::Bloc(values) = value else {
+                panic!("expected a bloc, got {value:?}");
+            };
+            assert!(matches!(
+                &values[1],
+                ParsedValue::Variable { bounds, .. } if bounds.is::<crate::formatters::datetime::DateFormatter>()
+            ));
+        }
+
+        // every use must agree on the type
+        let mut key_path = KeyPath::new(None);
+        let mut keys = InterpolOrLit::Lit(LiteralType::String);
+        new_parsed_value("{{ count: u32 }}")
+            .get_keys_inner(&mut key_path, &mut keys, false)
+            .unwrap();
+        new_parsed_value("{{ count }}")
+            .get_keys_inner(&mut key_path, &mut keys, false)
+            .unwrap();
+        let err = new_parsed_value("{{ count: i64 }}")
+            .get_keys_inner(&mut key_path, &mut keys, false)
+            .unwrap_err();
+        assert!(matches!(err.into_inner(), Error::VarTypeMismatch { .. }));
+
+        let err = try_new_parsed_value("{{ count: u31 }}").unwrap_err();
+        assert!(matches!(err.into_inner(), Error::UnknownVarType { name, .. } if name == "u31"));
+        let err = try_new_parsed_value("{{ count: u32, number }}").unwrap_err();
+        assert!(err.to_string().contains("both a type and a formatter"));
+        let err = try_new_parsed_value(r#"{{ name: str = "friend" }}"#).unwrap_err();
+        assert!(err.to_string().contains("both a type and a default value"));
+    }
+
+    #[test]
+    fn parse_condition() {
+        let lit = |s: &str| ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX));
+        let mut value =
+            new_parsed_value("Hello {{ name }}{{#if premium}} <badge>Pro</badge>{{else}}!{{/if}}");
+        value.reduce();
+        assert_eq!(
+            value,
+            ParsedValue::Bloc(vec![
+                lit("Hello "),
+                ParsedValue::Variable {
+                    key: new_key("var_name"),
+                    bounds: VarBounds::None,
+                    default: None,
+                },
+                ParsedValue::Condition(Condition {
+                    condition_key: new_key("var_premium"),
+                    then: Box::new(ParsedValue::Bloc(vec![
+                        lit(" "),
+                        ParsedValue::Component {
+                            key: new_key("comp_badge"),
+                            inner: Some(Box::new(lit("Pro"))),
+                            attributes: Attributes::default(),
+                        }
+                    ])),
+                    otherwise: Box::new(lit("!")),
+                }),
+            ])
+        );
+
+        let mut value = new_parsed_value("<b>{{#if a}}x{{#if b}}y{{/if}}{{/if}}</b>");
+        value.reduce();
+        let nested = ParsedValue::Condition(Condition {
+            condition_key: new_key("var_b"),
+            then: Box::new(lit("y")),
+            otherwise: Box::new(lit("")),
+        });
+        assert_eq!(
+            value,
+            ParsedValue::Component {
+                key: new_key("comp_b"),
+                inner: Some(Box::new(ParsedValue::Condition(Condition {
+                    condition_key: new_key("var_a"),
+                    then: Box::new(ParsedValue::Bloc(vec![lit("x"), nested])),
+                    otherwise: Box::new(lit("")),
+                }))),
+                attributes: Attributes::default(),
+            }
+        );
+
+        let mut key_path = KeyPath::new(None);
+        let keys = value.get_keys(&mut key_path).unwrap();
+        let conditions = keys
+            .is_interpol()
+            .unwrap()
+            .iter_vars()
+            .filter(|(_, infos)| infos.condition)
+            .count();
+        assert_eq!(conditions, 2);
+
+        let err = try_new_parsed_value("{{#if a}} never closed").unwrap_err();
+        assert!(matches!(err.into_inner(), Error::UnclosedCondition { .. }));
+        assert_eq!(
+            new_parsed_value(r"\{{#if a}} escaped"),
+            lit("{{#if a}} escaped")
+        );
+    }
+
     #[test]
     fn parse_comp() {
         let value =
@@ -1779,4 +2615,84 @@ mod tests {
             ])
         )
     }
+
+    #[test]
+    fn parse_select_object() {
+        let value = deserialize_value(
+            r#"{ "$select": "gender", "male": "He", "female": "She", "other": "They" }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            value,
+            ParsedValue::Select(Select {
+                select_key: new_key("var_gender"),
+                other: Box::new(ParsedValue::Literal(Literal::String(
+                    "They".to_string(),
+                    usize::MAX
+                ))),
+                variants: BTreeMap::from([
+                    (
+                        new_key("female"),
+                        ParsedValue::Literal(Literal::String("She".to_string(), usize::MAX))
+                    ),
+                    (
+                        new_key("male"),
+                        ParsedValue::Literal(Literal::String("He".to_string(), usize::MAX))
+                    ),
+                ]),
+            })
+        );
+
+        let err = deserialize_value(r#"{ "$select": "gender", "male": "He" }"#).unwrap_err();
+        assert!(err.to_string().contains("other"));
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn parse_plural_object() {
+        use crate::parse_locales::plurals::PluralForm;
+
+        let lit = |s: &str| ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX));
+        let value = deserialize_value_for(
+            "en",
+            r#"{ "$plural": "n", "one": "one item", "other": "many items" }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            ParsedValue::Plurals(Plurals {
+                rule_type: PluralRuleType::Cardinal,
+                count_key: new_key("var_n"),
+                other: Box::new(lit("many items")),
+                forms: BTreeMap::from([(PluralForm::One, lit("one item"))]),
+                offset: 0,
+            })
+        );
+
+        let value = deserialize_value_for(
+            "en",
+            r#"{ "$ordinal": "rank", "one": "st", "two": "nd", "few": "rd", "other": "th" }"#,
+        )
+        .unwrap();
+        let ParsedValue::Plurals(plurals) = value else {
+            panic!("expected plurals");
+        };
+        assert_eq!(plurals.rule_type, PluralRuleType::Ordinal);
+        assert_eq!(plurals.forms.len(), 3);
+
+        let err =
+            deserialize_value_for("en", r#"{ "$plural": "count", "one": "one" }"#).unwrap_err();
+        assert!(err.to_string().contains("\"other\" form"));
+        let err =
+            deserialize_value_for("en", r#"{ "$plural": "count", "some": "a", "other": "b" }"#)
+                .unwrap_err();
+        assert!(err.to_string().contains("Invalid plural form \"some\""));
+        let err = deserialize_value_for(
+            "en",
+            r#"{ "$plural": "count", "$select": "gender", "other": "b" }"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("markers"));
+    }
 }
@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Display,
+    rc::Rc,
 };
 
 use fixed_decimal::{Decimal, FloatPrecision};
@@ -12,6 +13,8 @@ use icu_plurals::{
 use super::{
     StringIndexer,
     error::{Error, Result, Warning},
+    locale::Locale,
+    options::{KeyMapping, ParseOptions},
     parsed_value::Literal,
 };
 use crate::{
@@ -21,6 +24,65 @@ use crate::{
 
 use super::parsed_value::ParsedValue;
 
+/// Key marking a map as cardinal plurals, the value being the name of the count variable:
+/// `{ "$plural": "count", "one": "...", "other": "..." }`
+pub const PLURAL_MARKER: &str = "$plural";
+
+/// Key marking a map as ordinal plurals, the value being the name of the count variable:
+/// `{ "$ordinal": "rank", "one": "...", "two": "...", "few": "...", "other": "..." }`
+pub const ORDINAL_MARKER: &str = "$ordinal";
+
+/// Key of plural maps giving the offset subtracted from the count before selecting its plural category:
+/// `{ "$plural": "count", "$offset": 1, "=0": "...", "one": "...", "other": "..." }`
+pub const OFFSET_MARKER: &str = "$offset";
+
+/// Prefix of the exact value forms of plurals: `items_=0`
+pub const EXACT_FORM_PREFIX: char = '=';
+
+/// Separates the dimensions of suffixed keys with several counts: `summary_files_one.folders_other`
+pub const PLURAL_DIMENSIONS_SEPARATOR: char = '.';
+
+/// The base key and the count variables with their plural form of a suffixed key with several counts,
+/// `None` if the key has a single dimension.
+pub fn split_plural_dimensions(name: &str) -> Option<(&str, Vec<(&str, PluralForm)>)> {
+    let mut segments = name.split(PLURAL_DIMENSIONS_SEPARATOR);
+    let (rest, form) = segments.next()?.rsplit_once('_')?;
+    let (base_key, var) = rest.rsplit_once('_')?;
+    let mut dimensions = vec![(var, PluralForm::try_from_str(form)?)];
+    for segment in segments {
+        let (var, form) = segment.split_once('_')?;
+        dimensions.push((var, PluralForm::try_from_str(form)?));
+    }
+    let is_valid = !base_key.is_empty() && dimensions.iter().all(|(var, _)| !var.is_empty());
+    (is_valid && dimensions.len() > 1).then_some((base_key, dimensions))
+}
+
+/// A key for a plural key that would not be a valid identifier: a suffixed key with several counts,
+/// an exact value form such as `items_=0`, or a bare `=0` inside a plural map.
+/// Its identifier is the one of the base key as they are merged afterward.
+pub fn plural_key(name: &str, key_mapping: &KeyMapping) -> Option<Key> {
+    let name = name.trim();
+    let ident = match split_plural_dimensions(name) {
+        Some((base_key, _)) => Key::with_mapping(base_key, key_mapping)?.ident,
+        None => match name.rsplit_once('_') {
+            Some((base_key, form)) => {
+                PluralForm::try_from_str(form).filter(PluralForm::is_exact)?;
+                Key::with_mapping(base_key, key_mapping)?.ident
+            }
+            None => {
+                let PluralForm::Exact(value) = PluralForm::try_from_str(name)? else {
+                    return None;
+                };
+                Rc::new(quote::format_ident!("exact_{}", value))
+            }
+        },
+    };
+    Some(Key {
+        name: Rc::from(name),
+        ident,
+    })
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Plurals {
     pub rule_type: PluralRuleType,
@@ -30,6 +92,8 @@ pub struct Plurals {
     // but that makes `ParsedValue::Plurals(Plurals { .. })` impossible in match patterns.
     pub other: Box<ParsedValue>,
     pub forms: BTreeMap<PluralForm, ParsedValue>,
+    /// Subtracted from the count before selecting its plural category, exact forms match the count itself.
+    pub offset: u64,
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -40,6 +104,8 @@ pub enum PluralRuleType {
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PluralForm {
+    /// Matches a count of exactly this value, before the plural category is checked.
+    Exact(u64),
     Zero,
     One,
     Two,
@@ -57,10 +123,17 @@ impl PluralForm {
             "few" => Some(PluralForm::Few),
             "many" => Some(PluralForm::Many),
             "other" => Some(PluralForm::Other),
-            _ => None,
+            _ => s
+                .strip_prefix(EXACT_FORM_PREFIX)
+                .and_then(|value| value.parse().ok())
+                .map(PluralForm::Exact),
         }
     }
 
+    pub fn is_exact(&self) -> bool {
+        matches!(self, PluralForm::Exact(_))
+    }
+
     pub fn from_icu_category(cat: PluralCategory) -> Self {
         match cat {
             PluralCategory::Zero => PluralForm::Zero,
@@ -88,8 +161,8 @@ impl From<PluralRuleType> for IcuPluralRulesOptions {
     }
 }
 
-impl Plurals {
-    fn get_plural_rules(&self, locale: &Key) -> Result<PluralRules> {
+impl PluralRuleType {
+    pub fn get_plural_rules(self, locale: &Key) -> Result<PluralRules> {
         let locale =
             locale
                 .name
@@ -98,19 +171,153 @@ impl Plurals {
                     locale: locale.name.clone(),
                     err,
                 })?;
-        let plural_rules = PluralRules::try_new(locale.into(), self.rule_type.into())
-            .map_err(Error::PluralRulesError)?;
+        let plural_rules =
+            PluralRules::try_new(locale.into(), self.into()).map_err(Error::PluralRulesError)?;
 
         Ok(plural_rules)
     }
 
-    pub fn check_forms(&self, loc: &Loc, diag: &Diagnostics) -> Result<()> {
-        let plural_rules = self.get_plural_rules(loc.locale)?;
-        let forms = self.forms.keys().copied().collect::<BTreeSet<_>>();
-        let used_forms = plural_rules
+    /// The plural forms used by the given locale, in CLDR order (zero, one, two, few, many, other).
+    pub fn categories(self, locale: &Key) -> Result<Vec<PluralForm>> {
+        let plural_rules = self.get_plural_rules(locale)?;
+        let forms = plural_rules
             .categories()
             .map(PluralForm::from_icu_category)
             .collect::<BTreeSet<_>>();
+        Ok(forms.into_iter().collect())
+    }
+}
+
+impl Plurals {
+    /// Create plurals from a map of plural forms, the `other` form being the fallback.
+    pub fn new(
+        rule_type: PluralRuleType,
+        count_key: Key,
+        values: BTreeMap<Key, ParsedValue>,
+        offset: u64,
+        loc: &Loc,
+        diag: &Diagnostics,
+    ) -> Result<Self> {
+        if !cfg!(feature = "plurals") {
+            return Err(Error::DisabledPlurals { loc: loc.into() }.into());
+        }
+        let mut forms = BTreeMap::new();
+        for (key, value) in values {
+            let Some(form) = PluralForm::try_from_str(&key.name) else {
+                return Err(Error::InvalidPluralForm {
+                    loc: loc.into(),
+                    form: key.name.to_string(),
+                }
+                .into());
+            };
+            if matches!(value, ParsedValue::Subkeys(_)) {
+                return Err(Error::SubkeysInPlurals { loc: loc.into() }.into());
+            }
+            forms.insert(form, value);
+        }
+        let Some(other) = forms.remove(&PluralForm::Other) else {
+            return Err(Error::MissingPluralFallback { loc: loc.into() }.into());
+        };
+        let plurals = Plurals {
+            rule_type,
+            count_key,
+            other: Box::new(other),
+            forms,
+            offset,
+        };
+        plurals.check_forms(loc, diag)?;
+        Ok(plurals)
+    }
+
+    /// Create nested plurals from suffixed keys with several counts, each dimension being a level of plurals.
+    pub fn from_dimensions(
+        values: Vec<(Vec<(String, PluralForm)>, ParsedValue)>,
+        loc: &Loc,
+        diag: &Diagnostics,
+    ) -> Result<ParsedValue> {
+        if !cfg!(feature = "plurals") {
+            return Err(Error::DisabledPlurals { loc: loc.into() }.into());
+        }
+        let vars = values
+            .first()
+            .map(|(dimensions, _)| {
+                dimensions
+                    .iter()
+                    .map(|(var, _)| var.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let same_vars = values.iter().all(|(dimensions, _)| {
+            dimensions.len() == vars.len()
+                && dimensions.iter().zip(&vars).all(|((var, _), v)| var == v)
+        });
+        if !same_vars {
+            return Err(Error::PluralDimensionsMismatch { loc: loc.into() }.into());
+        }
+        let values = values
+            .into_iter()
+            .map(|(dimensions, value)| {
+                let forms = dimensions.into_iter().map(|(_, form)| form).collect();
+                (forms, value)
+            })
+            .collect();
+        Self::from_dimensions_inner(&vars, values, loc, diag)
+    }
+
+    fn from_dimensions_inner(
+        vars: &[String],
+        values: Vec<(Vec<PluralForm>, ParsedValue)>,
+        loc: &Loc,
+        diag: &Diagnostics,
+    ) -> Result<ParsedValue> {
+        let Some((var, rest_vars)) = vars.split_first() else {
+            return Ok(values
+                .into_iter()
+                .next()
+                .map(|(_, value)| value)
+                .unwrap_or_default());
+        };
+        let mut by_form: BTreeMap<PluralForm, Vec<(Vec<PluralForm>, ParsedValue)>> =
+            BTreeMap::new();
+        for (mut forms, value) in values {
+            let form = forms.remove(0);
+            by_form.entry(form).or_default().push((forms, value));
+        }
+        let mut forms = BTreeMap::new();
+        for (form, values) in by_form {
+            let value = Self::from_dimensions_inner(rest_vars, values, loc, diag)?;
+            forms.insert(form, value);
+        }
+        let Some(other) = forms.remove(&PluralForm::Other) else {
+            return Err(Error::MissingPluralFallback { loc: loc.into() }.into());
+        };
+        let plurals = Plurals {
+            rule_type: PluralRuleType::Cardinal,
+            count_key: Key::try_new(&format!("var_{var}"))?,
+            other: Box::new(other),
+            forms,
+            offset: 0,
+        };
+        plurals.check_forms(loc, diag)?;
+        Ok(ParsedValue::Plurals(plurals))
+    }
+
+    fn get_plural_rules(&self, locale: &Key) -> Result<PluralRules> {
+        self.rule_type.get_plural_rules(locale)
+    }
+
+    pub fn check_forms(&self, loc: &Loc, diag: &Diagnostics) -> Result<()> {
+        let forms = self
+            .forms
+            .keys()
+            .copied()
+            .filter(|form| !form.is_exact())
+            .collect::<BTreeSet<_>>();
+        let used_forms = self
+            .rule_type
+            .categories(loc.locale)?
+            .into_iter()
+            .collect::<BTreeSet<_>>();
         for form in forms.difference(&used_forms).copied() {
             diag.emit_warning(Warning::UnusedForm {
                 loc: loc.into(),
@@ -118,9 +325,83 @@ impl Plurals {
                 rule_type: self.rule_type,
             });
         }
+        if self.forms.keys().any(PluralForm::is_exact) {
+            for form in self.unreachable_forms(loc.locale)? {
+                diag.emit_warning(Warning::UnreachablePluralForm {
+                    loc: loc.into(),
+                    form,
+                });
+            }
+        }
         Ok(())
     }
 
+    /// The exact values of the count that are matched by an exact form.
+    pub fn exact_values(&self) -> impl Iterator<Item = u64> + '_ {
+        self.forms.keys().filter_map(|form| match form {
+            PluralForm::Exact(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// The categories whose sampled counts are all matched by exact forms first.
+    /// The samples are the integers up to 1000, some larger powers of ten and some decimals,
+    /// which is enough to reach every category of the CLDR rules.
+    fn covered_categories(&self, locale: &Key) -> Result<BTreeSet<PluralForm>> {
+        let exact_values = self.exact_values().collect::<BTreeSet<_>>();
+        if exact_values.is_empty() {
+            return Ok(BTreeSet::new());
+        }
+        let plural_rules = self.get_plural_rules(locale)?;
+        let integers = (0..=1000).chain((4..=9).map(|exp| 10u64.pow(exp)));
+        let mut reachable = BTreeSet::new();
+        let mut sampled = BTreeSet::new();
+        for count in integers {
+            let form = PluralForm::from_icu_category(plural_rules.category_for(count));
+            sampled.insert(form);
+            let is_exact = count
+                .checked_add(self.offset)
+                .is_some_and(|count| exact_values.contains(&count));
+            if !is_exact {
+                reachable.insert(form);
+            }
+        }
+        for count in (0..=100).flat_map(|i| [0.1, 0.25, 0.5].map(|fract| i as f64 + fract)) {
+            let count = Decimal::try_from_f64(count, FloatPrecision::RoundTrip)
+                .unwrap_at("covered_categories_1");
+            reachable.insert(PluralForm::from_icu_category(
+                plural_rules.category_for(&count),
+            ));
+        }
+        Ok(sampled.difference(&reachable).copied().collect())
+    }
+
+    /// The forms that are never used, as every count of their category is matched by an exact form first.
+    fn unreachable_forms(&self, locale: &Key) -> Result<Vec<PluralForm>> {
+        let covered = self.covered_categories(locale)?;
+        Ok(self
+            .forms
+            .keys()
+            .copied()
+            .filter(|form| covered.contains(form))
+            .collect())
+    }
+
+    /// The forms used by the locale that are not declared, their counts falling back to `other`.
+    pub fn missing_forms(&self, locale: &Key) -> Result<Vec<PluralForm>> {
+        let covered = self.covered_categories(locale)?;
+        Ok(self
+            .rule_type
+            .categories(locale)?
+            .into_iter()
+            .filter(|form| {
+                *form != PluralForm::Other
+                    && !self.forms.contains_key(form)
+                    && !covered.contains(form)
+            })
+            .collect())
+    }
+
     fn populate_with_new_key(
         &self,
         new_key: Key,
@@ -140,6 +421,7 @@ impl Plurals {
             count_key: new_key,
             other: Box::new(other),
             forms,
+            offset: self.offset,
         }))
     }
 
@@ -201,16 +483,26 @@ impl Plurals {
             Ok(cat)
         }
 
-        let category = match count_arg {
-            ParsedValue::Literal(Literal::Float(count)) => {
-                let count = Decimal::try_from_f64(*count, FloatPrecision::RoundTrip)
-                    .unwrap_at("populate_with_count_arg_1");
-                get_category(self, loc.locale, &count)
-            }
-            ParsedValue::Literal(Literal::Unsigned(count)) => {
-                get_category(self, loc.locale, *count)
-            }
-            ParsedValue::Literal(Literal::Signed(count)) => get_category(self, loc.locale, *count),
+        fn to_decimal(count: f64) -> Decimal {
+            Decimal::try_from_f64(count, FloatPrecision::RoundTrip)
+                .unwrap_at("populate_with_count_arg_1")
+        }
+
+        // exact forms match the count itself, the category is the one of the count minus the offset.
+        let offset = self.offset;
+        let (operands, adjusted_count) = match count_arg {
+            ParsedValue::Literal(Literal::Float(count)) => (
+                PluralOperands::from(&to_decimal(*count)),
+                Literal::Float(count - offset as f64),
+            ),
+            ParsedValue::Literal(Literal::Unsigned(count)) => (
+                PluralOperands::from(*count),
+                Literal::Unsigned(count.saturating_sub(offset)),
+            ),
+            ParsedValue::Literal(Literal::Signed(count)) => (
+                PluralOperands::from(*count),
+                Literal::Signed(count.saturating_sub_unsigned(offset)),
+            ),
             ParsedValue::Bloc(values) => {
                 let new_key = Self::find_variable(values, loc, foreign_key)?;
                 return self.populate_with_new_key(new_key, args, foreign_key, loc);
@@ -227,7 +519,34 @@ impl Plurals {
             }
         };
 
-        let category = category?;
+        let mut adjusted_args;
+        let args = if offset == 0 {
+            args
+        } else {
+            adjusted_args = args.clone();
+            adjusted_args.insert(
+                self.count_key.name.to_string(),
+                ParsedValue::Literal(adjusted_count.clone()),
+            );
+            &adjusted_args
+        };
+
+        if let Some(value) = self
+            .exact_values()
+            .find(|value| PluralOperands::from(*value) == operands)
+            .and_then(|value| self.forms.get(&PluralForm::Exact(value)))
+        {
+            return value.populate(args, foreign_key, loc);
+        }
+
+        let category = match adjusted_count {
+            Literal::Float(count) => get_category(self, loc.locale, &to_decimal(count)),
+            Literal::Unsigned(count) => get_category(self, loc.locale, count),
+            Literal::Signed(count) => get_category(self, loc.locale, count),
+            Literal::String(..) | Literal::Bool(_) => {
+                unreachable!("the count is a number")
+            }
+        }?;
 
         match PluralForm::from_icu_category(category) {
             PluralForm::Other => self.other.populate(args, foreign_key, loc),
@@ -246,7 +565,8 @@ impl Plurals {
         foreign_key: &KeyPath,
         loc: &Loc,
     ) -> Result<ParsedValue> {
-        if let Some(count_arg) = args.get("var_count") {
+        // with several counts each plural reads its own argument, the default count key being `var_count`.
+        if let Some(count_arg) = args.get(&*self.count_key.name) {
             return self.populate_with_count_arg(count_arg, args, foreign_key, loc);
         }
 
@@ -261,6 +581,90 @@ impl Plurals {
     }
 }
 
+/// Check that the plurals of the locales declare every form used by their locale,
+/// as counts of a missing form silently fall back to `other`.
+pub fn check_locales(
+    locales: &[&Locale],
+    key_path: &mut KeyPath,
+    diag: &Diagnostics,
+    options: &ParseOptions,
+) -> Result<()> {
+    for locale in locales {
+        check_locale(locale, key_path, diag, options)?;
+    }
+    Ok(())
+}
+
+fn check_locale(
+    locale: &Locale,
+    key_path: &mut KeyPath,
+    diag: &Diagnostics,
+    options: &ParseOptions,
+) -> Result<()> {
+    for (key, value) in &locale.keys {
+        let mut pushed_key = key_path.push_key(key.clone());
+        if let ParsedValue::Subkeys(Some(subkeys)) = value {
+            check_locale(subkeys, &mut pushed_key, diag, options)?;
+            continue;
+        }
+        let mut plurals = vec![];
+        collect_plurals(value, &mut plurals);
+        for plurals in plurals {
+            let forms = plurals.missing_forms(&locale.top_locale_name)?;
+            if forms.is_empty() {
+                continue;
+            }
+            let locale = locale.top_locale_name.clone();
+            let key = pushed_key.clone();
+            if options.strict_plural_forms {
+                diag.emit_error(Error::MissingPluralForm { locale, key, forms });
+            } else {
+                diag.emit_warning(Warning::MissingPluralForm { locale, key, forms });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn collect_plurals<'a>(value: &'a ParsedValue, acc: &mut Vec<&'a Plurals>) {
+    match value {
+        ParsedValue::Plurals(plurals) => {
+            acc.push(plurals);
+            for value in plurals.forms.values().chain(Some(&*plurals.other)) {
+                collect_plurals(value, acc);
+            }
+        }
+        ParsedValue::Select(select) => {
+            for value in select.variants.values().chain(Some(&*select.other)) {
+                collect_plurals(value, acc);
+            }
+        }
+        ParsedValue::Condition(condition) => {
+            for value in condition.branches() {
+                collect_plurals(value, acc);
+            }
+        }
+        ParsedValue::Component {
+            inner: Some(inner), ..
+        } => collect_plurals(inner, acc),
+        ParsedValue::Bloc(values) => {
+            for value in values {
+                collect_plurals(value, acc);
+            }
+        }
+        // foreign keys are checked at the key they point to.
+        ParsedValue::ForeignKey(_)
+        | ParsedValue::Component { inner: None, .. }
+        | ParsedValue::Ranges(_)
+        | ParsedValue::Literal(_)
+        | ParsedValue::List(_)
+        | ParsedValue::Variable { .. }
+        | ParsedValue::Subkeys(_)
+        | ParsedValue::Default
+        | ParsedValue::Dummy(_) => {}
+    }
+}
+
 impl Display for PluralRuleType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -279,6 +683,7 @@ impl Display for PluralForm {
             PluralForm::Few => write!(f, "_few"),
             PluralForm::Many => write!(f, "_many"),
             PluralForm::Other => write!(f, "_other"),
+            PluralForm::Exact(value) => write!(f, "_={value}"),
         }
     }
 }
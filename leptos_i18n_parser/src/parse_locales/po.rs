@@ -0,0 +1,904 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::de::{
+    DeserializeSeed, IntoDeserializer, Visitor,
+    value::{Error as ValueError, MapDeserializer},
+};
+
+use super::{
+    error::{Error, Result, Warning},
+    locale::{Locale, LocaleSeed, SerdeError},
+    parsed_value::ParsedValue,
+    plurals::{PluralForm, PluralRuleType, Plurals},
+};
+use crate::utils::{Key, KeyPath, Loc};
+
+#[derive(Debug)]
+pub struct PoError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for PoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for PoError {}
+
+type PoResult<T> = core::result::Result<T, PoError>;
+
+#[derive(Debug, Default, PartialEq)]
+struct Entry {
+    line: usize,
+    msgctxt: Option<String>,
+    msgid: Option<String>,
+    msgid_plural: Option<String>,
+    msgstr: BTreeMap<usize, String>,
+    fuzzy: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Ctxt,
+    Id,
+    IdPlural,
+    Str(usize),
+}
+
+fn unescape(s: &str, line: usize) -> PoResult<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(c) => {
+                return Err(PoError {
+                    line,
+                    message: format!("unknown escape sequence \"\\{c}\""),
+                });
+            }
+            None => {
+                return Err(PoError {
+                    line,
+                    message: "unterminated escape sequence".to_string(),
+                });
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn parse_quoted(s: &str, line: usize) -> PoResult<String> {
+    let s = s.trim();
+    match s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .filter(|inner| !inner.ends_with('\\') || inner.ends_with("\\\\"))
+    {
+        Some(inner) => unescape(inner, line),
+        None => Err(PoError {
+            line,
+            message: format!("expected a quoted string, found {s:?}"),
+        }),
+    }
+}
+
+fn parse_keyword(line: &str, line_number: usize) -> PoResult<(Field, &str)> {
+    let (keyword, rest) = line.split_once([' ', '\t']).unwrap_or((line, ""));
+    let field = match keyword {
+        "msgctxt" => Field::Ctxt,
+        "msgid" => Field::Id,
+        "msgid_plural" => Field::IdPlural,
+        "msgstr" => Field::Str(0),
+        _ => {
+            let index = keyword
+                .strip_prefix("msgstr[")
+                .and_then(|s| s.strip_suffix(']'))
+                .and_then(|s| s.parse().ok());
+            match index {
+                Some(index) => Field::Str(index),
+                None => {
+                    return Err(PoError {
+                        line: line_number,
+                        message: format!("unknown keyword {keyword:?}"),
+                    });
+                }
+            }
+        }
+    };
+    Ok((field, rest))
+}
+
+fn parse_entries(src: &str) -> PoResult<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut current = Entry::default();
+    let mut last_field: Option<Field> = None;
+    let mut fuzzy = false;
+
+    for (i, line) in src.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(flags) = line.strip_prefix("#,") {
+            fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            continue;
+        }
+        if line.starts_with('#') {
+            // translator comments, references and obsolete entries are ignored.
+            continue;
+        }
+        if line.starts_with('"') {
+            let s = parse_quoted(line, line_number)?;
+            let target = match last_field {
+                Some(Field::Ctxt) => current.msgctxt.as_mut(),
+                Some(Field::Id) => current.msgid.as_mut(),
+                Some(Field::IdPlural) => current.msgid_plural.as_mut(),
+                Some(Field::Str(index)) => current.msgstr.get_mut(&index),
+                None => None,
+            };
+            match target {
+                Some(target) => target.push_str(&s),
+                None => {
+                    return Err(PoError {
+                        line: line_number,
+                        message: "string continuation without a keyword".to_string(),
+                    });
+                }
+            }
+            continue;
+        }
+
+        let (field, rest) = parse_keyword(line, line_number)?;
+        let value = parse_quoted(rest, line_number)?;
+
+        // a `msgctxt` or `msgid` after a `msgstr` starts a new entry.
+        if matches!(field, Field::Ctxt | Field::Id) && matches!(last_field, Some(Field::Str(_))) {
+            entries.push(std::mem::take(&mut current));
+        }
+        if current.msgctxt.is_none() && current.msgid.is_none() {
+            current.line = line_number;
+            current.fuzzy = std::mem::take(&mut fuzzy);
+        }
+
+        let duplicate = match field {
+            Field::Ctxt => current.msgctxt.replace(value).is_some(),
+            Field::Id => current.msgid.replace(value).is_some(),
+            Field::IdPlural => current.msgid_plural.replace(value).is_some(),
+            Field::Str(index) => current.msgstr.insert(index, value).is_some(),
+        };
+        if duplicate {
+            return Err(PoError {
+                line: line_number,
+                message: "duplicate keyword in entry".to_string(),
+            });
+        }
+        last_field = Some(field);
+    }
+
+    if current.msgid.is_some() || current.msgctxt.is_some() {
+        entries.push(current);
+    }
+
+    for entry in &entries {
+        if entry.msgid.is_none() {
+            return Err(PoError {
+                line: entry.line,
+                message: "entry is missing \"msgid\"".to_string(),
+            });
+        }
+        if entry.msgstr.is_empty() {
+            return Err(PoError {
+                line: entry.line,
+                message: "entry is missing \"msgstr\"".to_string(),
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+#[derive(Debug)]
+enum Node {
+    Value(String),
+    Subkeys(BTreeMap<String, Node>),
+}
+
+impl<'de> serde::Deserializer<'de> for Node {
+    type Error = ValueError;
+
+    fn deserialize_any<V>(self, visitor: V) -> core::result::Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Node::Value(s) => visitor.visit_string(s),
+            Node::Subkeys(map) => visitor.visit_map(MapDeserializer::new(map.into_iter())),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl IntoDeserializer<'_, ValueError> for Node {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+fn insert_node(
+    root: &mut BTreeMap<String, Node>,
+    path: &[&str],
+    value: Node,
+    line: usize,
+) -> PoResult<()> {
+    let conflict = || PoError {
+        line,
+        message: format!("key \"{}\" is declared multiple times", path.join(".")),
+    };
+    let Some((last, parents)) = path.split_last() else {
+        return Err(PoError {
+            line,
+            message: "empty key".to_string(),
+        });
+    };
+    let mut map = root;
+    for segment in parents {
+        let node = map
+            .entry(segment.to_string())
+            .or_insert_with(|| Node::Subkeys(BTreeMap::new()));
+        let Node::Subkeys(inner) = node else {
+            return Err(conflict());
+        };
+        map = inner;
+    }
+    if map.insert(last.to_string(), value).is_some() {
+        return Err(conflict());
+    }
+    Ok(())
+}
+
+/// Which `msgstr[N]` gives each plural form of the locale.
+type FormsIndices = BTreeMap<PluralForm, usize>;
+
+/// The `plural` expression of the `Plural-Forms` header, a C expression over the count `n`.
+#[derive(Debug, PartialEq)]
+enum PluralExpr {
+    N,
+    Num(u64),
+    Not(Box<PluralExpr>),
+    Binary(BinOp, Box<PluralExpr>, Box<PluralExpr>),
+    Ternary(Box<PluralExpr>, Box<PluralExpr>, Box<PluralExpr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinOp {
+    /// Operators by increasing precedence.
+    const LEVELS: &[&[(&str, BinOp)]] = &[
+        &[("||", BinOp::Or)],
+        &[("&&", BinOp::And)],
+        &[("==", BinOp::Eq), ("!=", BinOp::Ne)],
+        &[
+            ("<=", BinOp::Le),
+            (">=", BinOp::Ge),
+            ("<", BinOp::Lt),
+            (">", BinOp::Gt),
+        ],
+        &[("+", BinOp::Add), ("-", BinOp::Sub)],
+        &[("*", BinOp::Mul), ("/", BinOp::Div), ("%", BinOp::Rem)],
+    ];
+}
+
+impl PluralExpr {
+    fn eval(&self, n: u64) -> u64 {
+        match self {
+            PluralExpr::N => n,
+            PluralExpr::Num(value) => *value,
+            PluralExpr::Not(expr) => (expr.eval(n) == 0) as u64,
+            PluralExpr::Ternary(cond, then, otherwise) => {
+                if cond.eval(n) != 0 {
+                    then.eval(n)
+                } else {
+                    otherwise.eval(n)
+                }
+            }
+            PluralExpr::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.eval(n), rhs.eval(n));
+                match op {
+                    BinOp::Or => (lhs != 0 || rhs != 0) as u64,
+                    BinOp::And => (lhs != 0 && rhs != 0) as u64,
+                    BinOp::Eq => (lhs == rhs) as u64,
+                    BinOp::Ne => (lhs != rhs) as u64,
+                    BinOp::Lt => (lhs < rhs) as u64,
+                    BinOp::Le => (lhs <= rhs) as u64,
+                    BinOp::Gt => (lhs > rhs) as u64,
+                    BinOp::Ge => (lhs >= rhs) as u64,
+                    BinOp::Add => lhs.wrapping_add(rhs),
+                    BinOp::Sub => lhs.wrapping_sub(rhs),
+                    BinOp::Mul => lhs.wrapping_mul(rhs),
+                    BinOp::Div => lhs.checked_div(rhs).unwrap_or_default(),
+                    BinOp::Rem => lhs.checked_rem(rhs).unwrap_or_default(),
+                }
+            }
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let mut rest = s.trim();
+        let expr = Self::parse_ternary(&mut rest)?;
+        rest.is_empty().then_some(expr)
+    }
+
+    fn parse_ternary(s: &mut &str) -> Option<Self> {
+        let cond = Self::parse_binary(s, 0)?;
+        let Some(rest) = s.strip_prefix('?') else {
+            return Some(cond);
+        };
+        *s = rest.trim_start();
+        let then = Self::parse_ternary(s)?;
+        *s = s.strip_prefix(':')?.trim_start();
+        let otherwise = Self::parse_ternary(s)?;
+        Some(PluralExpr::Ternary(
+            Box::new(cond),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    fn parse_binary(s: &mut &str, level: usize) -> Option<Self> {
+        let Some(ops) = BinOp::LEVELS.get(level) else {
+            return Self::parse_unary(s);
+        };
+        let mut lhs = Self::parse_binary(s, level + 1)?;
+        while let Some((token, op)) = ops.iter().find(|(token, _)| s.starts_with(token)) {
+            *s = s[token.len()..].trim_start();
+            let rhs = Self::parse_binary(s, level + 1)?;
+            lhs = PluralExpr::Binary(*op, Box::new(lhs), Box::new(rhs));
+        }
+        Some(lhs)
+    }
+
+    fn parse_unary(s: &mut &str) -> Option<Self> {
+        let expr = if let Some(rest) = s.strip_prefix('!') {
+            *s = rest.trim_start();
+            PluralExpr::Not(Box::new(Self::parse_unary(s)?))
+        } else if let Some(rest) = s.strip_prefix('(') {
+            *s = rest.trim_start();
+            let expr = Self::parse_ternary(s)?;
+            *s = s.strip_prefix(')')?;
+            expr
+        } else if let Some(rest) = s.strip_prefix('n') {
+            *s = rest;
+            PluralExpr::N
+        } else {
+            let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let value = s[..end].parse().ok()?;
+            *s = &s[end..];
+            PluralExpr::Num(value)
+        };
+        *s = s.trim_start();
+        Some(expr)
+    }
+}
+
+/// Parse the `Plural-Forms` field of the header entry: `nplurals=2; plural=(n > 1);`.
+fn parse_plural_forms_header(entries: &[Entry]) -> PoResult<Option<(usize, PluralExpr)>> {
+    let Some(header) = entries
+        .iter()
+        .find(|entry| entry.msgid.as_deref() == Some("") && entry.msgctxt.is_none())
+    else {
+        return Ok(None);
+    };
+    let Some(field) = header.msgstr.get(&0).and_then(|msgstr| {
+        msgstr
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("Plural-Forms:"))
+    }) else {
+        return Ok(None);
+    };
+    let invalid = || PoError {
+        line: header.line,
+        message: format!("invalid \"Plural-Forms\" header {:?}", field.trim()),
+    };
+    let mut nplurals = None;
+    let mut plural = None;
+    for part in field.split(';') {
+        let Some((name, value)) = part.split_once('=') else {
+            continue;
+        };
+        match name.trim() {
+            "nplurals" => nplurals = Some(value.trim().parse::<usize>().map_err(|_| invalid())?),
+            "plural" => plural = Some(PluralExpr::parse(value).ok_or_else(invalid)?),
+            _ => {}
+        }
+    }
+    match (nplurals, plural) {
+        (Some(nplurals), Some(plural)) => Ok(Some((nplurals, plural))),
+        _ => Err(invalid()),
+    }
+}
+
+/// The counts used to match the gettext forms with the CLDR categories.
+fn sample_counts() -> impl Iterator<Item = u64> {
+    (0..=1000).chain([10_000, 100_000, 1_000_000, 2_000_000, 10_000_000])
+}
+
+/// Map each CLDR category of the locale to the gettext form used by most of its counts.
+/// Categories no integer count can reach are left out, they fall back to `other`.
+fn forms_indices_from_header(
+    nplurals: usize,
+    plural: &PluralExpr,
+    seed: &LocaleSeed,
+) -> Result<FormsIndices> {
+    let plural_rules = PluralRuleType::Cardinal.get_plural_rules(&seed.top_locale_name)?;
+    let mut hits = BTreeMap::<PluralForm, BTreeMap<usize, usize>>::new();
+    for count in sample_counts() {
+        let form = PluralForm::from_icu_category(plural_rules.category_for(count));
+        let index = plural.eval(count) as usize;
+        if index < nplurals {
+            *hits.entry(form).or_default().entry(index).or_default() += 1;
+        }
+    }
+    let indices = hits
+        .into_iter()
+        .filter_map(|(form, hits)| {
+            // ties go to the first form
+            let (index, _) = hits
+                .into_iter()
+                .max_by(|(i1, h1), (i2, h2)| h1.cmp(h2).then(i2.cmp(i1)))?;
+            Some((form, index))
+        })
+        .collect::<FormsIndices>();
+
+    let mismatch = sample_counts().find_map(|count| {
+        let form = PluralForm::from_icu_category(plural_rules.category_for(count));
+        let index = plural.eval(count) as usize;
+        (index < nplurals && indices.get(&form) != Some(&index)).then_some((count, form))
+    });
+    if let Some((count, form)) = mismatch {
+        seed.diag.emit_warning(Warning::PoPluralFormsMismatch {
+            locale: seed.top_locale_name.clone(),
+            count,
+            form,
+        });
+    }
+    Ok(indices)
+}
+
+/// Without a `Plural-Forms` header the gettext forms are assumed to follow the CLDR categories order.
+fn forms_indices_by_position(seed: &LocaleSeed) -> Result<FormsIndices> {
+    let categories = PluralRuleType::Cardinal.categories(&seed.top_locale_name)?;
+    Ok(categories
+        .into_iter()
+        .enumerate()
+        .map(|(index, form)| (form, index))
+        .collect())
+}
+
+fn plural_forms(
+    entry: &Entry,
+    indices: &FormsIndices,
+    loc: &Loc,
+    seed: &LocaleSeed,
+) -> BTreeMap<String, Node> {
+    let mut forms = BTreeMap::new();
+    for (form, index) in indices {
+        if let Some(msgstr) = entry.msgstr.get(index) {
+            forms.insert(*form, msgstr.clone());
+        }
+    }
+    for index in entry.msgstr.keys() {
+        if !indices.values().any(|i| i == index) {
+            seed.diag.emit_warning(Warning::SurplusPoPluralForm {
+                loc: loc.into(),
+                index: *index,
+            });
+        }
+    }
+    // gettext can declare less forms than CLDR, in that case the last one is used as the fallback.
+    if let Some((_, last)) = entry
+        .msgstr
+        .iter()
+        .rfind(|(index, _)| indices.values().any(|i| i == *index))
+    {
+        forms
+            .entry(PluralForm::Other)
+            .or_insert_with(|| last.clone());
+    }
+    forms
+        .into_iter()
+        .map(|(form, msgstr)| (form_name(form).to_string(), Node::Value(msgstr)))
+        .collect()
+}
+
+fn form_name(form: PluralForm) -> &'static str {
+    match form {
+        PluralForm::Zero => "zero",
+        PluralForm::One => "one",
+        PluralForm::Two => "two",
+        PluralForm::Few => "few",
+        PluralForm::Many => "many",
+        PluralForm::Other => "other",
+        PluralForm::Exact(_) => unreachable!("gettext plural forms are CLDR categories"),
+    }
+}
+
+fn key_path_of(entry: &Entry) -> Vec<&str> {
+    let msgid = entry.msgid.as_deref().unwrap_or_default();
+    entry
+        .msgctxt
+        .iter()
+        .flat_map(|ctx| ctx.split('.'))
+        .chain(msgid.split('.'))
+        .map(str::trim)
+        .collect()
+}
+
+fn make_key_path(seed: &LocaleSeed, path: &[&str]) -> Result<KeyPath> {
+    let path = path
+        .iter()
+        .map(|segment| Key::try_with_mapping(segment, seed.key_mapping))
+        .collect::<Result<Vec<_>>>()?;
+    let mut key_path = seed.key_path.clone();
+    key_path.path.extend(path);
+    Ok(key_path)
+}
+
+fn get_value_mut<'a>(
+    keys: &'a mut BTreeMap<Key, ParsedValue>,
+    path: &[Key],
+) -> Option<&'a mut ParsedValue> {
+    let (first, rest) = path.split_first()?;
+    let value = keys.get_mut(first)?;
+    if rest.is_empty() {
+        return Some(value);
+    }
+    match value {
+        ParsedValue::Subkeys(Some(locale)) => get_value_mut(&mut locale.keys, rest),
+        _ => None,
+    }
+}
+
+fn make_plurals(locale: &mut Locale, key_path: &KeyPath, seed: &LocaleSeed) -> Result<()> {
+    let loc = Loc {
+        locale: &seed.top_locale_name,
+        key_path,
+    };
+    let relative_path = &key_path.path[seed.key_path.path.len()..];
+    let value = get_value_mut(&mut locale.keys, relative_path)
+        .expect("plural entries are deserialized as subkeys");
+    let ParsedValue::Subkeys(Some(forms)) = std::mem::take(value) else {
+        unreachable!("plural entries are deserialized as subkeys");
+    };
+
+    if !cfg!(feature = "plurals") {
+        return Err(Error::DisabledPlurals { loc: (&loc).into() }.into());
+    }
+
+    let mut forms = forms
+        .keys
+        .into_iter()
+        .filter_map(|(key, value)| Some((PluralForm::try_from_str(&key.name)?, value)))
+        .collect::<BTreeMap<_, _>>();
+    let other = forms.remove(&PluralForm::Other).unwrap_or_default();
+    let plurals = Plurals {
+        rule_type: PluralRuleType::Cardinal,
+        count_key: Key::count(),
+        other: Box::new(other),
+        forms,
+        offset: 0,
+    };
+    plurals.check_forms(&loc, seed.diag)?;
+    *value = ParsedValue::Plurals(plurals);
+    Ok(())
+}
+
+fn build_tree(
+    entries: &[Entry],
+    seed: &LocaleSeed,
+) -> Result<(BTreeMap<String, Node>, Vec<KeyPath>), SerdeError> {
+    let mut root = BTreeMap::new();
+    let mut plurals = Vec::new();
+    let mut indices = None;
+
+    for entry in entries {
+        if entry.msgid.as_deref() == Some("") {
+            // header entry
+            continue;
+        }
+        if entry.fuzzy || entry.msgstr.values().all(String::is_empty) {
+            // untranslated or fuzzy entries are skipped, like `msgfmt` does.
+            continue;
+        }
+        let path = key_path_of(entry);
+        let node = if entry.msgid_plural.is_some() {
+            let key_path = make_key_path(seed, &path).map_err(SerdeError::custom)?;
+            let loc = Loc {
+                locale: &seed.top_locale_name,
+                key_path: &key_path,
+            };
+            let indices = match &indices {
+                Some(indices) => indices,
+                None => {
+                    let header = parse_plural_forms_header(entries).map_err(SerdeError::Po)?;
+                    let computed = match header {
+                        Some((nplurals, plural)) => {
+                            forms_indices_from_header(nplurals, &plural, seed)
+                        }
+                        None => forms_indices_by_position(seed),
+                    };
+                    indices.insert(computed.map_err(SerdeError::custom)?)
+                }
+            };
+            let forms = plural_forms(entry, indices, &loc, seed);
+            plurals.push(key_path);
+            Node::Subkeys(forms)
+        } else {
+            match &entry.msgstr.iter().collect::<Vec<_>>()[..] {
+                [(0, msgstr)] => Node::Value(msgstr.to_string()),
+                _ => {
+                    return Err(SerdeError::Po(PoError {
+                        line: entry.line,
+                        message: "\"msgstr[N]\" can only be used with \"msgid_plural\"".to_string(),
+                    }));
+                }
+            }
+        };
+        insert_node(&mut root, &path, node, entry.line).map_err(SerdeError::Po)?;
+    }
+
+    Ok((root, plurals))
+}
+
+pub fn deserialize(src: &str, seed: LocaleSeed) -> Result<Locale, SerdeError> {
+    let entries = parse_entries(src).map_err(SerdeError::Po)?;
+    let (root, plurals) = build_tree(&entries, &seed)?;
+    let mut locale = seed
+        .clone()
+        .deserialize(Node::Subkeys(root))
+        .map_err(SerdeError::custom)?;
+    for key_path in &plurals {
+        make_plurals(&mut locale, key_path, &seed).map_err(SerdeError::custom)?;
+    }
+    Ok(locale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        formatters::{Formatters, VarBounds},
+        parse_locales::{
+            ForeignKeysPaths, error::Diagnostics, options::KeyMapping, parsed_value::Literal,
+        },
+    };
+
+    fn parse_locale(locale: &str, src: &str) -> (Locale, Diagnostics) {
+        let foreign_keys_paths = ForeignKeysPaths::new();
+        let diag = Diagnostics::new();
+        let formatters = Formatters::new();
+        let locale = Key::new(locale).unwrap();
+        let seed = LocaleSeed {
+            name: locale.clone(),
+            top_locale_name: locale,
+            key_path: KeyPath::new(None),
+            foreign_keys_paths: &foreign_keys_paths,
+            diag: &diag,
+            formatters: &formatters,
+            parse_fns: ParsedValue::DEFAULT_FNS,
+            key_mapping: &KeyMapping::new(),
+        };
+        let locale = deserialize(src, seed).unwrap();
+        (locale, diag)
+    }
+
+    fn lit(s: &str) -> ParsedValue {
+        ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX))
+    }
+
+    fn get<'a>(locale: &'a Locale, path: &str) -> &'a ParsedValue {
+        let path = path
+            .split('.')
+            .map(|k| Key::new(k).unwrap())
+            .collect::<Vec<_>>();
+        locale.get_value_at(&path).unwrap()
+    }
+
+    #[test]
+    fn parse_simple_entries() {
+        let (locale, diag) = parse_locale(
+            "en",
+            r#"
+msgid ""
+msgstr ""
+"Language: en\n"
+
+# a comment
+msgid "hello"
+msgstr "Hello "
+"{{ name }}"
+
+msgctxt "menu.file"
+msgid "open"
+msgstr "Open"
+
+msgid "dialog.title"
+msgstr "Title"
+
+#, fuzzy
+msgid "draft"
+msgstr "Not ready"
+
+msgid "untranslated"
+msgstr ""
+"#,
+        );
+
+        assert!(diag.errors().is_empty());
+        assert_eq!(
+            get(&locale, "hello"),
+            &ParsedValue::Bloc(vec![
+                lit("Hello "),
+                ParsedValue::Variable {
+                    key: Key::new("var_name").unwrap(),
+                    bounds: VarBounds::None,
+                    default: None,
+                },
+                lit("")
+            ])
+        );
+        assert_eq!(get(&locale, "menu.file.open"), &lit("Open"));
+        assert_eq!(get(&locale, "dialog.title"), &lit("Title"));
+        assert!(locale.get_value_at(&[Key::new("draft").unwrap()]).is_none());
+        assert!(
+            locale
+                .get_value_at(&[Key::new("untranslated").unwrap()])
+                .is_none()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn parse_plurals() {
+        let src = r#"
+msgid "items"
+msgid_plural "items"
+msgstr[0] "one item"
+msgstr[1] "few items"
+msgstr[2] "many items"
+"#;
+        let (locale, diag) = parse_locale("ru", src);
+        assert!(diag.errors().is_empty());
+        let ParsedValue::Plurals(plurals) = get(&locale, "items") else {
+            panic!("expected plurals");
+        };
+        assert_eq!(plurals.forms.get(&PluralForm::One), Some(&lit("one item")));
+        assert_eq!(plurals.forms.get(&PluralForm::Few), Some(&lit("few items")));
+        assert_eq!(
+            plurals.forms.get(&PluralForm::Many),
+            Some(&lit("many items"))
+        );
+        // russian has 4 CLDR categories, the last gettext form is used as the fallback.
+        assert_eq!(*plurals.other, lit("many items"));
+    }
+
+    #[test]
+    #[cfg(feature = "plurals")]
+    fn parse_plurals_with_header() {
+        let src = r#"
+msgid ""
+msgstr ""
+"Language: fr\n"
+"Plural-Forms: nplurals=2; plural=(n > 1);\n"
+
+msgid "items"
+msgid_plural "items"
+msgstr[0] "{{ count }} article"
+msgstr[1] "{{ count }} articles"
+"#;
+        let (locale, diag) = parse_locale("fr", src);
+        assert!(diag.errors().is_empty());
+        assert!(diag.warnings().is_empty());
+        let ParsedValue::Plurals(plurals) = get(&locale, "items") else {
+            panic!("expected plurals");
+        };
+        let form = |form| match plurals.forms.get(&form) {
+            Some(ParsedValue::Bloc(values)) => values.last().cloned(),
+            _ => None,
+        };
+        assert_eq!(form(PluralForm::One), Some(lit(" article")));
+        // french "many" is for large numbers such as 1 000 000, gettext uses its second form for them.
+        assert_eq!(form(PluralForm::Many), Some(lit(" articles")));
+
+        // latvian uses a specific form for 0 in gettext, but CLDR "zero" also covers 10, 11..19, 20...
+        let src = r#"
+msgid ""
+msgstr "Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n != 0 ? 1 : 2);\n"
+
+msgid "items"
+msgid_plural "items"
+msgstr[0] "one"
+msgstr[1] "many"
+msgstr[2] "none"
+"#;
+        let (locale, diag) = parse_locale("lv", src);
+        let ParsedValue::Plurals(plurals) = get(&locale, "items") else {
+            panic!("expected plurals");
+        };
+        assert_eq!(plurals.forms.get(&PluralForm::One), Some(&lit("one")));
+        assert_eq!(plurals.forms.get(&PluralForm::Zero), Some(&lit("many")));
+        assert_eq!(*plurals.other, lit("many"));
+        // `msgstr[2]` is only used by 0, which CLDR puts with the counts using `msgstr[1]`
+        let warnings = diag.warnings();
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(matches!(
+            warnings[0],
+            Warning::PoPluralFormsMismatch { count: 0, .. }
+        ));
+        assert!(matches!(
+            warnings[1],
+            Warning::SurplusPoPluralForm { index: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn parse_plural_expressions() {
+        let expr = PluralExpr::parse(
+            "n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2",
+        )
+        .unwrap();
+        let forms = [0, 1, 2, 5, 11, 21, 22, 25, 112].map(|n| expr.eval(n));
+        assert_eq!(forms, [2, 0, 1, 2, 2, 0, 1, 2, 2]);
+        assert_eq!(PluralExpr::parse("(n != 1)").unwrap().eval(1), 0);
+        assert_eq!(PluralExpr::parse("!(n > 1)").unwrap().eval(0), 1);
+        assert!(PluralExpr::parse("n >").is_none());
+        assert!(PluralExpr::parse("(n > 1").is_none());
+
+        let entries =
+            parse_entries("msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2; plural=n >;\\n\"")
+                .unwrap();
+        let err = parse_plural_forms_header(&entries).unwrap_err();
+        assert_eq!(err.line, 1);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = parse_entries("msgid \"a\"\nmsgstr \"b\nmsgid \"c\"").unwrap_err();
+        assert_eq!(err.line, 2);
+        let err = parse_entries("msgid \"a\"\nfoo \"b\"").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+}
@@ -244,11 +244,17 @@ impl RangeType {
     }
 }
 
+/// A sequence is either ranges, or a list of strings when the first element is a string that isn't a numerical type.
+pub enum RangesOrList {
+    Ranges(Ranges),
+    List(Vec<String>),
+}
+
 impl Ranges {
     pub fn from_serde_seq<'de, A>(
         mut seq: A,
         parsed_value_seed: ParsedValueSeed,
-    ) -> Result<Self, A::Error>
+    ) -> Result<RangesOrList, A::Error>
     where
         A: serde::de::SeqAccess<'de>,
     {
@@ -263,10 +269,17 @@ impl Ranges {
                 count_key: Key::count(),
                 inner: UntypedRangesInner::I32(vec![range]),
             },
+            TypeOrRange::Item(item) => {
+                let mut items = vec![item];
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                return Ok(RangesOrList::List(items));
+            }
         };
 
         ranges.deserialize_inner(seq, parsed_value_seed)?;
-        Ok(ranges)
+        Ok(RangesOrList::Ranges(ranges))
     }
 
     pub fn from_type(range_type: RangeType) -> Self {
@@ -751,6 +764,8 @@ impl Ranges {
 pub enum TypeOrRange {
     Type(RangeType),
     Range((Range<DefaultRangeType>, ParsedValue)),
+    /// The first item of a list.
+    Item(String),
 }
 struct TypeOrRangeSeed<'a>(pub ParsedValueSeed<'a>);
 
@@ -772,6 +787,13 @@ impl TypeOrRange {
     }
 }
 
+/// A numerical type name such as `"u23"`, not a valid list item.
+fn looks_like_range_type(s: &str) -> bool {
+    let s = s.trim();
+    s.strip_prefix(['i', 'u', 'f'])
+        .is_some_and(|bits| !bits.is_empty() && bits.bytes().all(|b| b.is_ascii_digit()))
+}
+
 impl<'de> serde::de::DeserializeSeed<'de> for TypeOrRangeSeed<'_> {
     type Value = TypeOrRange;
 
@@ -789,7 +811,7 @@ impl<'de> serde::de::Visitor<'de> for TypeOrRangeSeed<'_> {
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             formatter,
-            "either a string describing a numerical type or a range"
+            "either a string describing a numerical type, a range or the first item of a list"
         )
     }
 
@@ -797,8 +819,16 @@ impl<'de> serde::de::Visitor<'de> for TypeOrRangeSeed<'_> {
     where
         E: serde::de::Error,
     {
-        TypeOrRange::from_string(v)
-            .ok_or_else(|| serde::de::Error::custom(Error::InvalidRangeType(v.to_string())))
+        if let Some(type_or_range) = TypeOrRange::from_string(v) {
+            Ok(type_or_range)
+        } else if looks_like_range_type(v) {
+            // most likely a typo, such as "u23"
+            Err(serde::de::Error::custom(Error::InvalidRangeType(
+                v.to_string(),
+            )))
+        } else {
+            Ok(TypeOrRange::Item(v.to_string()))
+        }
     }
 
     fn visit_map<A>(self, map: A) -> std::result::Result<Self::Value, A::Error>
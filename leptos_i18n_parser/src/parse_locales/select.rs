@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use super::{
+    StringIndexer,
+    error::{Error, Result},
+    parsed_value::{Literal, ParsedValue},
+    plurals::Plurals,
+};
+use crate::utils::{Key, KeyPath, Loc};
+
+/// Name of the fallback variant of a select.
+pub const SELECT_OTHER: &str = "other";
+
+/// Key marking a map as a select, the value being the name of the variable to select on:
+/// `{ "$select": "gender", "male": "...", "female": "...", "other": "..." }`
+pub const SELECT_MARKER: &str = "$select";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Select {
+    pub select_key: Key,
+    // Box for the same reason as `Plurals::other`
+    pub other: Box<ParsedValue>,
+    pub variants: BTreeMap<Key, ParsedValue>,
+}
+
+impl Select {
+    /// Create a select from all its variants, the `other` variant being the fallback.
+    pub fn new(
+        select_key: Key,
+        mut variants: BTreeMap<Key, ParsedValue>,
+        loc: &Loc,
+    ) -> Result<Self> {
+        let Some(other) = variants.remove(SELECT_OTHER) else {
+            return Err(Error::MissingSelectFallback { loc: loc.into() }.into());
+        };
+        if variants
+            .values()
+            .chain(Some(&other))
+            .any(|value| matches!(value, ParsedValue::Subkeys(_)))
+        {
+            return Err(Error::SubkeysInSelect { loc: loc.into() }.into());
+        }
+        Ok(Select {
+            select_key,
+            other: Box::new(other),
+            variants,
+        })
+    }
+
+    pub fn get_variant(&self, name: &str) -> &ParsedValue {
+        self.variants.get(name).unwrap_or(&self.other)
+    }
+
+    fn populate_with_new_key(
+        &self,
+        new_key: Key,
+        args: &BTreeMap<String, ParsedValue>,
+        foreign_key: &KeyPath,
+        loc: &Loc,
+    ) -> Result<ParsedValue> {
+        let other = self.other.populate(args, foreign_key, loc)?;
+        let mut variants = BTreeMap::new();
+        for (variant, value) in &self.variants {
+            let value = value.populate(args, foreign_key, loc)?;
+            variants.insert(variant.clone(), value);
+        }
+
+        Ok(ParsedValue::Select(Select {
+            select_key: new_key,
+            other: Box::new(other),
+            variants,
+        }))
+    }
+
+    pub fn populate(
+        &self,
+        args: &BTreeMap<String, ParsedValue>,
+        foreign_key: &KeyPath,
+        loc: &Loc,
+    ) -> Result<ParsedValue> {
+        let Some(select_arg) = args.get(&*self.select_key.name) else {
+            return self.populate_with_new_key(self.select_key.clone(), args, foreign_key, loc);
+        };
+
+        match select_arg {
+            ParsedValue::Literal(Literal::String(variant, _)) => {
+                self.get_variant(variant).populate(args, foreign_key, loc)
+            }
+            ParsedValue::Variable { key, .. } => {
+                self.populate_with_new_key(key.clone(), args, foreign_key, loc)
+            }
+            ParsedValue::Bloc(values) => {
+                let new_key = Plurals::find_variable(values, loc, foreign_key)?;
+                self.populate_with_new_key(new_key, args, foreign_key, loc)
+            }
+            _ => Err(Error::InvalidSelectArg {
+                loc: loc.into(),
+                foreign_key: foreign_key.to_owned(),
+            }
+            .into()),
+        }
+    }
+
+    pub fn index_strings(&mut self, strings: &mut StringIndexer) {
+        for value in self.variants.values_mut() {
+            value.index_strings(strings);
+        }
+        self.other.index_strings(strings);
+    }
+}
@@ -0,0 +1,477 @@
+use std::{collections::BTreeMap, fmt::Display, path::Path};
+
+use serde::de::{DeserializeSeed, IntoDeserializer, value::Error as ValueError};
+
+use super::{
+    ForeignKeysPaths,
+    error::Diagnostics,
+    locale::{Locale, LocalesOrNamespaces, Namespace},
+    parsed_value::{ParsedValue, ParsedValueSeed},
+};
+use crate::{
+    parse_locales::options::Config,
+    utils::{Key, KeyPath},
+};
+
+pub const KEY_COLUMN: &str = "key";
+
+#[derive(Debug)]
+pub struct TableError {
+    pub row: usize,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "row {}, column {}: {}", self.row, column, self.message),
+            None => write!(f, "row {}: {}", self.row, self.message),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+type TableResult<T> = core::result::Result<T, TableError>;
+
+fn error(row: usize, column: Option<usize>, message: impl Into<String>) -> TableError {
+    TableError {
+        row,
+        column,
+        message: message.into(),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Record {
+    row: usize,
+    cells: Vec<String>,
+}
+
+impl Record {
+    fn is_empty(&self) -> bool {
+        self.cells.iter().all(|cell| cell.trim().is_empty())
+    }
+
+    fn cell(&self, index: usize) -> &str {
+        self.cells
+            .get(index)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
+
+/// `.tsv` files are tab separated, any other file is comma separated.
+pub fn delimiter_of(path: &Path) -> char {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("tsv") => '\t',
+        _ => ',',
+    }
+}
+
+/// Split the file in records, following RFC 4180: cells can be quoted to contain the delimiter,
+/// line breaks or `"` written as `""`.
+fn parse_records(src: &str, delimiter: char) -> TableResult<Vec<Record>> {
+    let src = src.strip_prefix('\u{feff}').unwrap_or(src);
+    let mut records = vec![];
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut row = 1;
+    let mut record_row = 1;
+    let mut chars = src.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if cell.is_empty() => {
+                let quote_row = row;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            cell.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                row += 1;
+                            }
+                            cell.push(c);
+                        }
+                        None => {
+                            return Err(error(
+                                quote_row,
+                                Some(cells.len() + 1),
+                                "unterminated quoted cell",
+                            ));
+                        }
+                    }
+                }
+                match chars.peek() {
+                    None | Some('\r' | '\n') => {}
+                    Some(&c) if c == delimiter => {}
+                    Some(_) => {
+                        return Err(error(
+                            row,
+                            Some(cells.len() + 1),
+                            "unexpected character after closing quote",
+                        ));
+                    }
+                }
+            }
+            c if c == delimiter => cells.push(std::mem::take(&mut cell)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                cells.push(std::mem::take(&mut cell));
+                records.push(Record {
+                    row: record_row,
+                    cells: std::mem::take(&mut cells),
+                });
+                row += 1;
+                record_row = row;
+            }
+            c => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !cells.is_empty() {
+        cells.push(cell);
+        records.push(Record {
+            row: record_row,
+            cells,
+        });
+    }
+
+    Ok(records)
+}
+
+struct Header {
+    key_column: usize,
+    /// index of the column of each locale, in the order of the config.
+    locale_columns: Vec<usize>,
+}
+
+fn parse_header(header: Option<&Record>, cfg: &Config) -> TableResult<Header> {
+    let Some(header) = header else {
+        return Err(error(1, None, "the table is empty"));
+    };
+    let find_column = |name: &str| -> TableResult<Option<usize>> {
+        let mut columns = header
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.trim() == name)
+            .map(|(index, _)| index);
+        let column = columns.next();
+        match columns.next() {
+            Some(duplicate) => Err(error(
+                header.row,
+                Some(duplicate + 1),
+                format!("column {name:?} is declared multiple times"),
+            )),
+            None => Ok(column),
+        }
+    };
+
+    let Some(key_column) = find_column(KEY_COLUMN)? else {
+        return Err(error(
+            header.row,
+            None,
+            format!("missing the {KEY_COLUMN:?} column"),
+        ));
+    };
+    let locale_columns = cfg
+        .locales
+        .iter()
+        .map(|locale| {
+            find_column(&locale.name)?.ok_or_else(|| {
+                error(
+                    header.row,
+                    None,
+                    format!("missing the column of locale {:?}", locale.name),
+                )
+            })
+        })
+        .collect::<TableResult<Vec<_>>>()?;
+
+    Ok(Header {
+        key_column,
+        locale_columns,
+    })
+}
+
+fn parse_key_path(record: &Record, column: usize, cfg: &Config) -> TableResult<KeyPath> {
+    let err = |message: String| error(record.row, Some(column + 1), message);
+    let key = record.cell(column).trim();
+    let mut segments = key.split('.').map(|segment| {
+        Key::with_mapping(segment, &cfg.options.key_mapping).ok_or_else(|| {
+            err(format!(
+                "invalid key {key:?}, {segment:?} is not a valid identifier"
+            ))
+        })
+    });
+    let namespace = if cfg.namespaces.is_empty() {
+        None
+    } else {
+        let namespace = segments.next().transpose()?;
+        match namespace {
+            Some(namespace) if cfg.namespaces.contains(&namespace) => Some(namespace),
+            _ => {
+                return Err(err(format!(
+                    "key {key:?} does not start with a namespace, expected one of {:?}",
+                    cfg.namespaces
+                )));
+            }
+        }
+    };
+    let path = segments.collect::<TableResult<Vec<_>>>()?;
+    if path.is_empty() {
+        return Err(err(format!("key {key:?} is missing a path")));
+    }
+    Ok(KeyPath::new_from_path(namespace, path))
+}
+
+pub struct TableContext<'a> {
+    pub cfg: &'a Config,
+    pub foreign_keys_paths: &'a ForeignKeysPaths,
+    pub diag: &'a Diagnostics,
+}
+
+impl TableContext<'_> {
+    fn insert_value(
+        &self,
+        locale: &mut Locale,
+        key_path: &KeyPath,
+        value: &str,
+        row: usize,
+        column: usize,
+    ) -> TableResult<()> {
+        let err = |message: String| error(row, Some(column + 1), message);
+        let (last, parents) = key_path.path.split_last().expect("key paths are not empty");
+        let mut keys = &mut locale.keys;
+        for (depth, segment) in parents.iter().enumerate() {
+            let entry = keys.entry(segment.clone()).or_insert_with(|| {
+                ParsedValue::Subkeys(Some(Locale {
+                    name: segment.clone(),
+                    keys: BTreeMap::new(),
+                    top_locale_name: locale.top_locale_name.clone(),
+                    strings: vec![],
+                    top_locale_string_count: 0,
+                    metadata: BTreeMap::new(),
+                }))
+            });
+            let ParsedValue::Subkeys(Some(subkeys)) = entry else {
+                let path = KeyPath::new_from_path(
+                    key_path.namespace.clone(),
+                    key_path.path[..=depth].to_vec(),
+                );
+                return Err(err(format!("key {path} is a value and can't have subkeys")));
+            };
+            keys = &mut subkeys.keys;
+        }
+        if keys.contains_key(last) {
+            return Err(err(format!("key {key_path} already has subkeys")));
+        }
+
+        let seed = ParsedValueSeed {
+            top_locale_name: &locale.top_locale_name,
+            in_range: false,
+            key_path,
+            key: last,
+            foreign_keys_paths: self.foreign_keys_paths,
+            diag: self.diag,
+            formatters: &self.cfg.options.formatters,
+            parse_fns: &self.cfg.options.parse_fns(),
+            key_mapping: &self.cfg.options.key_mapping,
+        };
+        let value = seed
+            .deserialize(IntoDeserializer::<ValueError>::into_deserializer(value))
+            .map_err(|e| err(e.to_string()))?;
+        keys.insert(last.clone(), value);
+        Ok(())
+    }
+
+    fn parse_records(&self, records: &[Record]) -> TableResult<LocalesOrNamespaces> {
+        let cfg = self.cfg;
+        let (header, rows) = match records.iter().position(|record| !record.is_empty()) {
+            Some(index) => (Some(&records[index]), &records[index + 1..]),
+            None => (None, records),
+        };
+        let Header {
+            key_column,
+            locale_columns,
+        } = parse_header(header, cfg)?;
+
+        let new_locales = || {
+            cfg.locales
+                .iter()
+                .map(|locale| Locale {
+                    top_locale_name: locale.clone(),
+                    name: locale.clone(),
+                    keys: BTreeMap::new(),
+                    strings: vec![],
+                    top_locale_string_count: 0,
+                    metadata: BTreeMap::new(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut namespaces = BTreeMap::new();
+        let mut declared_keys = BTreeMap::new();
+
+        for record in rows {
+            if record.is_empty() {
+                continue;
+            }
+            let key_path = parse_key_path(record, key_column, cfg)?;
+            if let Some(first_row) = declared_keys.insert(key_path.clone(), record.row) {
+                return Err(error(
+                    record.row,
+                    Some(key_column + 1),
+                    format!("key {key_path} is already declared at row {first_row}"),
+                ));
+            }
+            let locales = namespaces
+                .entry(key_path.namespace.clone())
+                .or_insert_with(new_locales);
+            for (locale, &column) in locales.iter_mut().zip(&locale_columns) {
+                let value = record.cell(column);
+                // empty cells are missing translations.
+                if value.trim().is_empty() {
+                    continue;
+                }
+                self.insert_value(locale, &key_path, value, record.row, column)?;
+            }
+        }
+
+        if cfg.namespaces.is_empty() {
+            let locales = namespaces.remove(&None).unwrap_or_else(new_locales);
+            return Ok(LocalesOrNamespaces::Locales(locales));
+        }
+        let namespaces = cfg
+            .namespaces
+            .iter()
+            .map(|namespace| Namespace {
+                key: namespace.clone(),
+                locales: namespaces
+                    .remove(&Some(namespace.clone()))
+                    .unwrap_or_else(new_locales),
+            })
+            .collect();
+        Ok(LocalesOrNamespaces::NameSpaces(namespaces))
+    }
+
+    pub fn parse(&self, src: &str, delimiter: char) -> TableResult<LocalesOrNamespaces> {
+        let records = parse_records(src, delimiter)?;
+        self.parse_records(&records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_locales::parsed_value::Literal;
+
+    fn lit(s: &str) -> ParsedValue {
+        ParsedValue::Literal(Literal::String(s.to_string(), usize::MAX))
+    }
+
+    fn parse(cfg: &Config, src: &str) -> TableResult<LocalesOrNamespaces> {
+        let foreign_keys_paths = ForeignKeysPaths::new();
+        let diag = Diagnostics::new();
+        let ctx = TableContext {
+            cfg,
+            foreign_keys_paths: &foreign_keys_paths,
+            diag: &diag,
+        };
+        ctx.parse(src, ',')
+    }
+
+    fn get<'a>(locale: &'a Locale, path: &str) -> Option<&'a ParsedValue> {
+        let path = path
+            .split('.')
+            .map(|k| Key::new(k).unwrap())
+            .collect::<Vec<_>>();
+        locale.get_value_at(&path)
+    }
+
+    #[test]
+    fn parse_quoted_records() {
+        let src = "key,en\r\n\"a,b\",\"say \"\"hi\"\"\nthere\"\nc,d";
+        let records = parse_records(src, ',').unwrap();
+        assert_eq!(
+            records,
+            vec![
+                Record {
+                    row: 1,
+                    cells: vec!["key".into(), "en".into()],
+                },
+                Record {
+                    row: 2,
+                    cells: vec!["a,b".into(), "say \"hi\"\nthere".into()],
+                },
+                Record {
+                    row: 4,
+                    cells: vec!["c".into(), "d".into()],
+                },
+            ]
+        );
+        assert_eq!(
+            parse_records("key\ten\nk\tv\n", '\t').unwrap()[1].cells,
+            vec!["k", "v"]
+        );
+    }
+
+    #[test]
+    fn parse_locales() {
+        let cfg = Config::new("en").unwrap().add_locale("fr").unwrap();
+        let src = "key,notes,fr,en\n\
+                   hello,greeting,Bonjour,Hello\n\
+                   \n\
+                   sub.a,,,A\n\
+                   items_one,,,one item\n";
+        let LocalesOrNamespaces::Locales(locales) = parse(&cfg, src).unwrap() else {
+            panic!("expected locales");
+        };
+        let [en, fr] = &locales[..] else {
+            panic!("expected 2 locales");
+        };
+        assert_eq!(en.name.name.as_ref(), "en");
+        assert_eq!(get(en, "hello"), Some(&lit("Hello")));
+        assert_eq!(get(fr, "hello"), Some(&lit("Bonjour")));
+        assert_eq!(get(en, "sub.a"), Some(&lit("A")));
+        assert_eq!(get(fr, "sub.a"), None);
+        assert_eq!(get(en, "items_one"), Some(&lit("one item")));
+    }
+
+    #[test]
+    fn parse_namespaces() {
+        let cfg = Config::new("en")
+            .unwrap()
+            .add_namespaces(["first", "second"])
+            .unwrap();
+        let src = "key,en\nfirst.a,A\nsecond.b.c,C\n";
+        let LocalesOrNamespaces::NameSpaces(namespaces) = parse(&cfg, src).unwrap() else {
+            panic!("expected namespaces");
+        };
+        assert_eq!(get(&namespaces[0].locales[0], "a"), Some(&lit("A")));
+        assert_eq!(get(&namespaces[1].locales[0], "b.c"), Some(&lit("C")));
+
+        let err = parse(&cfg, "key,en\nthird.a,A\n").unwrap_err();
+        assert_eq!((err.row, err.column), (2, Some(1)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cfg = Config::new("en").unwrap().add_locale("fr").unwrap();
+        let position = |src: &str| {
+            let err = parse(&cfg, src).unwrap_err();
+            (err.row, err.column)
+        };
+        assert_eq!(position("key,en\na,A\n"), (1, None));
+        assert_eq!(position("id,en,fr\na,A,B\n"), (1, None));
+        assert_eq!(position("key,en,fr\na,A,B\na,C,D\n"), (3, Some(1)));
+        assert_eq!(position("key,en,fr\na b,A,B\n"), (2, Some(1)));
+        assert_eq!(position("key,en,fr\na,A,B\na.b,C,D\n"), (3, Some(2)));
+        assert_eq!(position("key,en,fr\na.b,A,B\na,,D\n"), (3, Some(3)));
+        assert_eq!(position("key,en,fr\na,\"A\" ,B\n"), (2, Some(2)));
+    }
+}
@@ -1,8 +1,10 @@
 use crate::parse_locales::{
     VAR_COUNT_KEY,
     error::{Error, Result},
+    options::KeyMapping,
 };
 use std::{
+    borrow::Borrow,
     fmt::{Debug, Display},
     hash::Hash,
     ops::{Deref, DerefMut},
@@ -19,8 +21,17 @@ pub struct Key {
 
 impl Key {
     pub fn new(name: &str) -> Option<Self> {
+        Self::with_mapping(name, &KeyMapping::new())
+    }
+
+    pub fn try_new(name: &str) -> Result<Self> {
+        Self::new(name).ok_or_else(|| Error::InvalidKey(name.to_string()).into())
+    }
+
+    /// Create a key for a translation, its identifier being given by `key_mapping`.
+    pub fn with_mapping(name: &str, key_mapping: &KeyMapping) -> Option<Self> {
         let name = name.trim();
-        let ident_repr = name.replace('-', "_");
+        let ident_repr = key_mapping.map(name);
         let ident = syn::parse_str::<syn::Ident>(&ident_repr).ok()?;
         Some(Key {
             name: Rc::from(name),
@@ -28,8 +39,9 @@ impl Key {
         })
     }
 
-    pub fn try_new(name: &str) -> Result<Self> {
-        Self::new(name).ok_or_else(|| Error::InvalidKey(name.to_string()).into())
+    pub fn try_with_mapping(name: &str, key_mapping: &KeyMapping) -> Result<Self> {
+        Self::with_mapping(name, key_mapping)
+            .ok_or_else(|| Error::InvalidKey(name.to_string()).into())
     }
 
     pub fn from_ident(ident: syn::Ident) -> Self {
@@ -83,6 +95,12 @@ impl Ord for Key {
     }
 }
 
+impl Borrow<str> for Key {
+    fn borrow(&self) -> &str {
+        &self.name
+    }
+}
+
 impl quote::ToTokens for Key {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         quote::ToTokens::to_tokens(&*self.ident, tokens);
@@ -9,10 +9,22 @@ use crate::{
     parse_locales::{
         ForeignKeysPaths,
         error::{Diagnostics, Result},
+        options::KeyMapping,
         parsed_value::ParsedValue,
     },
 };
 
+/// A function parsing string values, registered with `ParseOptions::add_parse_fn` or `ParseOptions::with_parse_fns`.
+///
+/// The functions are tried in order on the whole string, the first one returning `Some` is used,
+/// and the string is a literal if they all return `None`. A function that finds its syntax in the string
+/// should parse the text around it with [`ParsedValue::new`], so the other functions still apply to it,
+/// and return them in a [`ParsedValue::Bloc`], e.g. `Bloc(vec![before, this, after])`.
+///
+/// The returned value is reduced afterward, nested blocs are flattened and empty literals removed,
+/// so it does not need to be minimal. It should be built from literals, variables, components,
+/// foreign keys and blocs: keys of variables are prefixed with `var_` and keys of components with `comp_`,
+/// as they are generated by the default functions, so `{name}` and `{{ name }}` both declare the `name` variable.
 pub type ParseFn = fn(&ParseContext, &str) -> Option<Result<ParsedValue>>;
 
 #[derive(Clone, Copy)]
@@ -22,6 +34,7 @@ pub struct ParseContext<'a> {
     pub formatters: &'a Formatters,
     pub diag: &'a Diagnostics,
     pub parse_fns: &'a [ParseFn],
+    pub key_mapping: &'a KeyMapping,
 }
 
 #[derive(Clone, Copy)]
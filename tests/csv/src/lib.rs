@@ -0,0 +1,8 @@
+#![deny(warnings)]
+include!(concat!(env!("OUT_DIR"), "/i18n/mod.rs"));
+
+#[cfg(test)]
+mod subkeys;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,61 @@
+use crate::i18n::*;
+use tests_common::*;
+
+#[test]
+fn click_to_change_lang() {
+    let en = td!(Locale::en, click_to_change_lang);
+    assert_eq_rendered!(en, "Click to change language");
+    let fr = td!(Locale::fr, click_to_change_lang);
+    assert_eq_rendered!(fr, "Cliquez pour changez de langue");
+}
+
+#[test]
+fn click_count() {
+    for count in -5..5 {
+        let en = td!(Locale::en, click_count, count);
+        assert_eq_rendered!(en, format!("You clicked {} times", count));
+        let fr = td!(Locale::fr, click_count, count);
+        assert_eq_rendered!(fr, format!("Vous avez cliqué {} fois", count));
+    }
+}
+
+#[test]
+fn items() {
+    let count = move || 1;
+    let en = td!(Locale::en, items, count);
+    assert_eq_rendered!(en, "one item");
+    let fr = td!(Locale::fr, items, count);
+    assert_eq_rendered!(fr, "1 élément");
+
+    for i in [0, 2, 5, 1000] {
+        let count = move || i;
+        let en = td!(Locale::en, items, count);
+        assert_eq_rendered!(en, format!("{} items", i));
+    }
+
+    for i in [2, 5, 1000] {
+        let count = move || i;
+        let fr = td!(Locale::fr, items, count);
+        assert_eq_rendered!(fr, format!("{} éléments", i));
+    }
+
+    let count = move || 1_000_000;
+    let fr = td!(Locale::fr, items, count);
+    assert_eq_rendered!(fr, "1000000 d'éléments");
+}
+
+#[test]
+fn quoted() {
+    let en = td!(Locale::en, quoted);
+    assert_eq_rendered!(en, "Hello, \"world\"");
+    let fr = td!(Locale::fr, quoted);
+    assert_eq_rendered!(fr, "Bonjour, \"monde\"");
+}
+
+#[test]
+fn only_en() {
+    let en = td!(Locale::en, only_en);
+    assert_eq_rendered!(en, "Only in english");
+    let fr = td!(Locale::fr, only_en);
+    assert_eq_rendered!(fr, "Only in english");
+}
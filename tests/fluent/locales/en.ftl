@@ -0,0 +1,28 @@
+# Simple messages
+click_to_change_lang = Click to change language
+click_count = You clicked { $count } times
+click_to_inc = Click to increment the counter
+
+-brand = Leptos
+
+# Attributes are turned into subkeys
+subkeys =
+    .subkey_1 = subkey_1
+    .subkey_2 = subkey_2 from { -brand }
+
+items = { $count ->
+    [one] one item
+   *[other] { $count } items
+}
+
+new_items = { $count ->
+    [0] no new items
+    [one] one new item
+   *[other] { $count } new items
+}
+
+invited = { $gender ->
+    [male] He invited you
+    [female] She invited you
+   *[other] They invited you
+}
@@ -0,0 +1,28 @@
+click_to_change_lang = Cliquez pour changez de langue
+click_count = Vous avez cliqué { $count } fois
+click_to_inc = Cliquez pour incrémenter le compteur
+
+-brand = Leptos
+
+subkeys =
+    .subkey_1 = subkey_1
+    .subkey_2 = subkey_2 de { -brand }
+
+items = { $count ->
+    [one] { $count } élément
+    [many] { $count } d'éléments
+   *[other] { $count } éléments
+}
+
+new_items = { $count ->
+    [0] aucun nouvel élément
+    [one] { $count } nouvel élément
+    [many] { $count } de nouveaux éléments
+   *[other] { $count } nouveaux éléments
+}
+
+invited = { $gender ->
+    [male] Il vous a invité
+    [female] Elle vous a invité
+   *[other] Iel vous a invité
+}
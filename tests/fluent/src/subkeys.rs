@@ -0,0 +1,18 @@
+use crate::i18n::*;
+use tests_common::*;
+
+#[test]
+fn subkey_1() {
+    let en = td!(Locale::en, subkeys.subkey_1);
+    assert_eq_rendered!(en, "subkey_1");
+    let fr = td!(Locale::fr, subkeys.subkey_1);
+    assert_eq_rendered!(fr, "subkey_1");
+}
+
+#[test]
+fn subkey_2() {
+    let en = td!(Locale::en, subkeys.subkey_2);
+    assert_eq_rendered!(en, "subkey_2 from Leptos");
+    let fr = td!(Locale::fr, subkeys.subkey_2);
+    assert_eq_rendered!(fr, "subkey_2 de Leptos");
+}
@@ -0,0 +1,68 @@
+use crate::i18n::*;
+use tests_common::*;
+
+#[test]
+fn click_to_change_lang() {
+    let en = td!(Locale::en, click_to_change_lang);
+    assert_eq_rendered!(en, "Click to change language");
+    let fr = td!(Locale::fr, click_to_change_lang);
+    assert_eq_rendered!(fr, "Cliquez pour changez de langue");
+}
+
+#[test]
+fn click_count() {
+    for count in -5..5 {
+        let en = td!(Locale::en, click_count, count);
+        assert_eq_rendered!(en, format!("You clicked {} times", count));
+        let fr = td!(Locale::fr, click_count, count);
+        assert_eq_rendered!(fr, format!("Vous avez cliqué {} fois", count));
+    }
+}
+
+#[test]
+fn items() {
+    let count = move || 1;
+    let en = td!(Locale::en, items, count);
+    assert_eq_rendered!(en, "one item");
+    let fr = td!(Locale::fr, items, count);
+    assert_eq_rendered!(fr, "1 élément");
+
+    for i in [0, 2, 5, 1000] {
+        let count = move || i;
+        let en = td!(Locale::en, items, count);
+        assert_eq_rendered!(en, format!("{} items", i));
+    }
+
+    let count = move || 1_000_000;
+    let fr = td!(Locale::fr, items, count);
+    assert_eq_rendered!(fr, "1000000 d'éléments");
+}
+
+#[test]
+fn new_items() {
+    let count = move || 0;
+    let en = td!(Locale::en, new_items, count);
+    assert_eq_rendered!(en, "no new items");
+    let fr = td!(Locale::fr, new_items, count);
+    assert_eq_rendered!(fr, "aucun nouvel élément");
+
+    let count = move || 1;
+    let en = td!(Locale::en, new_items, count);
+    assert_eq_rendered!(en, "one new item");
+    let fr = td!(Locale::fr, new_items, count);
+    assert_eq_rendered!(fr, "1 nouvel élément");
+
+    let count = move || 5;
+    let en = td!(Locale::en, new_items, count);
+    assert_eq_rendered!(en, "5 new items");
+}
+
+#[test]
+fn invited() {
+    let en = td!(Locale::en, invited, gender = || selects::Gender::Female);
+    assert_eq_rendered!(en, "She invited you");
+    let fr = td!(Locale::fr, invited, gender = || selects::Gender::Male);
+    assert_eq_rendered!(fr, "Il vous a invité");
+    let fr = td!(Locale::fr, invited, gender = || "unknown");
+    assert_eq_rendered!(fr, "Iel vous a invité");
+}
@@ -0,0 +1,45 @@
+use crate::i18n::*;
+use tests_common::*;
+
+#[test]
+fn condition_else() {
+    let last_seen = "yesterday";
+    let en = td!(Locale::en, status_condition, online = || true, last_seen);
+    assert_eq_rendered!(en, "Online");
+    let en = td!(Locale::en, status_condition, online = || false, last_seen);
+    assert_eq_rendered!(en, "Last seen yesterday");
+    let fr = td!(Locale::fr, status_condition, online = || true, last_seen);
+    assert_eq_rendered!(fr, "En ligne");
+    let fr = td!(Locale::fr, status_condition, online = || false, last_seen);
+    assert_eq_rendered!(fr, "Vu yesterday");
+}
+
+#[test]
+fn condition_with_component() {
+    let b = |children: ChildrenFn| view! { <b>{children()}</b> };
+    let name = "Alice";
+    let en = td!(Locale::en, badge_condition, name, premium = || true, <b>);
+    assert_eq_rendered!(en, "Hello Alice <b>Pro</b>");
+    let en = td!(Locale::en, badge_condition, name, premium = || false, <b>);
+    assert_eq_rendered!(en, "Hello Alice");
+    let fr = td!(Locale::fr, badge_condition, name, premium = || true, <b>);
+    assert_eq_rendered!(fr, "Bonjour Alice <b>Pro</b>");
+}
+
+#[test]
+fn condition_string() {
+    let en = td_string!(
+        Locale::en,
+        status_condition,
+        online = true,
+        last_seen = "today"
+    );
+    assert_eq!(en, "Online");
+    let fr = td_string!(
+        Locale::fr,
+        status_condition,
+        online = false,
+        last_seen = "aujourd'hui"
+    );
+    assert_eq!(fr, "Vu aujourd'hui");
+}
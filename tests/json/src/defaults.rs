@@ -0,0 +1,52 @@
+use crate::i18n::*;
+use leptos_i18n::Locale as _;
+use tests_common::*;
+
+#[test]
+fn default_value() {
+    let en = td!(Locale::en, greeting_with_default);
+    assert_eq_rendered!(en, "Hello friend!");
+    let fr = td!(Locale::fr, greeting_with_default);
+    assert_eq_rendered!(fr, "Bonjour friend !");
+
+    let name = "Alice";
+    let en = td!(Locale::en, greeting_with_default, name);
+    assert_eq_rendered!(en, "Hello Alice!");
+    let fr = td!(Locale::fr, greeting_with_default, name);
+    assert_eq_rendered!(fr, "Bonjour Alice !");
+}
+
+#[test]
+fn default_value_string() {
+    let en = td_string!(Locale::en, greeting_with_default);
+    assert_eq!(en, "Hello friend!");
+    let fr = td_string!(Locale::fr, greeting_with_default, name = "Alice");
+    assert_eq!(fr, "Bonjour Alice !");
+}
+
+#[test]
+fn fill_defaults() {
+    let en = Locale::en
+        .get_keys()
+        .greeting_with_default()
+        .display_builder()
+        .fill_defaults()
+        .build_string();
+    assert_eq!(en, "Hello friend!");
+    let fr = Locale::fr
+        .get_keys()
+        .greeting_with_default()
+        .display_builder()
+        .var_name("Alice")
+        .fill_defaults()
+        .build_string();
+    assert_eq!(fr, "Bonjour Alice !");
+
+    let en = Locale::en
+        .get_keys()
+        .greeting_with_default()
+        .builder()
+        .fill_defaults()
+        .build();
+    assert_eq_rendered!(en.into_view(), "Hello friend!");
+}
@@ -4,12 +4,18 @@
 include!(concat!(env!("OUT_DIR"), "/i18n/mod.rs"));
 
 mod components;
+mod conditions;
 mod defaulted;
+mod defaults;
 mod foreign;
 mod formatting;
+mod lists;
+mod metadata;
 mod plurals;
 mod scoped;
+mod select;
 mod subkeys;
 mod t_format;
 mod t_plural;
 mod tests;
+mod typed;
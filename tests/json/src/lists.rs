@@ -0,0 +1,28 @@
+use crate::i18n::*;
+use tests_common::*;
+
+#[test]
+fn list() {
+    let en = td!(Locale::en, list_features);
+    assert_eq_rendered!(en, "Fast, Cheap");
+    let fr = td!(Locale::fr, list_features);
+    assert_eq_rendered!(fr, "Rapide, Bon marché");
+}
+
+#[test]
+fn list_string() {
+    let en = td_string!(Locale::en, list_features);
+    assert_eq!(en, "Fast, Cheap");
+    let fr = td_display!(Locale::fr, list_features).to_string();
+    assert_eq!(fr, "Rapide, Bon marché");
+}
+
+#[test]
+fn list_items() {
+    let features = Locale::fr.get_keys_const().list_features().inner();
+    assert_eq!(features.len(), 2);
+    assert_eq!(features.get(1), Some("Bon marché"));
+    assert_eq!(features.get(2), None);
+    assert_eq!(features.iter().collect::<Vec<_>>(), ["Rapide", "Bon marché"]);
+    assert_eq!(features.into_iter().rev().next(), Some("Bon marché"));
+}
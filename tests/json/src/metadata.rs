@@ -0,0 +1,31 @@
+use crate::i18n::*;
+use tests_common::*;
+
+#[test]
+fn key_with_metadata() {
+    let en = td!(Locale::en, checkout);
+    assert_eq_rendered!(en, "Checkout");
+    let fr = td!(Locale::fr, checkout);
+    assert_eq_rendered!(fr, "Payer");
+}
+
+// the accessors of deprecated keys are `#[deprecated]`, denied by `#![deny(warnings)]` otherwise
+#[test]
+#[allow(deprecated)]
+fn deprecated_key() {
+    let en = td!(Locale::en, old_checkout);
+    assert_eq_rendered!(en, "Checkout");
+    let fr = td!(Locale::fr, old_checkout);
+    assert_eq_rendered!(fr, "Payer");
+}
+
+#[test]
+#[allow(deprecated)]
+fn alias_key() {
+    let en = td!(Locale::en, checkout_alias);
+    assert_eq_rendered!(en, "Checkout");
+    let fr = td!(Locale::fr, checkout_alias);
+    assert_eq_rendered!(fr, "Payer");
+    let fr = td_string!(Locale::fr, checkout_alias);
+    assert_eq!(fr, "Payer");
+}
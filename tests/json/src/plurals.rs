@@ -99,3 +99,71 @@ fn renamed_plurals_count() {
     let fr = td!(Locale::fr, renamed_plurals_count, first_count, second_count);
     assert_eq_rendered!(fr, "0 1re place");
 }
+
+#[test]
+fn multiple_counts_plural() {
+    let en = td!(Locale::en, summary, files = || 1, folders = || 1);
+    assert_eq_rendered!(en, "1 file in 1 folder");
+    let en = td!(Locale::en, summary, files = || 3, folders = || 1);
+    assert_eq_rendered!(en, "3 files in 1 folder");
+    let en = td!(Locale::en, summary, files = || 1, folders = || 0);
+    assert_eq_rendered!(en, "1 file in 0 folders");
+    // 0 is singular in french
+    let fr = td!(Locale::fr, summary, files = || 0, folders = || 2);
+    assert_eq_rendered!(fr, "0 fichier dans 2 dossiers");
+    let fr = td!(Locale::fr, summary, files = || 5, folders = || 1);
+    assert_eq_rendered!(fr, "5 fichiers dans 1 dossier");
+    // millions use the "many" form in french
+    let fr = td!(Locale::fr, summary, files = || 1_000_000, folders = || 2);
+    assert_eq_rendered!(fr, "1000000 de fichiers dans 2 dossiers");
+
+    let en = td_string!(Locale::en, summary, files = 2, folders = 2);
+    assert_eq!(en, "2 files in 2 folders");
+}
+
+#[test]
+fn foreign_key_to_multiple_counts_plural() {
+    let en = td!(Locale::en, foreign_key_to_summary);
+    assert_eq_rendered!(en, "3 files in 1 folder");
+    let fr = td!(Locale::fr, foreign_key_to_summary);
+    assert_eq_rendered!(fr, "0 fichier dans 2 dossiers");
+}
+
+#[test]
+fn exact_value_plural() {
+    let en = td!(Locale::en, exact_plural, count = || 0);
+    assert_eq_rendered!(en, "No items");
+    let en = td!(Locale::en, exact_plural, count = || 1);
+    assert_eq_rendered!(en, "1 item");
+    let en = td!(Locale::en, exact_plural, count = || 2);
+    assert_eq_rendered!(en, "2 items");
+    let fr = td!(Locale::fr, exact_plural, count = || 0);
+    assert_eq_rendered!(fr, "Aucun élément");
+    let fr = td!(Locale::fr, exact_plural, count = || 1);
+    assert_eq_rendered!(fr, "1 élément");
+    let fr = td!(Locale::fr, exact_plural, count = || 1_000_000);
+    assert_eq_rendered!(fr, "1000000 d'éléments");
+
+    let en = td_string!(Locale::en, exact_plural, count = 0);
+    assert_eq!(en, "No items");
+}
+
+#[test]
+fn offset_plural() {
+    // exact forms match the count, the categories and the count variable use the count minus the offset
+    let en = td!(Locale::en, offset_plural, count = || 0);
+    assert_eq_rendered!(en, "Nobody is coming");
+    let en = td!(Locale::en, offset_plural, count = || 1);
+    assert_eq_rendered!(en, "You are coming");
+    let en = td!(Locale::en, offset_plural, count = || 2);
+    assert_eq_rendered!(en, "You and 1 other are coming");
+    let en = td!(Locale::en, offset_plural, count = || 3);
+    assert_eq_rendered!(en, "You and 2 others are coming");
+    let fr = td!(Locale::fr, offset_plural, count = || 2);
+    assert_eq_rendered!(fr, "Vous et 1 autre venez");
+    let fr = td!(Locale::fr, offset_plural, count = || 4);
+    assert_eq_rendered!(fr, "Vous et 3 autres venez");
+
+    let en = td_string!(Locale::en, offset_plural, count = 3);
+    assert_eq!(en, "You and 2 others are coming");
+}
@@ -0,0 +1,30 @@
+use crate::i18n::*;
+use tests_common::*;
+
+#[test]
+fn select() {
+    let en = td!(Locale::en, select_gender, gender = || selects::Gender::Male, name = "Alice", <b> = <b/>);
+    assert_eq_rendered!(en, "He invited <b>Alice</b>");
+    let fr = td!(Locale::fr, select_gender, gender = || selects::Gender::Female, name = "Alice", <b> = <b/>);
+    assert_eq_rendered!(fr, "Elle a invité <b>Alice</b>");
+    let fr = td!(Locale::fr, select_gender, gender = || selects::Gender::Other, name = "Alice", <b> = <b/>);
+    assert_eq_rendered!(fr, "Iel a invité <b>Alice</b>");
+}
+
+#[test]
+fn select_str_variant() {
+    let en = td!(Locale::en, select_gender, gender = || "female", name = "Bob", <b> = <b/>);
+    assert_eq_rendered!(en, "She invited <b>Bob</b>");
+    // unknown variants fallback to `other`
+    let en = td!(Locale::en, select_gender, gender = || "unknown", name = "Bob", <b> = <b/>);
+    assert_eq_rendered!(en, "They invited <b>Bob</b>");
+}
+
+#[test]
+fn select_string() {
+    let en =
+        td_string!(Locale::en, select_gender, gender = selects::Gender::Male, name = "Alice", <b> = "b");
+    assert_eq!(en, "He invited <b>Alice</b>");
+    let fr = td_string!(Locale::fr, select_gender, gender = "female", name = "Alice", <b> = "b");
+    assert_eq!(fr, "Elle a invité <b>Alice</b>");
+}
@@ -57,3 +57,26 @@ fn subkey_2_string() {
     let fr = td_string!(Locale::fr, subkeys.subkey_2, <b>);
     assert_eq!(fr, "<span id=\"my_id\">subkey_2</span>");
 }
+
+#[test]
+fn subkeys_alias() {
+    let en = td!(Locale::en, dialog_buttons.ok);
+    assert_eq_rendered!(en, "Ok");
+    let en = td!(Locale::en, dialog_buttons.cancel);
+    assert_eq_rendered!(en, "Cancel");
+    // other locales can declare the subkeys themselves
+    let fr = td!(Locale::fr, dialog_buttons.ok);
+    assert_eq_rendered!(fr, "Valider");
+    let fr = td_string!(Locale::fr, dialog_buttons.cancel);
+    assert_eq!(fr, "Annuler");
+}
+
+#[test]
+fn subkeys_alias_in_every_locale() {
+    let en = td!(Locale::en, confirm_buttons.ok);
+    assert_eq_rendered!(en, "Ok");
+    let fr = td!(Locale::fr, confirm_buttons.ok);
+    assert_eq_rendered!(fr, "D'accord");
+    let fr = td!(Locale::fr, confirm_buttons.cancel);
+    assert_eq_rendered!(fr, "Annuler");
+}
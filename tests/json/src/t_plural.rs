@@ -55,3 +55,12 @@ fn ordinal_plural() {
     let fr = td_plural_ordinal!(Locale::fr, count = count, one => "one", two => "two", few => "few", _ => "other");
     assert_eq!(fr, "other");
 }
+
+#[test]
+fn exact_plural() {
+    for (i, expected) in [(0, "none"), (1, "one"), (2, "other"), (5, "five")] {
+        let count = move || i;
+        let en = td_plural!(Locale::en, count = count, =0 => "none", =5 => "five", one => "one", _ => "other");
+        assert_eq!(en, expected);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::i18n::*;
+use leptos_i18n::reexports::icu::datetime::input::Date;
+use tests_common::*;
+
+#[test]
+fn typed_variables() {
+    let en = td!(
+        Locale::en,
+        typed_variables,
+        name = || "Alice".to_string(),
+        count = || 3
+    );
+    assert_eq_rendered!(en, "Alice, you have 3 new messages");
+    let fr = td!(
+        Locale::fr,
+        typed_variables,
+        name = || "Alice".to_string(),
+        count = || 3
+    );
+    assert_eq_rendered!(fr, "Alice, vous avez 3 nouveaux messages");
+}
+
+#[test]
+fn typed_variables_string() {
+    let en = td_string!(Locale::en, typed_variables, name = "Bob", count = 1u32);
+    assert_eq!(en, "Bob, you have 1 new messages");
+    let name = String::from("Bob");
+    let fr = td_string!(Locale::fr, typed_variables, name = name.as_str(), count = 2);
+    assert_eq!(fr, "Bob, vous avez 2 nouveaux messages");
+}
+
+#[test]
+fn typed_date() {
+    let updated = move || Date::try_new_iso(1970, 1, 2).unwrap().to_any();
+    let en = td!(Locale::en, typed_date, updated);
+    assert_eq_rendered!(en, "Updated on Jan 2, 1970");
+    let fr = td!(Locale::fr, typed_date, updated);
+    assert_eq_rendered!(fr, "Mis à jour le 2 janv. 1970");
+}